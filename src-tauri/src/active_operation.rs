@@ -0,0 +1,53 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// 引擎当前正在进行的长时间操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OperationKind {
+    Installing,
+    Downloading,
+    Transcribing,
+    Correcting,
+}
+
+// 各引擎进行中的操作：(操作 id, 类型)，按开始顺序排列
+static ACTIVE_OPERATIONS: Lazy<Mutex<HashMap<String, Vec<(u64, OperationKind)>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static NEXT_OPERATION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 操作登记凭证，drop 时自动注销（包括出错提前返回的情况）
+pub struct OperationGuard {
+    engine: String,
+    id: u64,
+}
+
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        if let Ok(mut operations) = ACTIVE_OPERATIONS.lock() {
+            if let Some(list) = operations.get_mut(&self.engine) {
+                list.retain(|(id, _)| *id != self.id);
+            }
+        }
+    }
+}
+
+/// 登记一个进行中的操作，返回的凭证需保持到操作结束
+pub fn begin_operation(engine: &str, kind: OperationKind) -> OperationGuard {
+    let id = NEXT_OPERATION_ID.fetch_add(1, Ordering::SeqCst);
+    if let Ok(mut operations) = ACTIVE_OPERATIONS.lock() {
+        operations.entry(engine.to_string()).or_default().push((id, kind));
+    }
+    OperationGuard { engine: engine.to_string(), id }
+}
+
+/// 获取引擎当前的操作（多个操作同时进行时返回最近开始的一个），空闲时返回 None
+pub fn get_active_operation(engine: &str) -> Option<OperationKind> {
+    ACTIVE_OPERATIONS
+        .lock()
+        .ok()
+        .and_then(|operations| operations.get(engine).and_then(|list| list.last().map(|(_, kind)| *kind)))
+}
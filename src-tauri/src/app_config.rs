@@ -0,0 +1,60 @@
+use std::path::{Path, PathBuf};
+
+/// 应用名，用作配置和缓存目录名，可在编译时通过 VOSUB_APP_NAME 环境变量覆盖
+pub const APP_NAME: &str = match option_env!("VOSUB_APP_NAME") {
+    Some(name) => name,
+    None => "vosub",
+};
+
+/// 旧版本使用的配置目录名
+const LEGACY_APP_NAME: &str = "srt-editor";
+
+/// 应用配置根目录（~/.config/vosub），Python 环境、脚本和各项设置都存放在此
+pub fn app_config_dir() -> Result<PathBuf, String> {
+    let home_dir = dirs::home_dir()
+        .ok_or_else(|| "Failed to get home directory".to_string())?;
+
+    Ok(home_dir.join(".config").join(APP_NAME))
+}
+
+/// 把旧目录中新目录尚不存在的条目移动过去，返回移动的条目数
+fn merge_dir_into(legacy: &Path, target: &Path) -> Result<usize, String> {
+    let mut moved = 0;
+    let entries = std::fs::read_dir(legacy)
+        .map_err(|e| format!("读取旧配置目录失败: {}", e))?;
+    for entry in entries.flatten() {
+        let destination = target.join(entry.file_name());
+        if destination.exists() {
+            continue;
+        }
+        std::fs::rename(entry.path(), &destination)
+            .map_err(|e| format!("迁移 {} 失败: {}", entry.path().display(), e))?;
+        moved += 1;
+    }
+    Ok(moved)
+}
+
+/// 一次性迁移：旧版 ~/.config/srt-editor 存在时并入 ~/.config/vosub
+/// 新目录不存在时整体重命名；都存在时只移动新目录中没有的条目，旧目录清空后删除
+pub fn migrate_legacy_config_dir() -> Result<(), String> {
+    let target = app_config_dir()?;
+    let Some(legacy) = target.parent().map(|p| p.join(LEGACY_APP_NAME)) else {
+        return Ok(());
+    };
+    if legacy == target || !legacy.is_dir() {
+        return Ok(());
+    }
+
+    if !target.exists() {
+        std::fs::rename(&legacy, &target)
+            .map_err(|e| format!("迁移旧配置目录失败: {}", e))?;
+        log::info!("已将旧配置目录 {} 迁移到 {}", legacy.display(), target.display());
+        return Ok(());
+    }
+
+    let moved = merge_dir_into(&legacy, &target)?;
+    // 旧目录中剩下的都是新目录已有的条目，目录为空时才删除
+    let _ = std::fs::remove_dir(&legacy);
+    log::info!("已从旧配置目录 {} 迁移 {} 项到 {}", legacy.display(), moved, target.display());
+    Ok(())
+}
@@ -0,0 +1,311 @@
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::app_config::app_config_dir;
+use crate::firered_corrector::{get_firered_cpu_env_dir, get_firered_gpu_env_dir, get_firered_model_dir};
+use crate::sensevoice_transcriber::{
+    get_sensevoice_cpu_env_dir, get_sensevoice_gpu_env_dir, get_sensevoice_model_dir,
+};
+use crate::whisper_python_transcriber::{get_whisper_cpu_env_dir, get_whisper_gpu_env_dir, get_whisper_model_dir};
+
+// 备份包内的目录前缀
+const CONFIG_PREFIX: &str = "config";
+const HUGGINGFACE_PREFIX: &str = "huggingface";
+const MODELSCOPE_PREFIX: &str = "modelscope";
+
+// 符号链接的 unix 文件类型位（venv 中的 python 通常是符号链接）
+const S_IFMT: u32 = 0o170000;
+const S_IFLNK: u32 = 0o120000;
+
+/// 备份结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupSummary {
+    pub file_count: usize,
+    pub total_bytes: u64,
+}
+
+/// 获取应用配置目录（~/.config/vosub）
+fn get_config_dir() -> Result<PathBuf, String> {
+    app_config_dir()
+}
+
+/// 获取 ModelScope 缓存根目录（~/.cache/modelscope/hub）
+fn get_modelscope_hub_dir() -> Result<PathBuf, String> {
+    let model_dir = get_sensevoice_model_dir()?;
+    model_dir
+        .parent()
+        .map(|p| p.to_path_buf())
+        .ok_or_else(|| "无法获取 ModelScope 缓存目录".to_string())
+}
+
+/// 体积较大的环境目录（Python 虚拟环境，含旧版目录名）
+fn get_env_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = [
+        get_whisper_cpu_env_dir(),
+        get_whisper_gpu_env_dir(),
+        get_sensevoice_cpu_env_dir(),
+        get_sensevoice_gpu_env_dir(),
+        get_firered_cpu_env_dir(),
+        get_firered_gpu_env_dir(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    if let Ok(config_dir) = get_config_dir() {
+        dirs.push(config_dir.join("sensevoice-env"));
+        dirs.push(config_dir.join("firered-env"));
+    }
+
+    dirs
+}
+
+/// 备份源：(本地目录, 备份包内路径前缀)
+fn get_model_sources() -> Vec<(PathBuf, String)> {
+    let mut sources = Vec::new();
+
+    // Whisper 模型位于 HuggingFace 缓存中，只备份 faster-whisper 相关目录
+    if let Ok(hub_dir) = get_whisper_model_dir() {
+        if let Ok(entries) = std::fs::read_dir(&hub_dir) {
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if entry.path().is_dir() && name.to_lowercase().contains("whisper") {
+                    sources.push((entry.path(), format!("{}/{}", HUGGINGFACE_PREFIX, name)));
+                }
+            }
+        }
+    }
+
+    // SenseVoice / FireRedASR 模型位于 ModelScope 缓存中
+    for dir in [get_sensevoice_model_dir(), get_firered_model_dir()].into_iter().flatten() {
+        if let Some(name) = dir.file_name().map(|n| n.to_string_lossy().to_string()) {
+            sources.push((dir, format!("{}/{}", MODELSCOPE_PREFIX, name)));
+        }
+    }
+
+    sources
+}
+
+/// 递归写入目录到压缩包
+fn add_dir_to_zip(
+    zip: &mut ZipWriter<File>,
+    dir: &Path,
+    prefix: &str,
+    skip_dirs: &[PathBuf],
+    compression: CompressionMethod,
+    summary: &mut BackupSummary,
+) -> Result<(), String> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| format!("读取目录失败 {}: {}", dir.display(), e))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if skip_dirs.iter().any(|d| d == &path) {
+            continue;
+        }
+
+        let name = format!("{}/{}", prefix, entry.file_name().to_string_lossy());
+        let meta = std::fs::symlink_metadata(&path)
+            .map_err(|e| format!("读取文件信息失败 {}: {}", path.display(), e))?;
+
+        if meta.file_type().is_symlink() {
+            let target = std::fs::read_link(&path)
+                .map_err(|e| format!("读取符号链接失败 {}: {}", path.display(), e))?;
+            zip.add_symlink(name, target.to_string_lossy(), SimpleFileOptions::default())
+                .map_err(|e| format!("写入备份失败: {}", e))?;
+            summary.file_count += 1;
+        } else if meta.is_dir() {
+            add_dir_to_zip(zip, &path, &name, skip_dirs, compression, summary)?;
+        } else {
+            #[allow(unused_mut)]
+            let mut options = SimpleFileOptions::default()
+                .compression_method(compression)
+                .large_file(meta.len() >= u32::MAX as u64);
+
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                options = options.unix_permissions(meta.permissions().mode());
+            }
+
+            zip.start_file(name, options)
+                .map_err(|e| format!("写入备份失败: {}", e))?;
+            let mut file = File::open(&path)
+                .map_err(|e| format!("打开文件失败 {}: {}", path.display(), e))?;
+            std::io::copy(&mut file, zip)
+                .map_err(|e| format!("写入备份失败 {}: {}", path.display(), e))?;
+
+            summary.file_count += 1;
+            summary.total_bytes += meta.len();
+        }
+    }
+
+    Ok(())
+}
+
+/// 导出应用备份（zip）
+/// 默认只包含配置目录（脚本、激活环境配置、设置、最近文件、词典等）
+/// include_large: 同时备份 Python 环境和已下载的模型（可能有数 GB）
+pub fn export_app_backup(output_path: &str, include_large: bool) -> Result<BackupSummary, String> {
+    let config_dir = get_config_dir()?;
+    let env_dirs = get_env_dirs();
+
+    let file = File::create(output_path)
+        .map_err(|e| format!("创建备份文件失败: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    let mut summary = BackupSummary { file_count: 0, total_bytes: 0 };
+
+    // 配置目录：不备份环境时跳过各环境目录
+    let skip_dirs = if include_large { Vec::new() } else { env_dirs };
+    if config_dir.exists() {
+        add_dir_to_zip(&mut zip, &config_dir, CONFIG_PREFIX, &skip_dirs, CompressionMethod::Deflated, &mut summary)?;
+    }
+
+    // 模型文件本身已是压缩格式，直接存储以节省时间
+    if include_large {
+        for (dir, prefix) in get_model_sources() {
+            if dir.exists() {
+                add_dir_to_zip(&mut zip, &dir, &prefix, &[], CompressionMethod::Stored, &mut summary)?;
+            }
+        }
+    }
+
+    zip.finish().map_err(|e| format!("写入备份失败: {}", e))?;
+
+    log::info!(
+        "[Backup] 已导出备份到 {}：{} 个文件，{} 字节",
+        output_path, summary.file_count, summary.total_bytes
+    );
+    Ok(summary)
+}
+
+/// 根据备份包内路径计算恢复根目录与目标路径
+fn resolve_restore_path(entry_path: &Path) -> Result<Option<(PathBuf, PathBuf)>, String> {
+    let mut components = entry_path.components();
+    let root = match components.next() {
+        Some(c) => c.as_os_str().to_string_lossy().to_string(),
+        None => return Ok(None),
+    };
+    let rest = components.as_path();
+
+    let base = match root.as_str() {
+        CONFIG_PREFIX => get_config_dir()?,
+        HUGGINGFACE_PREFIX => get_whisper_model_dir()?,
+        MODELSCOPE_PREFIX => get_modelscope_hub_dir()?,
+        _ => return Ok(None),
+    };
+
+    let target = base.join(rest);
+    Ok(Some((base, target)))
+}
+
+/// 确认目录 dir（可尚未创建）解析符号链接后仍位于恢复根目录 base 内
+/// 备份中可以包含指向任意位置的符号链接（如虚拟环境的解释器），不能让后续条目经由它们写到根目录之外
+fn ensure_within_base(base: &Path, dir: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(base)
+        .map_err(|e| format!("创建目录失败 {}: {}", base.display(), e))?;
+    let canonical_base = base.canonicalize()
+        .map_err(|e| format!("解析路径失败 {}: {}", base.display(), e))?;
+
+    // 从最近的已存在祖先开始解析，尚未创建的部分不会包含符号链接
+    let existing = dir
+        .ancestors()
+        .find(|p| std::fs::symlink_metadata(p).is_ok())
+        .unwrap_or(base);
+    let resolved = existing.canonicalize()
+        .map_err(|e| format!("解析路径失败 {}: {}", existing.display(), e))?;
+    if !resolved.starts_with(&canonical_base) {
+        return Err(format!("备份条目指向恢复目录之外，已拒绝: {}", dir.display()));
+    }
+    Ok(())
+}
+
+/// 写入前移除目标位置已有的符号链接，避免经由链接写到别处
+fn remove_existing_symlink(target: &Path) {
+    let is_symlink = std::fs::symlink_metadata(target)
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false);
+    if is_symlink {
+        let _ = std::fs::remove_file(target);
+    }
+}
+
+/// 从备份恢复应用配置（以及备份中包含的环境和模型）
+/// 同名文件会被覆盖；恢复的 Python 环境依赖系统中相同位置的解释器，若无法启动需在设置中修复
+pub fn import_app_backup(backup_path: &str) -> Result<BackupSummary, String> {
+    let file = File::open(backup_path)
+        .map_err(|e| format!("打开备份文件失败: {}", e))?;
+    let mut archive = ZipArchive::new(file)
+        .map_err(|e| format!("无效的备份文件: {}", e))?;
+    let mut summary = BackupSummary { file_count: 0, total_bytes: 0 };
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)
+            .map_err(|e| format!("读取备份失败: {}", e))?;
+
+        // enclosed_name 会拒绝 "../" 等越界路径
+        let Some(entry_path) = entry.enclosed_name() else {
+            log::warn!("[Backup] 跳过不安全的路径: {}", entry.name());
+            continue;
+        };
+        let Some((base, target)) = resolve_restore_path(&entry_path)? else {
+            continue;
+        };
+
+        if entry.is_dir() {
+            ensure_within_base(&base, &target)?;
+            std::fs::create_dir_all(&target)
+                .map_err(|e| format!("创建目录失败 {}: {}", target.display(), e))?;
+            continue;
+        }
+
+        if let Some(parent) = target.parent() {
+            ensure_within_base(&base, parent)?;
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("创建目录失败 {}: {}", parent.display(), e))?;
+        }
+
+        remove_existing_symlink(&target);
+
+        let mode = entry.unix_mode();
+        if mode.map(|m| m & S_IFMT == S_IFLNK).unwrap_or(false) {
+            let mut link_target = String::new();
+            entry.read_to_string(&mut link_target)
+                .map_err(|e| format!("读取备份失败: {}", e))?;
+
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(&link_target, &target)
+                .map_err(|e| format!("创建符号链接失败 {}: {}", target.display(), e))?;
+            #[cfg(not(unix))]
+            log::warn!("[Backup] 当前平台不支持恢复符号链接: {} -> {}", target.display(), link_target);
+
+            summary.file_count += 1;
+            continue;
+        }
+
+        let mut out = File::create(&target)
+            .map_err(|e| format!("写入文件失败 {}: {}", target.display(), e))?;
+        let written = std::io::copy(&mut entry, &mut out)
+            .map_err(|e| format!("写入文件失败 {}: {}", target.display(), e))?;
+        out.flush().map_err(|e| format!("写入文件失败 {}: {}", target.display(), e))?;
+
+        #[cfg(unix)]
+        if let Some(mode) = mode {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = std::fs::set_permissions(&target, std::fs::Permissions::from_mode(mode & 0o7777));
+        }
+
+        summary.file_count += 1;
+        summary.total_bytes += written;
+    }
+
+    log::info!(
+        "[Backup] 已从 {} 恢复：{} 个文件，{} 字节",
+        backup_path, summary.file_count, summary.total_bytes
+    );
+    Ok(summary)
+}
@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+
+use crate::srt_parser::DictionaryEntry;
+use crate::app_config::app_config_dir;
+
+/// 词典文件路径（~/.config/vosub/dictionary.json）
+fn get_dictionary_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("dictionary.json"))
+}
+
+/// 读取词典（文件不存在时为空）
+pub fn load_dictionary() -> Result<Vec<DictionaryEntry>, String> {
+    let path = get_dictionary_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("读取词典失败: {}", e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("解析词典失败: {}", e))
+}
+
+fn save_dictionary(entries: &[DictionaryEntry]) -> Result<(), String> {
+    let path = get_dictionary_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("创建配置目录失败: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(entries)
+        .map_err(|e| format!("序列化词典失败: {}", e))?;
+    std::fs::write(&path, content)
+        .map_err(|e| format!("写入词典失败: {}", e))
+}
+
+/// 添加词条（已存在相同错词时更新替换词），返回更新后的词典
+pub fn add_entry(wrong: &str, right: &str) -> Result<Vec<DictionaryEntry>, String> {
+    let wrong = wrong.trim();
+    if wrong.is_empty() {
+        return Err("错词不能为空".to_string());
+    }
+
+    let mut entries = load_dictionary()?;
+    match entries.iter_mut().find(|e| e.wrong == wrong) {
+        Some(entry) => entry.right = right.trim().to_string(),
+        None => entries.push(DictionaryEntry {
+            wrong: wrong.to_string(),
+            right: right.trim().to_string(),
+        }),
+    }
+    save_dictionary(&entries)?;
+    Ok(entries)
+}
+
+/// 删除词条，返回更新后的词典
+pub fn remove_entry(wrong: &str) -> Result<Vec<DictionaryEntry>, String> {
+    let mut entries = load_dictionary()?;
+    entries.retain(|e| e.wrong != wrong.trim());
+    save_dictionary(&entries)?;
+    Ok(entries)
+}
@@ -0,0 +1,204 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const GB: u64 = 1024 * 1024 * 1024;
+
+// Python 环境安装所需空间估算（含 uv 下载缓存）
+// GPU 版本需要 CUDA 版 PyTorch，体积远大于 CPU 版本
+pub const CPU_ENV_REQUIRED_BYTES: u64 = 2 * GB;
+pub const GPU_ENV_REQUIRED_BYTES: u64 = 8 * GB;
+
+/// 磁盘空间检查结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskSpaceInfo {
+    /// 实际检查的路径（目标目录或其最近的已存在上级目录）
+    pub path: String,
+    pub free_bytes: u64,
+    pub required_bytes: u64,
+    pub enough: bool,
+}
+
+/// 目标目录可能尚未创建，向上查找最近的已存在目录
+fn existing_ancestor(path: &Path) -> Option<PathBuf> {
+    path.ancestors().find(|p| p.exists()).map(|p| p.to_path_buf())
+}
+
+fn format_gb(bytes: u64) -> String {
+    format!("{:.1} GB", bytes as f64 / GB as f64)
+}
+
+/// 检查目标目录所在卷的剩余空间
+pub fn check_disk_space(path: &Path, required_bytes: u64) -> Result<DiskSpaceInfo, String> {
+    let existing = existing_ancestor(path)
+        .ok_or_else(|| format!("无法定位目录所在磁盘: {}", path.display()))?;
+    let free_bytes = fs2::available_space(&existing)
+        .map_err(|e| format!("获取磁盘剩余空间失败: {}", e))?;
+
+    Ok(DiskSpaceInfo {
+        path: existing.to_string_lossy().to_string(),
+        free_bytes,
+        required_bytes,
+        enough: free_bytes >= required_bytes,
+    })
+}
+
+/// 下载/安装前预检：空间不足时直接返回错误，避免下载到一半磁盘写满
+/// 无法获取剩余空间时仅记录日志，不阻止后续操作
+pub fn ensure_disk_space(path: &Path, required_bytes: u64) -> Result<(), String> {
+    match check_disk_space(path, required_bytes) {
+        Ok(info) if !info.enough => Err(format!(
+            "磁盘空间不足：需要 {}，{} 所在磁盘仅剩 {}",
+            format_gb(required_bytes),
+            info.path,
+            format_gb(info.free_bytes)
+        )),
+        Ok(_) => Ok(()),
+        Err(e) => {
+            log::warn!("[DiskSpace] {}", e);
+            Ok(())
+        }
+    }
+}
+
+// 未完成下载的文件后缀（ModelScope 直连下载为 .part，HuggingFace 缓存为 .incomplete）
+const PARTIAL_SUFFIXES: &[&str] = &[".part", ".incomplete"];
+
+/// 递归收集修改时间早于 min_age 的未完成下载文件
+fn collect_partial_files(dir: &Path, min_age: Duration, out: &mut Vec<PathBuf>) {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            collect_partial_files(&path, min_age, out);
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !PARTIAL_SUFFIXES.iter().any(|suffix| name.ends_with(suffix)) {
+            continue;
+        }
+        // 仍在写入的文件修改时间很新，不会被清理
+        let old_enough = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age >= min_age);
+        if old_enough {
+            out.push(path);
+        }
+    }
+}
+
+/// 清理模型缓存目录（HuggingFace、ModelScope）中中断下载遗留的临时文件
+/// 返回已删除的文件路径
+pub fn cleanup_partial_downloads(min_age: Duration) -> Result<Vec<String>, String> {
+    let mut roots = vec![crate::whisper_python_transcriber::get_whisper_model_dir()?];
+    if let Some(modelscope_hub) = crate::sensevoice_transcriber::get_sensevoice_model_dir()?.parent() {
+        roots.push(modelscope_hub.to_path_buf());
+    }
+
+    let mut candidates = Vec::new();
+    for root in &roots {
+        collect_partial_files(root, min_age, &mut candidates);
+    }
+
+    let mut removed = Vec::new();
+    for path in candidates {
+        match std::fs::remove_file(&path) {
+            Ok(()) => removed.push(path.to_string_lossy().to_string()),
+            Err(e) => log::warn!("[DiskSpace] 删除临时文件失败 {}: {}", path.display(), e),
+        }
+    }
+
+    log::info!("[DiskSpace] 清理中断下载的临时文件 {} 个", removed.len());
+    Ok(removed)
+}
+
+/// 各引擎模型缓存的占用情况
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineUsage {
+    /// "whisper" | "sensevoice" | "firered"
+    pub engine: String,
+    pub bytes: u64,
+    pub model_count: usize,
+}
+
+/// 递归统计目录大小（不跟随符号链接，HuggingFace 快照中的链接指向 blobs，避免重复计算）
+fn dir_size(path: &Path) -> u64 {
+    let Ok(read_dir) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    read_dir
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(t) if t.is_dir() => dir_size(&entry.path()),
+            Ok(t) if t.is_file() => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            _ => 0,
+        })
+        .sum()
+}
+
+/// 把 root 下名称满足 name_filter 的子目录计入 usage（每个子目录算一个模型）
+fn add_model_dirs(usage: &mut EngineUsage, root: &Path, name_filter: impl Fn(&str) -> bool) {
+    let Ok(read_dir) = std::fs::read_dir(root) else {
+        return;
+    };
+    for entry in read_dir.flatten() {
+        let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+        if !is_dir || !name_filter(&entry.file_name().to_string_lossy()) {
+            continue;
+        }
+        usage.bytes += dir_size(&entry.path());
+        usage.model_count += 1;
+    }
+}
+
+/// ModelScope 组织下的模型占用，兼容三种缓存结构：
+/// hub/<org>/<model>、hub/models/<org>/<model> 与 hub/models--<org>--<model>
+fn modelscope_usage(engine: &str, org_dir: &Path) -> EngineUsage {
+    let mut usage = EngineUsage {
+        engine: engine.to_string(),
+        bytes: 0,
+        model_count: 0,
+    };
+    add_model_dirs(&mut usage, org_dir, |_| true);
+    if let (Some(hub), Some(org)) = (org_dir.parent(), org_dir.file_name()) {
+        add_model_dirs(&mut usage, &hub.join("models").join(org), |_| true);
+        let prefix = format!("models--{}--", org.to_string_lossy());
+        add_model_dirs(&mut usage, hub, |name| name.starts_with(&prefix));
+    }
+    usage
+}
+
+/// 统计 Whisper（HuggingFace 缓存）、SenseVoice 与 FireRed（ModelScope 缓存）的模型占用空间
+/// HuggingFace 缓存由多个应用共用，Whisper 只统计内置的 faster-whisper 模型和已登记的自定义仓库
+pub fn get_model_cache_usage() -> Result<Vec<EngineUsage>, String> {
+    let huggingface_hub = crate::whisper_python_transcriber::get_whisper_model_dir()?;
+    let sensevoice_dir = crate::sensevoice_transcriber::get_sensevoice_model_dir()?;
+    let firered_dir = crate::firered_corrector::get_firered_model_dir()?;
+
+    let custom_whisper_dirs: Vec<String> = crate::model_registry::get_registered_models("whisper")
+        .iter()
+        .map(|m| crate::whisper_python_transcriber::get_model_cache_dir_name(&m.name))
+        .collect();
+    let mut whisper = EngineUsage {
+        engine: "whisper".to_string(),
+        bytes: 0,
+        model_count: 0,
+    };
+    add_model_dirs(&mut whisper, &huggingface_hub, |name| {
+        name.starts_with("models--Systran--faster-whisper-") || custom_whisper_dirs.iter().any(|d| d == name)
+    });
+
+    Ok(vec![
+        whisper,
+        modelscope_usage("sensevoice", &sensevoice_dir),
+        modelscope_usage("firered", &firered_dir),
+    ])
+}
@@ -0,0 +1,204 @@
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::app_config::app_config_dir;
+
+// 未保存代理配置时读取的环境变量
+const PROXY_ENV_VAR: &str = "VOSUB_DOWNLOAD_PROXY";
+
+// 各模型源的默认地址
+const DEFAULT_HUGGINGFACE_URL: &str = "https://huggingface.co";
+const DEFAULT_MODELSCOPE_URL: &str = "https://modelscope.cn";
+
+/// 代理配置文件路径（~/.config/vosub/download-proxy）
+fn get_proxy_config_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("download-proxy"))
+}
+
+/// 模型下载使用的代理地址：优先读取保存的配置，其次读取 VOSUB_DOWNLOAD_PROXY 环境变量
+/// 环境变量中的地址不受支持时忽略并记录警告
+pub fn get_download_proxy() -> Option<String> {
+    let saved = get_proxy_config_path()
+        .ok()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+    if saved.is_some() {
+        return saved;
+    }
+
+    let url = std::env::var(PROXY_ENV_VAR).ok()?.trim().to_string();
+    if url.is_empty() {
+        return None;
+    }
+    match validate_proxy_url(&url) {
+        Ok(()) => Some(url),
+        Err(e) => {
+            log::warn!("忽略 {}: {}", PROXY_ENV_VAR, e);
+            None
+        }
+    }
+}
+
+/// 检查代理地址：只支持 http:// 和 https://
+/// Whisper 模型由 Python requests 下载，环境中未安装 PySocks，无法使用 SOCKS 代理
+fn validate_proxy_url(url: &str) -> Result<(), String> {
+    let lower = url.to_ascii_lowercase();
+    if !lower.starts_with("http://") && !lower.starts_with("https://") {
+        return Err(format!("代理地址必须以 http:// 或 https:// 开头: {}", url));
+    }
+    reqwest::Proxy::all(url).map_err(|e| format!("无效的代理地址 {}: {}", url, e))?;
+    Ok(())
+}
+
+/// 保存代理地址（支持 http://、https://），传入空字符串清除
+pub fn set_download_proxy(url: &str) -> Result<(), String> {
+    let config_path = get_proxy_config_path()?;
+    let url = url.trim();
+    if url.is_empty() {
+        if config_path.exists() {
+            std::fs::remove_file(&config_path)
+                .map_err(|e| format!("删除代理配置失败: {}", e))?;
+        }
+        return Ok(());
+    }
+
+    validate_proxy_url(url)?;
+    if let Some(parent) = config_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("创建配置目录失败: {}", e))?;
+    }
+    std::fs::write(&config_path, url)
+        .map_err(|e| format!("写入代理配置失败: {}", e))
+}
+
+/// 创建下载用的 HTTP 客户端构建器（已配置代理时所有请求走代理）
+pub fn http_client_builder() -> Result<reqwest::ClientBuilder, String> {
+    let builder = reqwest::Client::builder();
+    match get_download_proxy() {
+        Some(url) => {
+            let proxy = reqwest::Proxy::all(&url)
+                .map_err(|e| format!("无效的代理地址 {}: {}", url, e))?;
+            Ok(builder.proxy(proxy))
+        }
+        None => Ok(builder),
+    }
+}
+
+// ============ 模型镜像 ============
+
+fn default_mirror(service: &str) -> Result<&'static str, String> {
+    match service {
+        "huggingface" => Ok(DEFAULT_HUGGINGFACE_URL),
+        "modelscope" => Ok(DEFAULT_MODELSCOPE_URL),
+        _ => Err(format!("不支持的模型源: {}（可选 huggingface、modelscope）", service)),
+    }
+}
+
+/// 镜像配置文件路径（~/.config/vosub/model-mirror-<service>）
+fn get_mirror_config_path(service: &str) -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join(format!("model-mirror-{}", service)))
+}
+
+/// 模型源的基础地址（如 https://hf-mirror.com），未配置时为官方地址，末尾不带 /
+pub fn get_model_mirror(service: &str) -> Result<String, String> {
+    let default = default_mirror(service)?;
+    Ok(get_mirror_config_path(service)
+        .ok()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .map(|s| s.trim().trim_end_matches('/').to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| default.to_string()))
+}
+
+/// 设置模型源镜像地址，传入空字符串恢复官方地址
+pub fn set_model_mirror(service: &str, base_url: &str) -> Result<(), String> {
+    default_mirror(service)?;
+    let config_path = get_mirror_config_path(service)?;
+    let base_url = base_url.trim().trim_end_matches('/');
+    if base_url.is_empty() {
+        if config_path.exists() {
+            std::fs::remove_file(&config_path)
+                .map_err(|e| format!("删除镜像配置失败: {}", e))?;
+        }
+        return Ok(());
+    }
+
+    if !base_url.starts_with("http://") && !base_url.starts_with("https://") {
+        return Err(format!("镜像地址必须以 http:// 或 https:// 开头: {}", base_url));
+    }
+    if let Some(parent) = config_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("创建配置目录失败: {}", e))?;
+    }
+    std::fs::write(&config_path, base_url)
+        .map_err(|e| format!("写入镜像配置失败: {}", e))
+}
+
+/// 传给 Python 下载进程的环境变量：代理（未配置时不设置）与 HuggingFace 地址（HF_ENDPOINT）
+pub fn download_env() -> Vec<(&'static str, String)> {
+    let mut envs = Vec::new();
+    if let Some(url) = get_download_proxy() {
+        envs.push(("HTTP_PROXY", url.clone()));
+        envs.push(("HTTPS_PROXY", url.clone()));
+        envs.push(("ALL_PROXY", url));
+    }
+    if let Ok(endpoint) = get_model_mirror("huggingface") {
+        envs.push(("HF_ENDPOINT", endpoint));
+    }
+    envs
+}
+
+// ============ 下载进度 ============
+
+/// 模型下载进度（在原有 progress/current_text/status 基础上增加字节数与速度）
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadProgress {
+    pub progress: f32,
+    pub current_text: String,
+    pub status: String,
+    pub downloaded_bytes: u64,
+    pub total_bytes: u64,
+    /// 当前下载速度（字节/秒）
+    pub speed_bps: u64,
+}
+
+// 速度采样间隔
+const SPEED_SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 下载速度采样器：按固定间隔统计收到的字节数，并做平滑避免数值跳动
+pub struct SpeedSampler {
+    window_start: Instant,
+    window_bytes: u64,
+    speed_bps: u64,
+}
+
+impl Default for SpeedSampler {
+    fn default() -> Self {
+        Self {
+            window_start: Instant::now(),
+            window_bytes: 0,
+            speed_bps: 0,
+        }
+    }
+}
+
+impl SpeedSampler {
+    /// 记录新收到的字节数，返回当前速度（字节/秒）
+    pub fn record(&mut self, bytes: u64) -> u64 {
+        self.window_bytes += bytes;
+        let elapsed = self.window_start.elapsed();
+        if elapsed >= SPEED_SAMPLE_INTERVAL {
+            let sample = (self.window_bytes as f64 / elapsed.as_secs_f64()) as u64;
+            self.speed_bps = if self.speed_bps == 0 {
+                sample
+            } else {
+                (self.speed_bps * 7 + sample * 3) / 10
+            };
+            self.window_start = Instant::now();
+            self.window_bytes = 0;
+        }
+        self.speed_bps
+    }
+}
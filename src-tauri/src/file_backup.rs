@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::srt_parser::{read_srt_file, write_srt_file, SubtitleEntry};
+use crate::app_config::app_config_dir;
+
+// 每个字幕文件最多保留的备份数量
+const MAX_BACKUPS_PER_FILE: usize = 10;
+
+// 备份存放在字幕文件所在目录的子文件夹中
+const BACKUP_DIR_NAME: &str = "backups";
+
+/// 备份开关配置文件路径（~/.config/vosub/srt-backup-enabled）
+fn get_backup_config_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("srt-backup-enabled"))
+}
+
+/// 保存前是否备份原文件（默认开启）
+pub fn is_backup_enabled() -> bool {
+    get_backup_config_path()
+        .ok()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .map(|s| s.trim() != "false")
+        .unwrap_or(true)
+}
+
+/// 设置保存前是否备份原文件
+pub fn set_backup_enabled(enabled: bool) -> Result<(), String> {
+    let config_path = get_backup_config_path()?;
+    if let Some(parent) = config_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("创建配置目录失败: {}", e))?;
+    }
+    std::fs::write(&config_path, if enabled { "true" } else { "false" })
+        .map_err(|e| format!("写入配置失败: {}", e))
+}
+
+/// 覆盖已有文件前将其复制到 backups/<文件名>.bak-<unix 毫秒时间戳>
+/// 文件不存在或备份已关闭时返回 None；超出数量上限的旧备份会被删除
+pub fn backup_before_overwrite(file_path: &str) -> Result<Option<PathBuf>, String> {
+    let path = Path::new(file_path);
+    if !path.is_file() || !is_backup_enabled() {
+        return Ok(None);
+    }
+
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| format!("无效的文件路径: {}", file_path))?;
+    let backup_dir = path
+        .parent()
+        .map(|p| p.join(BACKUP_DIR_NAME))
+        .ok_or_else(|| format!("无效的文件路径: {}", file_path))?;
+    std::fs::create_dir_all(&backup_dir)
+        .map_err(|e| format!("创建备份目录失败: {}", e))?;
+
+    let mut timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    // 同一毫秒内多次保存时顺延时间戳，避免覆盖刚写入的备份
+    let mut backup_path = backup_dir.join(format!("{}.bak-{}", file_name, timestamp));
+    while backup_path.exists() {
+        timestamp += 1;
+        backup_path = backup_dir.join(format!("{}.bak-{}", file_name, timestamp));
+    }
+    std::fs::copy(path, &backup_path)
+        .map_err(|e| format!("备份文件失败: {}", e))?;
+
+    prune_backups(&backup_dir, &file_name);
+
+    Ok(Some(backup_path))
+}
+
+/// 删除最旧的备份，只保留 MAX_BACKUPS_PER_FILE 个
+fn prune_backups(backup_dir: &Path, file_name: &str) {
+    let prefix = format!("{}.bak-", file_name);
+    let Ok(entries) = std::fs::read_dir(backup_dir) else {
+        return;
+    };
+
+    let mut backups: Vec<(u64, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let timestamp = name.strip_prefix(&prefix)?.parse::<u64>().ok()?;
+            Some((timestamp, entry.path()))
+        })
+        .collect();
+
+    if backups.len() <= MAX_BACKUPS_PER_FILE {
+        return;
+    }
+
+    backups.sort_by_key(|(timestamp, _)| *timestamp);
+    let excess = backups.len() - MAX_BACKUPS_PER_FILE;
+    for (_, path) in backups.into_iter().take(excess) {
+        if let Err(e) = std::fs::remove_file(&path) {
+            log::warn!("[FileBackup] 删除旧备份失败 {}: {}", path.display(), e);
+        }
+    }
+}
+
+// ============ 自动保存 ============
+
+/// 自动保存文件信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutosaveInfo {
+    pub path: String,
+    /// 自动保存文件的修改时间（unix 秒）
+    pub modified: u64,
+    pub entry_count: usize,
+}
+
+/// 自动保存文件路径：<原文件>.autosave
+fn autosave_path(original_path: &str) -> PathBuf {
+    PathBuf::from(format!("{}.autosave", original_path))
+}
+
+fn modified_secs(path: &Path) -> Option<u64> {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+}
+
+/// 将未保存的编辑写入旁路文件，返回自动保存文件路径
+pub fn autosave_srt(original_path: &str, entries: &[SubtitleEntry]) -> Result<String, String> {
+    let path = autosave_path(original_path);
+    let path_str = path.to_string_lossy().to_string();
+    write_srt_file(&path_str, entries)?;
+    Ok(path_str)
+}
+
+/// 检查是否存在比原文件更新的自动保存（用于崩溃后恢复）
+pub fn check_autosave(original_path: &str) -> Option<AutosaveInfo> {
+    let path = autosave_path(original_path);
+    let modified = modified_secs(&path)?;
+
+    // 原文件不存在（如新建后未保存）时自动保存总是有效
+    if let Some(original_modified) = modified_secs(Path::new(original_path)) {
+        if modified <= original_modified {
+            return None;
+        }
+    }
+
+    let path_str = path.to_string_lossy().to_string();
+    let entry_count = read_srt_file(&path_str).ok()?.entries.len();
+    Some(AutosaveInfo { path: path_str, modified, entry_count })
+}
+
+/// 删除自动保存文件（正常保存后调用）
+pub fn remove_autosave(original_path: &str) {
+    let path = autosave_path(original_path);
+    if path.exists() {
+        if let Err(e) = std::fs::remove_file(&path) {
+            log::warn!("[FileBackup] 删除自动保存文件失败 {}: {}", path.display(), e);
+        }
+    }
+}
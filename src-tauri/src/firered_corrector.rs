@@ -1,4 +1,6 @@
-use crate::srt_parser::TimeStamp;
+use crate::model_registry::{format_model_size, get_model_label, get_registered_models, register_model, verify_model_files, FileStatus, RegisteredModel};
+use crate::active_operation::{begin_operation, OperationKind};
+use crate::srt_parser::{apply_dictionary_to_text, DictionaryEntry, SubtitleEntry, TimeStamp};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::process::Command;
@@ -57,6 +59,7 @@ pub struct FireRedProgress {
 pub struct FireRedEnvInfo {
     pub installed: bool,
     pub ready: bool,
+    pub broken: bool,  // 依赖已安装但 Python 解释器无法启动（需要修复）
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,6 +81,8 @@ pub struct FireRedModelInfo {
     pub size: String,
     pub downloaded: bool,
     pub partial_size: Option<u64>,
+    /// 用户自定义的显示名称
+    pub label: Option<String>,
 }
 
 /// FireRedASR 模型文件信息
@@ -106,6 +111,9 @@ pub struct CorrectionEntry {
     pub original: String,
     pub corrected: String,
     pub has_diff: bool,
+    /// 识别置信度（0-1，解码得分按 token 数归一化），识别失败时为空
+    #[serde(default)]
+    pub confidence: Option<f32>,
 }
 
 /// Python 脚本输出的校正结果
@@ -122,14 +130,13 @@ struct CorrectionEntryRaw {
     original: String,
     corrected: String,
     has_diff: bool,
+    #[serde(default)]
+    confidence: Option<f32>,
 }
 
 /// 获取 FireRedASR 环境基础目录
 fn get_firered_base_dir() -> Result<PathBuf, String> {
-    let home_dir = dirs::home_dir()
-        .ok_or_else(|| "Failed to get home directory".to_string())?;
-    
-    Ok(home_dir.join(".config").join("vosub"))
+    crate::app_config::app_config_dir()
 }
 
 /// 获取旧版 FireRedASR 环境目录（用于迁移）
@@ -334,6 +341,28 @@ fn check_firered_model_files_exist(model_path: &std::path::Path) -> bool {
     model_pth.exists() && config.exists()
 }
 
+/// 下载模型还需要的磁盘空间（扣除已下载部分）
+fn firered_model_required_bytes(model_name: &str) -> u64 {
+    let total_size: u64 = FIRERED_AED_L_FILES.iter().map(|f| f.size).sum();
+    total_size.saturating_sub(get_firered_partial_size(model_name))
+}
+
+/// 下载前预检模型所在磁盘的剩余空间（已下载的模型不需要额外空间）
+pub fn check_firered_model_disk_space(model_name: &str) -> Result<crate::disk_space::DiskSpaceInfo, String> {
+    let required = if is_firered_model_downloaded(model_name) {
+        0
+    } else {
+        firered_model_required_bytes(model_name)
+    };
+    crate::disk_space::check_disk_space(&get_firered_model_path(model_name)?, required)
+}
+
+/// 按文件清单检查模型文件是否缺失或大小不符（用于排查手动拷贝不完整的模型）
+pub fn verify_firered_model(model_name: &str) -> Result<Vec<FileStatus>, String> {
+    let manifest: Vec<(&str, u64)> = FIRERED_AED_L_FILES.iter().map(|f| (f.name, f.size)).collect();
+    Ok(verify_model_files(&get_firered_model_path(model_name)?, &manifest))
+}
+
 /// 获取已下载的部分大小
 pub fn get_firered_partial_size(model_name: &str) -> u64 {
     let model_path = match get_firered_model_path(model_name) {
@@ -372,21 +401,165 @@ pub fn get_firered_models() -> Vec<FireRedModelInfo> {
         None
     };
     
-    vec![
+    let mut models = vec![
         FireRedModelInfo {
             name: "FireRedASR-AED-L".to_string(),
             size: "~4.4 GB".to_string(),
             downloaded,
             partial_size,
+            label: get_model_label("firered", "FireRedASR-AED-L"),
         },
-    ]
+    ];
+    
+    // 追加登记表中的其他模型（如手动导入的模型目录）
+    for model in get_registered_models("firered") {
+        if models.iter().any(|m| m.name == model.name) {
+            continue;
+        }
+        models.push(FireRedModelInfo {
+            downloaded: !model.path.is_empty() && std::path::Path::new(&model.path).exists(),
+            size: if model.size > 0 { format_model_size(model.size) } else { "未知".to_string() },
+            partial_size: None,
+            label: model.label,
+            name: model.name,
+        });
+    }
+    
+    models
 }
 
-/// 下载 FireRedASR 模型（支持断点续传）
-pub async fn download_firered_model(model_name: &str, window: Window) -> Result<String, String> {
+/// 同时下载的模型文件数
+const FIRERED_DOWNLOAD_CONCURRENCY: usize = 3;
+
+/// 各文件下载任务共享的状态，已下载字节数汇总到原子计数器以计算整体进度
+struct FireRedFileDownload<'a> {
+    client: &'a reqwest::Client,
+    base_url: String,
+    model_path: &'a std::path::Path,
+    task_id: u64,
+    downloaded: AtomicU64,
+    total_size: u64,
+    speed_sampler: std::sync::Mutex<crate::download_config::SpeedSampler>,
+    window: &'a Window,
+}
+
+/// 下载单个模型文件（写入 .part，完成并校验大小后重命名，支持断点续传）
+async fn download_firered_file(ctx: &FireRedFileDownload<'_>, file_info: &ModelFileInfo) -> Result<(), String> {
     use std::fs::{self, OpenOptions};
     use std::io::Write;
     
+    // 检查任务是否仍然有效
+    if !is_firered_model_download_task_valid(ctx.task_id) {
+        return Err("下载已取消".to_string());
+    }
+    
+    let file_path = ctx.model_path.join(file_info.name);
+    let part_path = ctx.model_path.join(format!("{}.part", file_info.name));
+    
+    // 如果文件已存在且大小正确，跳过
+    if fs::metadata(&file_path).map(|m| m.len() == file_info.size).unwrap_or(false) {
+        ctx.downloaded.fetch_add(file_info.size, Ordering::SeqCst);
+        return Ok(());
+    }
+    
+    // 检查部分下载
+    let existing_size = if part_path.exists() {
+        fs::metadata(&part_path).map(|m| m.len()).unwrap_or(0)
+    } else {
+        0
+    };
+    
+    let download_url = format!("{}/{}", ctx.base_url, file_info.name);
+    
+    // 构建请求
+    let mut request = ctx.client.get(&download_url);
+    if existing_size > 0 {
+        request = request.header("Range", format!("bytes={}-", existing_size));
+    }
+    
+    let response = request.send().await
+        .map_err(|e| format!("下载 {} 失败: {}", file_info.name, e))?;
+    
+    let status = response.status();
+    let is_partial = status == reqwest::StatusCode::PARTIAL_CONTENT;
+    
+    if !status.is_success() && !is_partial {
+        return Err(format!("下载 {} 失败: HTTP {}", file_info.name, status));
+    }
+    
+    // 确定实际起始位置
+    let actual_start = if is_partial { existing_size } else {
+        if existing_size > 0 {
+            let _ = fs::remove_file(&part_path);
+        }
+        0
+    };
+    
+    // 打开文件
+    let mut file = if actual_start > 0 {
+        OpenOptions::new()
+            .append(true)
+            .open(&part_path)
+            .map_err(|e| format!("打开部分文件失败: {}", e))?
+    } else {
+        fs::File::create(&part_path)
+            .map_err(|e| format!("创建文件失败: {}", e))?
+    };
+    ctx.downloaded.fetch_add(actual_start, Ordering::SeqCst);
+    
+    // 流式下载
+    let mut response = response;
+    while let Some(chunk) = response.chunk().await
+        .map_err(|e| format!("读取数据失败: {}", e))? 
+    {
+        // 检查任务是否仍然有效
+        if !is_firered_model_download_task_valid(ctx.task_id) {
+            return Err("下载已取消".to_string());
+        }
+        
+        file.write_all(&chunk)
+            .map_err(|e| format!("写入文件失败: {}", e))?;
+        
+        let chunk_len = chunk.len() as u64;
+        let current_total = ctx.downloaded.fetch_add(chunk_len, Ordering::SeqCst) + chunk_len;
+        let speed_bps = ctx.speed_sampler.lock().map(|mut s| s.record(chunk_len)).unwrap_or(0);
+        
+        // 更新进度
+        let progress = (current_total as f32 / ctx.total_size as f32) * 100.0;
+        let _ = ctx.window.emit("firered-model-progress", crate::download_config::DownloadProgress {
+            progress,
+            current_text: format!("{:.1}%", progress),
+            status: "downloading".to_string(),
+            downloaded_bytes: current_total,
+            total_bytes: ctx.total_size,
+            speed_bps,
+        });
+    }
+    
+    file.flush().map_err(|e| format!("刷新文件失败: {}", e))?;
+    drop(file);
+    
+    // 验证文件大小
+    let final_size = fs::metadata(&part_path).map(|m| m.len()).unwrap_or(0);
+    if final_size != file_info.size {
+        return Err(format!(
+            "文件 {} 下载不完整: 期望 {} 字节, 实际 {} 字节",
+            file_info.name, file_info.size, final_size
+        ));
+    }
+    
+    // 重命名为最终文件
+    fs::rename(&part_path, &file_path)
+        .map_err(|e| format!("重命名文件失败: {}", e))
+}
+
+/// 下载 FireRedASR 模型（支持断点续传）
+pub async fn download_firered_model(model_name: &str, window: Window) -> Result<String, String> {
+    use futures_util::{StreamExt, TryStreamExt};
+    use std::fs;
+    
+    let _operation = begin_operation("firered", OperationKind::Downloading);
+    
     // 生成新的任务ID，使之前的下载任务失效
     let task_id = new_firered_model_download_task_id();
     
@@ -411,7 +584,9 @@ pub async fn download_firered_model(model_name: &str, window: Window) -> Result<
     
     // 计算总大小
     let total_size: u64 = FIRERED_AED_L_FILES.iter().map(|f| f.size).sum();
-    let mut downloaded_total: u64 = 0;
+    
+    // 预检磁盘空间（扣除已下载部分）
+    crate::disk_space::ensure_disk_space(&model_path, firered_model_required_bytes(model_name))?;
     
     // 发送初始进度
     let _ = window.emit("firered-model-progress", FireRedProgress {
@@ -420,132 +595,29 @@ pub async fn download_firered_model(model_name: &str, window: Window) -> Result<
         status: "downloading".to_string(),
     });
     
-    let client = reqwest::Client::builder()
+    let modelscope_url = crate::download_config::get_model_mirror("modelscope")?;
+    let client = crate::download_config::http_client_builder()?
         .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
         .build()
         .map_err(|e| format!("创建 HTTP 客户端失败: {}", e))?;
     
-    // 下载每个文件
-    for file_info in FIRERED_AED_L_FILES.iter() {
-        // 检查任务是否仍然有效
-        if !is_firered_model_download_task_valid(task_id) {
-            return Err("下载已取消".to_string());
-        }
-        
-        let file_path = model_path.join(file_info.name);
-        let part_path = model_path.join(format!("{}.part", file_info.name));
-        
-        // 如果文件已存在且大小正确，跳过
-        if file_path.exists() {
-            if let Ok(meta) = fs::metadata(&file_path) {
-                if meta.len() == file_info.size {
-                    downloaded_total += file_info.size;
-                    continue;
-                }
-            }
-        }
-        
-        // 检查部分下载
-        let existing_size = if part_path.exists() {
-            fs::metadata(&part_path).map(|m| m.len()).unwrap_or(0)
-        } else {
-            0
-        };
-        
-        // 构建下载 URL
-        let download_url = format!(
-            "https://modelscope.cn/models/FireRedTeam/{}/resolve/master/{}",
-            model_name, file_info.name
-        );
-        
-        // 发送进度
-        let progress = (downloaded_total as f32 / total_size as f32) * 100.0;
-        let _ = window.emit("firered-model-progress", FireRedProgress {
-            progress,
-            current_text: format!("{:.1}%", progress),
-            status: "downloading".to_string(),
-        });
-        
-        // 构建请求
-        let mut request = client.get(&download_url);
-        if existing_size > 0 {
-            request = request.header("Range", format!("bytes={}-", existing_size));
-        }
-        
-        let response = request.send().await
-            .map_err(|e| format!("下载 {} 失败: {}", file_info.name, e))?;
-        
-        let status = response.status();
-        let is_partial = status == reqwest::StatusCode::PARTIAL_CONTENT;
-        
-        if !status.is_success() && !is_partial {
-            return Err(format!("下载 {} 失败: HTTP {}", file_info.name, status));
-        }
-        
-        // 确定实际起始位置
-        let actual_start = if is_partial { existing_size } else {
-            if existing_size > 0 {
-                let _ = fs::remove_file(&part_path);
-            }
-            0
-        };
-        
-        // 打开文件
-        let (mut file, mut file_downloaded) = if actual_start > 0 {
-            let file = OpenOptions::new()
-                .append(true)
-                .open(&part_path)
-                .map_err(|e| format!("打开部分文件失败: {}", e))?;
-            (file, actual_start)
-        } else {
-            let file = fs::File::create(&part_path)
-                .map_err(|e| format!("创建文件失败: {}", e))?;
-            (file, 0u64)
-        };
-        
-        // 流式下载
-        let mut response = response;
-        while let Some(chunk) = response.chunk().await
-            .map_err(|e| format!("读取数据失败: {}", e))? 
-        {
-            // 检查任务是否仍然有效
-            if !is_firered_model_download_task_valid(task_id) {
-                return Err("下载已取消".to_string());
-            }
-            
-            file.write_all(&chunk)
-                .map_err(|e| format!("写入文件失败: {}", e))?;
-            
-            file_downloaded += chunk.len() as u64;
-            
-            // 更新进度
-            let current_total = downloaded_total + file_downloaded;
-            let progress = (current_total as f32 / total_size as f32) * 100.0;
-            let _ = window.emit("firered-model-progress", FireRedProgress {
-                progress,
-                current_text: format!("{:.1}%", progress),
-                status: "downloading".to_string(),
-            });
-        }
-        
-        file.flush().map_err(|e| format!("刷新文件失败: {}", e))?;
-        drop(file);
-        
-        // 验证文件大小
-        let final_size = fs::metadata(&part_path).map(|m| m.len()).unwrap_or(0);
-        if final_size != file_info.size {
-            return Err(format!(
-                "文件 {} 下载不完整: 期望 {} 字节, 实际 {} 字节",
-                file_info.name, file_info.size, final_size
-            ));
-        }
-        
-        // 重命名为最终文件
-        fs::rename(&part_path, &file_path)
-            .map_err(|e| format!("重命名文件失败: {}", e))?;
-        
-        downloaded_total += file_info.size;
-    }
+    let context = FireRedFileDownload {
+        client: &client,
+        base_url: format!("{}/models/FireRedTeam/{}/resolve/master", modelscope_url, model_name),
+        model_path: &model_path,
+        task_id,
+        downloaded: AtomicU64::new(0),
+        total_size,
+        speed_sampler: std::sync::Mutex::new(crate::download_config::SpeedSampler::default()),
+        window: &window,
+    };
+    
+    // 大文件排在清单首位最先开始，其余小文件在剩余并发槽位中同时下载
+    futures_util::stream::iter(FIRERED_AED_L_FILES.iter())
+        .map(|file_info| download_firered_file(&context, file_info))
+        .buffer_unordered(FIRERED_DOWNLOAD_CONCURRENCY)
+        .try_collect::<Vec<()>>()
+        .await?;
     
     // 发送完成进度
     let _ = window.emit("firered-model-progress", FireRedProgress {
@@ -554,6 +626,18 @@ pub async fn download_firered_model(model_name: &str, window: Window) -> Result<
         status: "completed".to_string(),
     });
     
+    let registered = register_model(RegisteredModel {
+        name: model_name.to_string(),
+        repo: Some(format!("FireRedTeam/{}", model_name)),
+        size: total_size,
+        path: model_path.to_string_lossy().to_string(),
+        backend: "firered".to_string(),
+        label: None,
+    });
+    if let Err(e) = registered {
+        log::warn!("[FireRed] 登记模型失败: {}", e);
+    }
+    
     Ok(format!("{} 模型下载成功", model_name))
 }
 
@@ -622,13 +706,7 @@ pub fn open_firered_model_dir() -> Result<(), String> {
 
 /// 获取 Python 脚本目录
 fn get_scripts_dir() -> Result<PathBuf, String> {
-    let home_dir = dirs::home_dir()
-        .ok_or_else(|| "Failed to get home directory".to_string())?;
-    
-    let scripts_dir = home_dir
-        .join(".config")
-        .join("vosub")
-        .join("scripts");
+    let scripts_dir = crate::app_config::app_config_dir()?.join("scripts");
     
     if !scripts_dir.exists() {
         std::fs::create_dir_all(&scripts_dir)
@@ -770,8 +848,24 @@ fn get_uv_path() -> Option<PathBuf> {
     }
 }
 
-/// 检查指定环境是否就绪
+/// 获取指定环境的 Python 版本，解释器不存在或无法启动时返回 None
+fn get_python_version_for_env(env_dir: &PathBuf) -> Option<String> {
+    crate::python_env::python_version(&get_python_path_for_env(env_dir))
+}
+
+/// 获取 FireRed 指定环境（CPU/GPU）的 Python 版本
+pub fn get_firered_python_version(use_gpu: bool) -> Option<String> {
+    let env_dir = if use_gpu { get_firered_gpu_env_dir() } else { get_firered_cpu_env_dir() }.ok()?;
+    get_python_version_for_env(&env_dir)
+}
+
+/// 检查指定环境是否就绪（依赖已安装且 Python 解释器可以启动）
 fn check_firered_env_ready(env_dir: &PathBuf) -> bool {
+    check_firered_env_files_ready(env_dir) && get_python_version_for_env(env_dir).is_some()
+}
+
+/// 检查指定环境的依赖文件是否存在（不启动 Python）
+fn check_firered_env_files_ready(env_dir: &PathBuf) -> bool {
     let python_path = get_python_path_for_env(env_dir);
     if !env_dir.exists() || !python_path.exists() {
         return false;
@@ -807,7 +901,7 @@ fn check_firered_env_ready(env_dir: &PathBuf) -> bool {
     }
 }
 
-/// 检查 FireRedASR 环境状态（快速检查，不启动 Python）
+/// 检查 FireRedASR 环境状态（快速检查，仅通过 python --version 确认解释器可用）
 pub fn check_firered_env() -> FireRedEnvStatus {
     // 先尝试迁移旧版环境
     let _ = migrate_legacy_firered_env();
@@ -817,12 +911,16 @@ pub fn check_firered_env() -> FireRedEnvStatus {
     // 检查 CPU 环境
     let cpu_dir = get_firered_cpu_env_dir().unwrap_or_default();
     let cpu_installed = cpu_dir.exists();
+    let cpu_files_ready = check_firered_env_files_ready(&cpu_dir);
     let cpu_ready = check_firered_env_ready(&cpu_dir);
+    let cpu_broken = cpu_files_ready && !cpu_ready;
     
     // 检查 GPU 环境
     let gpu_dir = get_firered_gpu_env_dir().unwrap_or_default();
     let gpu_installed = gpu_dir.exists();
+    let gpu_files_ready = check_firered_env_files_ready(&gpu_dir);
     let gpu_ready = check_firered_env_ready(&gpu_dir);
+    let gpu_broken = gpu_files_ready && !gpu_ready;
     
     // 获取当前激活的环境
     let mut active_env = get_firered_active_env_type();
@@ -865,10 +963,12 @@ pub fn check_firered_env() -> FireRedEnvStatus {
         cpu_env: FireRedEnvInfo {
             installed: cpu_installed,
             ready: cpu_ready,
+            broken: cpu_broken,
         },
         gpu_env: FireRedEnvInfo {
             installed: gpu_installed,
             ready: gpu_ready,
+            broken: gpu_broken,
         },
         active_env,
         env_exists,
@@ -881,6 +981,8 @@ pub fn check_firered_env() -> FireRedEnvStatus {
 /// 安装 FireRedASR 环境
 /// use_gpu: 是否安装 GPU 版本（需要 NVIDIA 显卡和 CUDA）
 pub async fn install_firered_env(window: Window, use_gpu: bool) -> Result<String, String> {
+    let _operation = begin_operation("firered", OperationKind::Installing);
+    
     reset_cancellation();
     
     // 获取 uv 路径
@@ -894,6 +996,12 @@ pub async fn install_firered_env(window: Window, use_gpu: bool) -> Result<String
         get_firered_cpu_env_dir()?
     };
     
+    // 预检磁盘空间，避免安装到一半磁盘写满
+    crate::disk_space::ensure_disk_space(
+        &env_dir,
+        if use_gpu { crate::disk_space::GPU_ENV_REQUIRED_BYTES } else { crate::disk_space::CPU_ENV_REQUIRED_BYTES },
+    )?;
+    
     let version_type = if use_gpu { "GPU" } else { "CPU" };
     
     let _ = window.emit("firered-progress", FireRedProgress {
@@ -974,7 +1082,7 @@ pub async fn install_firered_env(window: Window, use_gpu: bool) -> Result<String
         .args([
             "pip", "install",
             "--python", python_path.to_str().unwrap(),
-            "fireredasr", "pydub", "transformers", "sentencepiece", "modelscope"
+            "fireredasr", "transformers", "sentencepiece", "modelscope"
         ])
         .output()
         .map_err(|e| format!("安装 FireRedASR 失败: {}", e))?;
@@ -1094,6 +1202,7 @@ fn write_correction_script() -> Result<(), String> {
 import sys
 import os
 import json
+import math
 import tempfile
 
 # 进度文件路径（通过环境变量传入）
@@ -1118,7 +1227,6 @@ def emit_progress(progress, current, total, text, device_info=False):
 
 import argparse
 import re
-from pydub import AudioSegment
 
 def get_firered_model_path():
     """获取 FireRedASR 模型路径"""
@@ -1188,6 +1296,14 @@ def parse_srt(srt_path):
     
     return entries
 
+def hyp_confidence(hyp):
+    """将解码得分（token 对数似然之和）按 token 数平均后取指数，得到 0-1 的置信度"""
+    score = hyp.get("score")
+    if score is None:
+        return None
+    tokens = max(len(hyp["yseq"]), 1)
+    return max(0.0, min(1.0, math.exp(float(score) / tokens)))
+
 def preserve_original_case(original, corrected):
     """保留原始文本中英文字母的大小写
     
@@ -1275,8 +1391,11 @@ def load_firered_model_from_local(model_dir):
     
     return feat_extractor, model, tokenizer, use_gpu
 
-def correct_subtitles(srt_path, audio_path, language="zh", preserve_case=True):
-    """使用 FireRedASR 校正字幕"""
+def correct_subtitles(srt_path, chunks_dir, language="zh", preserve_case=True, beam_size=1):
+    """使用 FireRedASR 校正字幕
+    
+    音频片段由 Rust 端预先切分为 16kHz 单声道 WAV，文件名为 {start_ms}_{end_ms}.wav
+    """
     import torch
     
     # 解析 SRT
@@ -1285,10 +1404,6 @@ def correct_subtitles(srt_path, audio_path, language="zh", preserve_case=True):
     if not entries:
         return {"entries": []}
     
-    # 加载音频
-    emit_progress(0.3, 0, 0, f"正在加载音频文件...", False)
-    audio = AudioSegment.from_file(audio_path)
-    
     # 加载模型 (使用本地已下载的模型)
     model_dir = get_firered_model_path()
     if not os.path.exists(model_dir):
@@ -1299,106 +1414,97 @@ def correct_subtitles(srt_path, audio_path, language="zh", preserve_case=True):
     device_str = f"GPU: {torch.cuda.get_device_name(0)}" if use_gpu else "CPU"
     emit_progress(5, 0, len(entries), f"模型加载完成 ({device_str})，开始校正 {len(entries)} 条字幕...", False)
     
-    # 创建临时目录
-    tmp_dir = tempfile.mkdtemp()
-    
     results = []
     total = len(entries)
     
-    try:
-        for i, entry in enumerate(entries):
-            # 输出进度（JSON 格式，包含当前字幕信息）
-            # 进度从 5% 开始（前面 0-5% 是设备检测和模型加载），到 100% 结束
-            progress = 5 + (i + 1) / total * 95
-            text_preview = entry['text'][:30].replace('\n', ' ')
-            emit_progress(progress, i + 1, total, text_preview, False)
-            
-            start_ms = entry['start_ms']
-            end_ms = entry['end_ms']
-            original_text = entry['text']
+    for i, entry in enumerate(entries):
+        # 输出进度（JSON 格式，包含当前字幕信息）
+        # 进度从 5% 开始（前面 0-5% 是设备检测和模型加载），到 100% 结束
+        progress = 5 + (i + 1) / total * 95
+        text_preview = entry['text'][:30].replace('\n', ' ')
+        emit_progress(progress, i + 1, total, text_preview, False)
+        
+        start_ms = entry['start_ms']
+        end_ms = entry['end_ms']
+        original_text = entry['text']
+        
+        # 预先切分好的音频片段（单声道 16kHz，FireRedASR 要求）
+        chunk_file = os.path.join(chunks_dir, f"{start_ms}_{end_ms}.wav")
+        
+        # 识别
+        try:
+            if not os.path.exists(chunk_file):
+                raise FileNotFoundError(f"音频片段不存在: {chunk_file}")
             
-            # 切分音频片段，转换为单声道 16kHz（FireRedASR 要求）
-            chunk = audio[start_ms:end_ms]
-            chunk = chunk.set_channels(1)  # 转为单声道
-            chunk = chunk.set_frame_rate(16000)  # 16kHz 采样率
-            chunk_file = os.path.join(tmp_dir, f"chunk_{i}.wav")
-            chunk.export(chunk_file, format="wav")
+            # 提取特征
+            feats, lengths, _ = feat_extractor([chunk_file])
             
-            # 识别
-            try:
-                # 提取特征
-                feats, lengths, _ = feat_extractor([chunk_file])
-                
-                # 如果使用 GPU，将数据移到 GPU
-                if use_gpu:
-                    feats = feats.cuda()
-                    lengths = lengths.cuda()
-                
-                # 使用模型进行识别
-                hyps = model.transcribe(
-                    feats,
-                    lengths,
-                    beam_size=1,
-                    nbest=1,
-                    decode_max_len=0,
-                    softmax_smoothing=1.0,
-                    length_penalty=0.0,
-                    eos_penalty=1.0,
-                )
-                
-                # 解码结果
-                if hyps:
-                    hyp = hyps[0][0]  # 取第一个结果的 1-best
-                    hyp_ids = [int(id) for id in hyp["yseq"].cpu()]
-                    corrected_text = tokenizer.detokenize(hyp_ids).strip()
-                else:
-                    corrected_text = ""
-                
-                # 如果启用了保留大小写，恢复原始英文大小写
-                if preserve_case and corrected_text:
-                    corrected_text = preserve_original_case(original_text, corrected_text)
-            except Exception as e:
-                print(f"识别片段 {i+1} 失败: {e}", file=sys.stderr)
-                import traceback
-                traceback.print_exc(file=sys.stderr)
-                corrected_text = original_text
+            # 如果使用 GPU，将数据移到 GPU
+            if use_gpu:
+                feats = feats.cuda()
+                lengths = lengths.cuda()
             
-            # 清理临时文件
-            os.remove(chunk_file)
+            # 使用模型进行识别
+            hyps = model.transcribe(
+                feats,
+                lengths,
+                beam_size=beam_size,
+                nbest=1,
+                decode_max_len=0,
+                softmax_smoothing=1.0,
+                length_penalty=0.0,
+                eos_penalty=1.0,
+            )
             
-            # 比较差异 - 如果识别结果为空，使用原文，不算差异
-            final_text = corrected_text if corrected_text else original_text
-            has_diff = original_text.strip() != final_text.strip()
+            # 解码结果
+            confidence = None
+            if hyps:
+                hyp = hyps[0][0]  # 取第一个结果的 1-best
+                hyp_ids = [int(id) for id in hyp["yseq"].cpu()]
+                corrected_text = tokenizer.detokenize(hyp_ids).strip()
+                confidence = hyp_confidence(hyp)
+            else:
+                corrected_text = ""
             
-            results.append({
-                "id": entry['id'],
-                "start_ms": start_ms,
-                "end_ms": end_ms,
-                "original": original_text,
-                "corrected": final_text,
-                "has_diff": has_diff
-            })
-    finally:
-        # 清理临时目录
-        try:
-            os.rmdir(tmp_dir)
-        except:
-            pass
-    
+            # 如果启用了保留大小写，恢复原始英文大小写
+            if preserve_case and corrected_text:
+                corrected_text = preserve_original_case(original_text, corrected_text)
+        except Exception as e:
+            print(f"识别片段 {i+1} 失败: {e}", file=sys.stderr)
+            import traceback
+            traceback.print_exc(file=sys.stderr)
+            corrected_text = original_text
+            confidence = None
+        
+        # 比较差异 - 如果识别结果为空，使用原文，不算差异
+        final_text = corrected_text if corrected_text else original_text
+        has_diff = original_text.strip() != final_text.strip()
+        
+        results.append({
+            "id": entry['id'],
+            "start_ms": start_ms,
+            "end_ms": end_ms,
+            "original": original_text,
+            "corrected": final_text,
+            "has_diff": has_diff,
+            "confidence": confidence
+        })
+
     return {"entries": results}
 
 def main():
     parser = argparse.ArgumentParser()
     parser.add_argument("srt_path", help="SRT 字幕文件路径")
-    parser.add_argument("audio_path", help="音频文件路径")
+    parser.add_argument("chunks_dir", help="预先切分的音频片段目录")
     parser.add_argument("--language", default="zh", help="语言代码")
     parser.add_argument("--output", help="输出 JSON 文件路径")
     parser.add_argument("--preserve-case", action="store_true", default=True, help="保留原始英文大小写")
     parser.add_argument("--no-preserve-case", action="store_false", dest="preserve_case", help="不保留原始英文大小写")
+    parser.add_argument("--beam-size", type=int, default=1, help="解码 beam 宽度（1-10）")
     args = parser.parse_args()
     
     try:
-        result = correct_subtitles(args.srt_path, args.audio_path, args.language, args.preserve_case)
+        result = correct_subtitles(args.srt_path, args.chunks_dir, args.language, args.preserve_case, args.beam_size)
         if args.output:
             with open(args.output, "w", encoding="utf-8") as f:
                 json.dump(result, f, ensure_ascii=False, indent=2)
@@ -1432,24 +1538,88 @@ if __name__ == "__main__":
 }
 
 
-/// 毫秒转 TimeStamp
-fn ms_to_timestamp(ms: u32) -> TimeStamp {
-    TimeStamp {
-        hours: ms / 3600000,
-        minutes: (ms % 3600000) / 60000,
-        seconds: (ms % 60000) / 1000,
-        milliseconds: ms % 1000,
+/// 临时目录守卫，离开作用域时删除目录（用于存放切分的音频片段）
+struct TempDirGuard(PathBuf);
+
+impl Drop for TempDirGuard {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// 校验解码 beam 宽度（1-10），未指定时为 1
+fn resolve_beam_size(beam_size: Option<u32>) -> Result<u32, String> {
+    let beam_size = beam_size.unwrap_or(1);
+    if !(1..=10).contains(&beam_size) {
+        return Err(format!("beam_size 必须在 1-10 之间，当前为 {}", beam_size));
     }
+    Ok(beam_size)
 }
 
 /// 使用 FireRedASR 校正字幕
+/// concurrency > 1 时通过持久化服务并发校正，否则逐条运行校正脚本
+/// beam_size: 解码 beam 宽度（1-10，默认 1），越大越准确但越慢
 pub async fn correct_with_firered(
     srt_path: String,
     audio_path: String,
     language: String,
     preserve_case: bool,
+    concurrency: Option<usize>,
+    beam_size: Option<u32>,
     window: Window,
 ) -> Result<Vec<CorrectionEntry>, String> {
+    correct_with_firered_report(srt_path, audio_path, language, preserve_case, concurrency, beam_size, window)
+        .await
+        .map(|report| report.entries)
+}
+
+/// 校正结果及运行信息（引擎、模型、实际设备、耗时）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrectionReport {
+    pub entries: Vec<CorrectionEntry>,
+    pub engine: String,
+    pub model: String,
+    pub device: String,
+    pub elapsed_secs: f64,
+}
+
+/// 读取本地词典，用于修正校正结果中反复出现的识别错误（读取失败时返回空词典）
+fn load_correction_dictionary() -> Vec<DictionaryEntry> {
+    crate::dictionary::load_dictionary().unwrap_or_else(|e| {
+        log::warn!("[FireRed] 读取本地词典失败: {}", e);
+        Vec::new()
+    })
+}
+
+/// 对校正结果应用词典，返回 (修正后的文本, 与原文是否不同)
+fn apply_dictionary_to_correction(
+    original: &str,
+    corrected: String,
+    has_diff: bool,
+    dictionary: &[DictionaryEntry],
+) -> (String, bool) {
+    if dictionary.is_empty() {
+        return (corrected, has_diff);
+    }
+    let corrected = apply_dictionary_to_text(&corrected, dictionary);
+    let has_diff = original.trim() != corrected.trim();
+    (corrected, has_diff)
+}
+
+/// 使用 FireRedASR 校正字幕，同时返回运行信息
+pub async fn correct_with_firered_report(
+    srt_path: String,
+    audio_path: String,
+    language: String,
+    preserve_case: bool,
+    concurrency: Option<usize>,
+    beam_size: Option<u32>,
+    window: Window,
+) -> Result<CorrectionReport, String> {
+    let _operation = begin_operation("firered", OperationKind::Correcting);
+    
+    let beam_size = resolve_beam_size(beam_size)?;
+    
     reset_cancellation();
     
     // 检查环境
@@ -1458,9 +1628,10 @@ pub async fn correct_with_firered(
         return Err("FireRedASR 环境未安装，请先安装环境".to_string());
     }
     
+    // GPU 环境下排队等待显卡空闲，避免与其他任务同时占用显存
+    let _gpu_permit = crate::gpu_guard::acquire_gpu_permit(&get_firered_active_env_type()).await?;
+    
     let python_path = get_python_path()?;
-    let scripts_dir = get_scripts_dir()?;
-    let script_path = scripts_dir.join("firered_correct.py");
     
     // 确保脚本是最新的
     write_correction_script()?;
@@ -1494,8 +1665,34 @@ pub async fn correct_with_firered(
         return Err("校正已取消".to_string());
     }
     
-    // 创建临时输出文件
-    let output_path = std::env::temp_dir().join(format!("firered_output_{}.json", std::process::id()));
+    // 在 Rust 端一次解码音频并切分所有字幕片段（16kHz 单声道 WAV），避免 Python 端依赖 pydub/ffmpeg
+    let _ = window.emit("firered-progress", FireRedProgress {
+        progress: 2.0,
+        current_text: "正在切分音频片段...".to_string(),
+        status: "loading".to_string(),
+    });
+    let chunks_dir = TempDirGuard(crate::temp_files::unique_temp_path("firered_chunks", ""));
+    let (srt_entries, chunk_paths) = {
+        let srt_path = srt_path.clone();
+        let audio_path = audio_path.clone();
+        let chunks_path = chunks_dir.0.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            let srt_file = crate::srt_parser::read_srt_file(&srt_path)?;
+            let ranges: Vec<(u64, u64)> = srt_file
+                .entries
+                .iter()
+                .map(|e| (e.start_time.to_millis(), e.end_time.to_millis()))
+                .collect();
+            let chunk_paths = crate::waveform_generator::extract_chunks(&audio_path, &ranges, &chunks_path)?;
+            Ok::<_, String>((srt_file.entries, chunk_paths))
+        })
+        .await
+        .map_err(|e| format!("Task error: {:?}", e))??
+    };
+    
+    if is_cancelled() {
+        return Err("校正已取消".to_string());
+    }
     
     // 映射语言代码
     let lang_code = match language.as_str() {
@@ -1507,11 +1704,81 @@ pub async fn correct_with_firered(
         _ => "zh",
     };
     
+    let result = match concurrency.filter(|&n| n > 1) {
+        Some(workers) => {
+            correct_via_service_batch(&srt_entries, &chunk_paths, preserve_case, workers, beam_size, &window).await?
+        }
+        None => run_correction_script(&python_path, &srt_path, &chunks_dir.0, lang_code, preserve_case, beam_size, &window)?,
+    };
+    
+    let dictionary = load_correction_dictionary();
+    
+    // 转换为 CorrectionEntry
+    let entries: Vec<CorrectionEntry> = result.entries.into_iter().map(|e| {
+        let (corrected, has_diff) = apply_dictionary_to_correction(&e.original, e.corrected, e.has_diff, &dictionary);
+        CorrectionEntry {
+            id: e.id,
+            start_time: TimeStamp::from_millis(e.start_ms as u64),
+            end_time: TimeStamp::from_millis(e.end_ms as u64),
+            original: e.original,
+            corrected,
+            has_diff,
+            confidence: e.confidence,
+        }
+    }).collect();
+    
+    let diff_count = entries.iter().filter(|e| e.has_diff).count();
+    let total_count = entries.len();
+    
+    // 记录结束时间和总耗时
+    let elapsed = start_time.elapsed();
+    let elapsed_secs = elapsed.as_secs_f64();
+    let elapsed_str = if elapsed_secs >= 60.0 {
+        format!("{:.0}分{:.1}秒", elapsed_secs / 60.0, elapsed_secs % 60.0)
+    } else {
+        format!("{:.1}秒", elapsed_secs)
+    };
+    
+    log::info!("[FireRed] 校正完成: 共 {} 条字幕，发现 {} 处差异，耗时 {}", total_count, diff_count, elapsed_str);
+    log::info!("[FireRed] ========== AI 校正结束 ==========");
+    
+    let _ = window.emit("firered-progress", FireRedProgress {
+        progress: 100.0,
+        current_text: format!("校正完成！共 {} 条，{} 处差异，耗时 {}", total_count, diff_count, elapsed_str),
+        status: "completed".to_string(),
+    });
+    
+    Ok(CorrectionReport {
+        entries,
+        engine: "firered".to_string(),
+        model: "FireRedASR-AED-L".to_string(),
+        device: device_info.unwrap_or_else(|| {
+            if env_status.is_gpu { "CUDA".to_string() } else { "CPU".to_string() }
+        }),
+        elapsed_secs,
+    })
+}
+
+/// 逐条执行校正脚本（每次启动新进程并加载模型），通过进度文件转发进度
+fn run_correction_script(
+    python_path: &std::path::Path,
+    srt_path: &str,
+    chunks_dir: &std::path::Path,
+    lang_code: &str,
+    preserve_case: bool,
+    beam_size: u32,
+    window: &Window,
+) -> Result<CorrectionResult, String> {
+    let script_path = get_scripts_dir()?.join("firered_correct.py");
+    
+    // 创建临时输出文件
+    let output_path = crate::temp_files::unique_temp_path("firered_output", ".json");
+    
     // 构建命令参数
     let mut args = vec![
         script_path.to_str().unwrap().to_string(),
-        srt_path.clone(),
-        audio_path.clone(),
+        srt_path.to_string(),
+        chunks_dir.to_str().unwrap().to_string(),
         "--language".to_string(), lang_code.to_string(),
         "--output".to_string(), output_path.to_str().unwrap().to_string(),
     ];
@@ -1522,10 +1789,11 @@ pub async fn correct_with_firered(
     } else {
         args.push("--no-preserve-case".to_string());
     }
+    args.push("--beam-size".to_string());
+    args.push(beam_size.to_string());
     
     // 创建进度文件
-    let progress_file = std::env::temp_dir().join(format!("firered_progress_{}.json", std::process::id()));
-    let _ = std::fs::remove_file(&progress_file); // 确保文件不存在
+    let progress_file = crate::temp_files::unique_temp_path("firered_progress", ".json");
     
     // 执行 Python 脚本
     let mut child = Command::new(&python_path)
@@ -1612,43 +1880,128 @@ pub async fn correct_with_firered(
     let _ = std::fs::remove_file(&output_path);
     
     // 解析 JSON
-    let result: CorrectionResult = serde_json::from_str(&result_json)
-        .map_err(|e| format!("解析校正结果失败: {}", e))?;
-    
-    // 转换为 CorrectionEntry
-    let entries: Vec<CorrectionEntry> = result.entries.into_iter().map(|e| {
-        CorrectionEntry {
-            id: e.id,
-            start_time: ms_to_timestamp(e.start_ms),
-            end_time: ms_to_timestamp(e.end_ms),
-            original: e.original,
-            corrected: e.corrected,
-            has_diff: e.has_diff,
-        }
-    }).collect();
+    serde_json::from_str(&result_json)
+        .map_err(|e| format!("解析校正结果失败: {}", e))
     
-    let diff_count = entries.iter().filter(|e| e.has_diff).count();
-    let total_count = entries.len();
+}
+
+/// 服务批量校正在这段时间内进度没有任何推进则视为卡死（首条字幕包含模型加载时间）
+const SERVICE_BATCH_STALL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(600);
+
+/// 通过持久化服务的 /correct_batch 端点校正（模型常驻，workers 个线程并发提取特征，推理串行）
+/// 服务将进度写入进度文件，这里与脚本路径一样轮询并转发 firered-progress 事件
+async fn correct_via_service_batch(
+    entries: &[SubtitleEntry],
+    chunk_paths: &[PathBuf],
+    preserve_case: bool,
+    workers: usize,
+    beam_size: u32,
+    window: &Window,
+) -> Result<CorrectionResult, String> {
+    start_service()?;
     
-    // 记录结束时间和总耗时
-    let elapsed = start_time.elapsed();
-    let elapsed_secs = elapsed.as_secs_f64();
-    let elapsed_str = if elapsed_secs >= 60.0 {
-        format!("{:.0}分{:.1}秒", elapsed_secs / 60.0, elapsed_secs % 60.0)
-    } else {
-        format!("{:.1}秒", elapsed_secs)
-    };
+    // 进度文件名同时作为批次 id，取消时通知服务停止处理剩余字幕
+    let progress_file = crate::temp_files::unique_temp_path("firered_progress", ".json");
+    let batch_id = progress_file
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    
+    let cues: Vec<serde_json::Value> = entries
+        .iter()
+        .zip(chunk_paths)
+        .map(|(entry, chunk_path)| serde_json::json!({
+            "id": entry.id,
+            "start_ms": entry.start_time.to_millis(),
+            "end_ms": entry.end_time.to_millis(),
+            "chunk_path": chunk_path.to_string_lossy(),
+            "original_text": entry.text,
+        }))
+        .collect();
+    let request_body = serde_json::json!({
+        "cues": cues,
+        "preserve_case": preserve_case,
+        "workers": workers,
+        "beam_size": beam_size,
+        "progress_file": progress_file.to_string_lossy(),
+        "batch_id": batch_id,
+    });
     
-    log::info!("[FireRed] 校正完成: 共 {} 条字幕，发现 {} 处差异，耗时 {}", total_count, diff_count, elapsed_str);
-    log::info!("[FireRed] ========== AI 校正结束 ==========");
+    // 批次耗时与字幕数量相关，不设整体超时；改由进度循环检测长时间无进展
+    let client = reqwest::Client::builder()
+        .connect_timeout(std::time::Duration::from_secs(10))
+        .build()
+        .map_err(|e| format!("创建 HTTP 客户端失败: {}", e))?;
+    let request = Box::pin(async {
+        let response = client
+            .post(service_url("/correct_batch"))
+            .header("Content-Type", "application/json")
+            .body(request_body.to_string())
+            .send()
+            .await
+            .map_err(|e| format!("请求服务失败: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!("校正失败: HTTP {}", response.status()));
+        }
+        response.text().await.map_err(|e| format!("读取响应失败: {}", e))
+    });
     
-    let _ = window.emit("firered-progress", FireRedProgress {
-        progress: 100.0,
-        current_text: format!("校正完成！共 {} 条，{} 处差异，耗时 {}", total_count, diff_count, elapsed_str),
-        status: "completed".to_string(),
+    // 轮询进度文件，直到请求完成、用户取消或长时间无进展
+    let progress_loop = Box::pin(async {
+        let mut last_progress: f32 = 2.0;
+        let mut last_advance = std::time::Instant::now();
+        loop {
+            if is_cancelled() {
+                return "校正已取消".to_string();
+            }
+            if last_advance.elapsed() >= SERVICE_BATCH_STALL_TIMEOUT {
+                return format!("校正服务超过 {} 秒没有进展，已中止", SERVICE_BATCH_STALL_TIMEOUT.as_secs());
+            }
+            if let Ok(content) = std::fs::read_to_string(&progress_file) {
+                if let Ok(progress_info) = serde_json::from_str::<serde_json::Value>(&content) {
+                    let progress = progress_info["progress"].as_f64().unwrap_or(0.0) as f32;
+                    let current = progress_info["current"].as_i64().unwrap_or(0);
+                    let total = progress_info["total"].as_i64().unwrap_or(0);
+                    if progress > last_progress {
+                        last_progress = progress;
+                        last_advance = std::time::Instant::now();
+                        let _ = window.emit("firered-progress", FireRedProgress {
+                            progress,
+                            current_text: format!("正在进行 AI 校正 ({}/{}，{} 线程)", current, total, workers),
+                            status: "correcting".to_string(),
+                        });
+                    }
+                }
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
     });
     
-    Ok(entries)
+    let outcome = futures_util::future::select(request, progress_loop).await;
+    let _ = std::fs::remove_file(&progress_file);
+    
+    let result_json = match outcome {
+        futures_util::future::Either::Left((result, _)) => result?,
+        futures_util::future::Either::Right((reason, _)) => {
+            // 仅丢弃请求不会停止服务端的线程池，需显式通知服务取消该批次
+            let _ = client
+                .post(service_url("/cancel_batch"))
+                .header("Content-Type", "application/json")
+                .body(serde_json::json!({ "batch_id": batch_id }).to_string())
+                .timeout(std::time::Duration::from_secs(5))
+                .send()
+                .await;
+            return Err(reason);
+        }
+    };
+    if result_json.contains("\"cancelled\"") {
+        return Err("校正已取消".to_string());
+    }
+    if result_json.contains("\"error\"") {
+        return Err(format!("校正服务返回错误: {}", result_json));
+    }
+    serde_json::from_str(&result_json)
+        .map_err(|e| format!("解析校正结果失败: {}", e))
 }
 
 /// 单条字幕校正结果
@@ -1657,6 +2010,9 @@ pub struct SingleCorrectionResult {
     pub original: String,
     pub corrected: String,
     pub has_diff: bool,
+    /// 识别置信度（0-1）
+    #[serde(default)]
+    pub confidence: Option<f32>,
 }
 
 /// 写入服务脚本
@@ -1671,17 +2027,58 @@ fn write_service_script() -> Result<PathBuf, String> {
 import sys
 import json
 import os
+import math
 import tempfile
 import hashlib
-from http.server import HTTPServer, BaseHTTPRequestHandler
+import threading
+import time
+import argparse
+from concurrent.futures import ThreadPoolExecutor
+from http.server import ThreadingHTTPServer, BaseHTTPRequestHandler
 import urllib.parse
 
-# 全局模型变量
+# 全局模型变量（服务为多线程，加载时加锁避免重复加载）
 MODEL = None
-# 音频缓存：{audio_path: (mtime, AudioSegment)}
-AUDIO_CACHE = {}
-# 最大缓存数量
-MAX_CACHE_SIZE = 3
+MODEL_LOCK = threading.Lock()
+
+# 所有线程共用一个模型，未确认其线程安全，推理时串行执行
+INFERENCE_LOCK = threading.Lock()
+
+# 已被客户端取消的批次 id，工作线程在每条字幕开始前检查
+CANCELLED_BATCHES = set()
+CANCEL_LOCK = threading.Lock()
+
+def is_batch_cancelled(batch_id):
+    with CANCEL_LOCK:
+        return batch_id in CANCELLED_BATCHES
+
+# 最近一次请求的时间与正在处理的请求数（用于空闲自动退出，/health 不计入）
+LAST_ACTIVITY = time.time()
+ACTIVE_REQUESTS = 0
+ACTIVITY_LOCK = threading.Lock()
+
+def begin_request():
+    global LAST_ACTIVITY, ACTIVE_REQUESTS
+    with ACTIVITY_LOCK:
+        ACTIVE_REQUESTS += 1
+        LAST_ACTIVITY = time.time()
+
+def end_request():
+    global LAST_ACTIVITY, ACTIVE_REQUESTS
+    with ACTIVITY_LOCK:
+        ACTIVE_REQUESTS -= 1
+        LAST_ACTIVITY = time.time()
+
+def idle_watchdog(server, idle_timeout):
+    """空闲超过 idle_timeout 秒且没有进行中的请求时关闭服务，释放模型占用的内存"""
+    while True:
+        time.sleep(min(idle_timeout, 30))
+        with ACTIVITY_LOCK:
+            idle = ACTIVE_REQUESTS == 0 and time.time() - LAST_ACTIVITY >= idle_timeout
+        if idle:
+            print(f"Idle for {idle_timeout}s, shutting down", file=sys.stderr)
+            server.shutdown()
+            return
 
 def get_firered_model_path():
     """获取 FireRedASR 模型路径"""
@@ -1714,74 +2111,56 @@ def get_firered_model_path():
 
 def load_model():
     global MODEL
-    if MODEL is None:
-        import torch
-        import argparse
-        from fireredasr.data.asr_feat import ASRFeatExtractor
-        from fireredasr.models.fireredasr_aed import FireRedAsrAed
-        from fireredasr.tokenizer.aed_tokenizer import ChineseCharEnglishSpmTokenizer
+    with MODEL_LOCK:
+        if MODEL is None:
+            import torch
+            import argparse
+            from fireredasr.data.asr_feat import ASRFeatExtractor
+            from fireredasr.models.fireredasr_aed import FireRedAsrAed
+            from fireredasr.tokenizer.aed_tokenizer import ChineseCharEnglishSpmTokenizer
         
-        # 修复 PyTorch 2.6+ 的兼容性问题
-        torch.serialization.add_safe_globals([argparse.Namespace])
+            # 修复 PyTorch 2.6+ 的兼容性问题
+            torch.serialization.add_safe_globals([argparse.Namespace])
         
-        # 检测是否有 GPU 可用
-        use_gpu = torch.cuda.is_available()
-        device = "cuda" if use_gpu else "cpu"
-        device_name = torch.cuda.get_device_name(0) if use_gpu else "CPU"
-        print(f"使用设备: {device.upper()} ({device_name})", file=sys.stderr, flush=True)
+            # 检测是否有 GPU 可用
+            use_gpu = torch.cuda.is_available()
+            device = "cuda" if use_gpu else "cpu"
+            device_name = torch.cuda.get_device_name(0) if use_gpu else "CPU"
+            print(f"使用设备: {device.upper()} ({device_name})", file=sys.stderr, flush=True)
         
-        print("Loading FireRedASR model...", file=sys.stderr)
-        model_dir = get_firered_model_path()
-        if not os.path.exists(model_dir):
-            raise RuntimeError(f"模型未下载，请先在设置中下载 FireRedASR-AED-L 模型")
+            print("Loading FireRedASR model...", file=sys.stderr)
+            model_dir = get_firered_model_path()
+            if not os.path.exists(model_dir):
+                raise RuntimeError(f"模型未下载，请先在设置中下载 FireRedASR-AED-L 模型")
         
-        cmvn_path = os.path.join(model_dir, "cmvn.ark")
-        feat_extractor = ASRFeatExtractor(cmvn_path)
+            cmvn_path = os.path.join(model_dir, "cmvn.ark")
+            feat_extractor = ASRFeatExtractor(cmvn_path)
         
-        model_path = os.path.join(model_dir, "model.pth.tar")
-        package = torch.load(model_path, map_location=lambda storage, loc: storage, weights_only=False)
-        model = FireRedAsrAed.from_args(package["args"])
-        model.load_state_dict(package["model_state_dict"], strict=True)
-        model.eval()
+            model_path = os.path.join(model_dir, "model.pth.tar")
+            package = torch.load(model_path, map_location=lambda storage, loc: storage, weights_only=False)
+            model = FireRedAsrAed.from_args(package["args"])
+            model.load_state_dict(package["model_state_dict"], strict=True)
+            model.eval()
         
-        # 如果有 GPU，将模型移到 GPU
-        if use_gpu:
-            model = model.cuda()
+            # 如果有 GPU，将模型移到 GPU
+            if use_gpu:
+                model = model.cuda()
         
-        dict_path = os.path.join(model_dir, "dict.txt")
-        spm_model = os.path.join(model_dir, "train_bpe1000.model")
-        tokenizer = ChineseCharEnglishSpmTokenizer(dict_path, spm_model)
+            dict_path = os.path.join(model_dir, "dict.txt")
+            spm_model = os.path.join(model_dir, "train_bpe1000.model")
+            tokenizer = ChineseCharEnglishSpmTokenizer(dict_path, spm_model)
         
-        MODEL = (feat_extractor, model, tokenizer, use_gpu)
-        print("Model loaded!", file=sys.stderr)
-    return MODEL
-
-def get_cached_audio(audio_path):
-    """获取缓存的音频，如果文件已修改则重新加载"""
-    global AUDIO_CACHE
-    from pydub import AudioSegment
-    
-    try:
-        mtime = os.path.getmtime(audio_path)
-    except OSError:
-        mtime = 0
-    
-    if audio_path in AUDIO_CACHE:
-        cached_mtime, cached_audio = AUDIO_CACHE[audio_path]
-        if cached_mtime == mtime:
-            return cached_audio
-    
-    # 加载新音频
-    audio = AudioSegment.from_file(audio_path)
-    
-    # 清理旧缓存
-    if len(AUDIO_CACHE) >= MAX_CACHE_SIZE:
-        # 删除最早的缓存
-        oldest_key = next(iter(AUDIO_CACHE))
-        del AUDIO_CACHE[oldest_key]
-    
-    AUDIO_CACHE[audio_path] = (mtime, audio)
-    return audio
+            MODEL = (feat_extractor, model, tokenizer, use_gpu)
+            print("Model loaded!", file=sys.stderr)
+        return MODEL
+
+def hyp_confidence(hyp):
+    """将解码得分（token 对数似然之和）按 token 数平均后取指数，得到 0-1 的置信度"""
+    score = hyp.get("score")
+    if score is None:
+        return None
+    tokens = max(len(hyp["yseq"]), 1)
+    return max(0.0, min(1.0, math.exp(float(score) / tokens)))
 
 def preserve_original_case(original, corrected):
     """保留原始文本中英文字母的大小写"""
@@ -1815,74 +2194,159 @@ def preserve_original_case(original, corrected):
     
     return ''.join(result)
 
+def recognize_chunk(chunk_path, beam_size=1):
+    """识别单个音频片段，返回 (识别文本, 置信度)"""
+    feat_extractor, model, tokenizer, use_gpu = load_model()
+    
+    # 提取特征
+    feats, lengths, _ = feat_extractor([chunk_path])
+    
+    # 如果使用 GPU，将数据移到 GPU
+    if use_gpu:
+        feats = feats.cuda()
+        lengths = lengths.cuda()
+    
+    # 使用模型进行识别
+    with INFERENCE_LOCK:
+        hyps = model.transcribe(
+            feats,
+            lengths,
+            beam_size=beam_size,
+            nbest=1,
+            decode_max_len=0,
+            softmax_smoothing=1.0,
+            length_penalty=0.0,
+            eos_penalty=1.0,
+        )
+    
+    # 解码结果
+    if hyps:
+        hyp = hyps[0][0]  # 取第一个结果的 1-best
+        hyp_ids = [int(id) for id in hyp["yseq"].cpu()]
+        return tokenizer.detokenize(hyp_ids).strip(), hyp_confidence(hyp)
+    return "", None
+
+def write_progress(progress_file, progress, current, total, text):
+    """写入进度文件（格式与校正脚本一致，由 Rust 端轮询）"""
+    if not progress_file:
+        return
+    try:
+        with open(progress_file, 'w', encoding='utf-8') as f:
+            json.dump({"progress": progress, "current": current, "total": total, "text": text}, f, ensure_ascii=False)
+    except:
+        pass
+
+def correct_batch(params):
+    """用线程池识别多条字幕（特征提取并发，模型推理串行），结果按输入顺序返回
+    客户端通过 /cancel_batch 取消后，尚未开始的字幕直接跳过"""
+    cues = params.get('cues', [])
+    batch_id = params.get('batch_id', '')
+    preserve_case = params.get('preserve_case', True)
+    workers = max(1, int(params.get('workers', 1)))
+    beam_size = int(params.get('beam_size', 1))
+    progress_file = params.get('progress_file', '')
+    
+    # 先在主线程加载模型，避免多个线程同时加载
+    load_model()
+    
+    total = len(cues)
+    done = [0]
+    lock = threading.Lock()
+    write_progress(progress_file, 5, 0, total, f"开始校正 {total} 条字幕...")
+    
+    def work(cue):
+        if is_batch_cancelled(batch_id):
+            return None
+        original_text = cue['original_text']
+        try:
+            corrected, confidence = recognize_chunk(cue['chunk_path'], beam_size)
+            if preserve_case and corrected:
+                corrected = preserve_original_case(original_text, corrected)
+        except Exception as e:
+            print(f"识别片段 {cue['id']} 失败: {e}", file=sys.stderr)
+            corrected, confidence = original_text, None
+        
+        # 识别结果为空时使用原文，不算差异
+        final_text = corrected if corrected else original_text
+        with lock:
+            done[0] += 1
+            write_progress(progress_file, 5 + done[0] / total * 95, done[0], total, original_text[:30].replace('\n', ' '))
+        
+        return {
+            "id": cue['id'],
+            "start_ms": cue['start_ms'],
+            "end_ms": cue['end_ms'],
+            "original": original_text,
+            "corrected": final_text,
+            "has_diff": original_text.strip() != final_text.strip(),
+            "confidence": confidence
+        }
+    
+    with ThreadPoolExecutor(max_workers=workers) as pool:
+        results = list(pool.map(work, cues))
+    
+    with CANCEL_LOCK:
+        cancelled = batch_id in CANCELLED_BATCHES
+        CANCELLED_BATCHES.discard(batch_id)
+    if cancelled:
+        return {"cancelled": True}
+    return {"entries": results}
+
 class Handler(BaseHTTPRequestHandler):
     def log_message(self, format, *args):
         pass  # 禁用日志
     
+    def send_json(self, status, data):
+        self.send_response(status)
+        self.send_header('Content-Type', 'application/json')
+        self.end_headers()
+        self.wfile.write(json.dumps(data, ensure_ascii=False).encode('utf-8'))
+    
     def do_POST(self):
+        begin_request()
+        try:
+            self.handle_post()
+        finally:
+            end_request()
+    
+    def handle_post(self):
         content_length = int(self.headers['Content-Length'])
         post_data = self.rfile.read(content_length)
         params = json.loads(post_data.decode('utf-8'))
         
+        if self.path == '/correct_batch':
+            try:
+                self.send_json(200, correct_batch(params))
+            except Exception as e:
+                self.send_json(500, {"error": str(e)})
+            return
+        
+        if self.path == '/cancel_batch':
+            with CANCEL_LOCK:
+                CANCELLED_BATCHES.add(params.get('batch_id', ''))
+            self.send_json(200, {"cancelled": True})
+            return
+        
         try:
-            audio_path = params['audio_path']
-            start_ms = params['start_ms']
-            end_ms = params['end_ms']
+            # 音频片段由 Rust 端切分好（16kHz 单声道 WAV），由调用方负责删除
+            chunk_path = params['chunk_path']
             original_text = params['original_text']
             language = params.get('language', 'zh')
             preserve_case = params.get('preserve_case', True)
-            
-            # 使用缓存的音频
-            audio = get_cached_audio(audio_path)
-            chunk = audio[start_ms:end_ms]
-            chunk = chunk.set_channels(1)
-            chunk = chunk.set_frame_rate(16000)
-            
-            tmp_file = tempfile.NamedTemporaryFile(suffix='.wav', delete=False)
-            chunk.export(tmp_file.name, format='wav')
-            tmp_file.close()
+            beam_size = int(params.get('beam_size', 1))
             
             # 识别
-            feat_extractor, model, tokenizer, use_gpu = load_model()
-            
-            # 提取特征
-            feats, lengths, _ = feat_extractor([tmp_file.name])
-            
-            # 如果使用 GPU，将数据移到 GPU
-            if use_gpu:
-                feats = feats.cuda()
-                lengths = lengths.cuda()
-            
-            # 使用模型进行识别
-            hyps = model.transcribe(
-                feats,
-                lengths,
-                beam_size=1,
-                nbest=1,
-                decode_max_len=0,
-                softmax_smoothing=1.0,
-                length_penalty=0.0,
-                eos_penalty=1.0,
-            )
-            
-            # 解码结果
-            if hyps:
-                hyp = hyps[0][0]  # 取第一个结果的 1-best
-                hyp_ids = [int(id) for id in hyp["yseq"].cpu()]
-                corrected = tokenizer.detokenize(hyp_ids).strip()
-            else:
-                corrected = ""
+            corrected, confidence = recognize_chunk(chunk_path, beam_size)
             
             # 如果启用了保留大小写，恢复原始英文大小写
             if preserve_case and corrected:
                 corrected = preserve_original_case(original_text, corrected)
             
-            os.remove(tmp_file.name)
-            
             response = {
                 "original": original_text,
                 "corrected": corrected,
-                "has_diff": original_text.strip() != corrected
+                "has_diff": original_text.strip() != corrected,
+                "confidence": confidence
             }
             
             self.send_response(200)
@@ -1901,7 +2365,16 @@ class Handler(BaseHTTPRequestHandler):
             self.send_header('Content-Type', 'text/plain')
             self.end_headers()
             self.wfile.write(b'ok')
-        elif self.path == '/preload':
+            return
+        
+        begin_request()
+        try:
+            self.handle_get()
+        finally:
+            end_request()
+    
+    def handle_get(self):
+        if self.path == '/preload':
             try:
                 load_model()
                 self.send_response(200)
@@ -1912,35 +2385,22 @@ class Handler(BaseHTTPRequestHandler):
                 self.send_response(500)
                 self.end_headers()
                 self.wfile.write(str(e).encode())
-        elif self.path.startswith('/preload_audio?'):
-            # 预加载音频文件
-            try:
-                query = urllib.parse.urlparse(self.path).query
-                params = urllib.parse.parse_qs(query)
-                audio_path = params.get('path', [''])[0]
-                if audio_path:
-                    get_cached_audio(audio_path)
-                    self.send_response(200)
-                    self.send_header('Content-Type', 'text/plain')
-                    self.end_headers()
-                    self.wfile.write(b'audio cached')
-                else:
-                    self.send_response(400)
-                    self.end_headers()
-                    self.wfile.write(b'missing path')
-            except Exception as e:
-                self.send_response(500)
-                self.end_headers()
-                self.wfile.write(str(e).encode())
         else:
             self.send_response(404)
             self.end_headers()
 
 if __name__ == "__main__":
-    port = int(sys.argv[1]) if len(sys.argv) > 1 else 18765
-    server = HTTPServer(('127.0.0.1', port), Handler)
-    print(f"FireRedASR service running on port {port}", file=sys.stderr)
+    parser = argparse.ArgumentParser()
+    parser.add_argument("port", nargs="?", type=int, default=18765)
+    parser.add_argument("--idle-timeout", type=int, default=0, help="空闲多少秒后自动退出（0 表示不退出）")
+    args = parser.parse_args()
+    
+    server = ThreadingHTTPServer(('127.0.0.1', args.port), Handler)
+    if args.idle_timeout > 0:
+        threading.Thread(target=idle_watchdog, args=(server, args.idle_timeout), daemon=True).start()
+    print(f"FireRedASR service running on port {args.port}", file=sys.stderr)
     server.serve_forever()
+    server.server_close()
 "#;
     
     std::fs::write(&script_path, script_content)
@@ -1960,10 +2420,107 @@ if __name__ == "__main__":
     Ok(script_path)
 }
 
+// 服务默认端口（未保存过端口或保存的端口无效时使用）
+const DEFAULT_SERVICE_PORT: u16 = 18765;
+
+/// 服务端口配置文件路径（~/.config/vosub/firered-service-port）
+fn get_service_port_config_path() -> Result<PathBuf, String> {
+    let base_dir = get_firered_base_dir()?;
+    Ok(base_dir.join("firered-service-port"))
+}
+
+/// 读取已保存的服务端口
+fn get_service_port() -> u16 {
+    get_service_port_config_path()
+        .ok()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|s| s.trim().parse::<u16>().ok())
+        .filter(|&port| port != 0)
+        .unwrap_or(DEFAULT_SERVICE_PORT)
+}
+
+/// 保存服务端口
+fn set_service_port(port: u16) -> Result<(), String> {
+    let config_path = get_service_port_config_path()?;
+    if let Some(parent) = config_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("创建配置目录失败: {}", e))?;
+    }
+    std::fs::write(&config_path, port.to_string())
+        .map_err(|e| format!("写入配置失败: {}", e))
+}
+
+/// 端口当前是否可以绑定
+fn is_port_available(port: u16) -> bool {
+    std::net::TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+/// 由系统分配一个空闲端口
+fn get_free_port() -> Result<u16, String> {
+    std::net::TcpListener::bind(("127.0.0.1", 0))
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .map_err(|e| format!("获取空闲端口失败: {}", e))
+}
+
+// 服务空闲自动退出的默认时长（秒），0 表示不自动退出
+const DEFAULT_IDLE_TIMEOUT_SECS: u32 = 600;
+
+/// 空闲超时配置文件路径（~/.config/vosub/firered-idle-timeout）
+fn get_idle_timeout_config_path() -> Result<PathBuf, String> {
+    let base_dir = get_firered_base_dir()?;
+    Ok(base_dir.join("firered-idle-timeout"))
+}
+
+/// 读取服务空闲自动退出时长（秒）
+pub fn get_service_idle_timeout() -> u32 {
+    get_idle_timeout_config_path()
+        .ok()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|s| s.trim().parse::<u32>().ok())
+        .unwrap_or(DEFAULT_IDLE_TIMEOUT_SECS)
+}
+
+/// 设置服务空闲自动退出时长（秒，0 表示不自动退出），下次启动服务时生效
+pub fn set_service_idle_timeout(secs: u32) -> Result<(), String> {
+    let config_path = get_idle_timeout_config_path()?;
+    if let Some(parent) = config_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("创建配置目录失败: {}", e))?;
+    }
+    std::fs::write(&config_path, secs.to_string())
+        .map_err(|e| format!("写入配置失败: {}", e))
+}
+
+/// 服务地址（使用已保存的端口）
+fn service_url(path: &str) -> String {
+    format!("http://127.0.0.1:{}{}", get_service_port(), path)
+}
+
 // 服务状态缓存
 static SERVICE_RUNNING: Lazy<Arc<std::sync::atomic::AtomicBool>> = Lazy::new(|| Arc::new(std::sync::atomic::AtomicBool::new(false)));
 static LAST_SERVICE_CHECK: Lazy<Arc<std::sync::Mutex<std::time::Instant>>> = Lazy::new(|| Arc::new(std::sync::Mutex::new(std::time::Instant::now())));
 
+// 本次启动的服务进程（用于跨平台停止服务，并在退出后回收，避免留下僵尸进程）
+static SERVICE_CHILD: Lazy<std::sync::Mutex<Option<std::process::Child>>> = Lazy::new(|| std::sync::Mutex::new(None));
+
+/// 结束并回收服务进程：先按进程树结束，2 秒内未退出则强制结束
+fn terminate_service_child(mut child: std::process::Child) {
+    if let Ok(Some(_)) = child.try_wait() {
+        return; // 已自行退出（如空闲超时），try_wait 已回收
+    }
+    log::info!("[FireRed] 停止服务进程 (PID: {})", child.id());
+    kill_process(child.id());
+    for _ in 0..10 {
+        if let Ok(Some(_)) = child.try_wait() {
+            return;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(200));
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
 /// 检查服务是否运行（带缓存，5秒内不重复检查）
 pub fn is_service_running() -> bool {
     // 如果缓存显示服务运行中，且距离上次检查不超过5秒，直接返回
@@ -1985,29 +2542,71 @@ pub fn is_service_running() -> bool {
     running
 }
 
-/// 实际检查服务健康状态
+/// 实际检查服务健康状态（GET /health，1 秒超时）
+/// 调用方可能位于异步运行时中，阻塞客户端不能在其中使用，因此放到独立线程执行
 fn check_service_health() -> bool {
-    std::process::Command::new("curl")
-        .args(["-s", "-o", "/dev/null", "-w", "%{http_code}", "--connect-timeout", "1", "http://127.0.0.1:18765/health"])
-        .output()
-        .map(|o| String::from_utf8_lossy(&o.stdout).trim() == "200")
-        .unwrap_or(false)
+    let url = service_url("/health");
+    std::thread::spawn(move || {
+        reqwest::blocking::Client::builder()
+            .timeout(std::time::Duration::from_secs(1))
+            .build()
+            .and_then(|client| client.get(&url).send())
+            .and_then(|response| {
+                // 端口上可能是别的程序，只有状态 200 且响应正文恰为 "ok" 才认为是本服务
+                let success = response.status().is_success();
+                response.text().map(|body| success && body == "ok")
+            })
+            .unwrap_or(false)
+    })
+    .join()
+    .unwrap_or(false)
+}
+
+/// 按 PID 结束进程（Windows 使用 taskkill，Unix 使用 kill）
+fn kill_process(pid: u32) {
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        
+        let _ = Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/T", "/F"])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output();
+    }
+    
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = Command::new("kill")
+            .arg(pid.to_string())
+            .output();
+    }
 }
 
 /// 停止 FireRedASR 服务
-fn stop_service() {
-    // 尝试通过 pkill 停止服务
-    let _ = std::process::Command::new("pkill")
-        .args(["-f", "firered_service.py"])
-        .output();
+pub fn stop_service() {
+    // 优先结束本次启动的服务进程
+    let child = SERVICE_CHILD.lock().ok().and_then(|mut child| child.take());
+    if let Some(child) = child {
+        terminate_service_child(child);
+    }
+    
+    // 服务可能由之前的应用实例启动（没有记录 PID），Unix 上再用 pkill 兜底
+    #[cfg(unix)]
+    if check_service_health() {
+        let _ = Command::new("pkill")
+            .args(["-f", "firered_service.py"])
+            .output();
+    }
     
-    // 等待服务停止
+    // 等待服务停止（直接检查，不使用运行状态缓存）
     for _ in 0..10 {
-        if !is_service_running() {
-            return;
+        if !check_service_health() {
+            break;
         }
         std::thread::sleep(std::time::Duration::from_millis(200));
     }
+    SERVICE_RUNNING.store(false, Ordering::SeqCst);
 }
 
 /// 启动服务（如果已运行则直接返回）
@@ -2021,14 +2620,29 @@ fn start_service() -> Result<(), String> {
     let script_path = write_service_script()?;
     let python_path = get_python_path()?;
     
+    // 优先沿用已保存的端口，被占用时改用系统分配的空闲端口并保存
+    let mut port = get_service_port();
+    if !is_port_available(port) {
+        port = get_free_port()?;
+        log::info!("[FireRed] 服务端口被占用，改用端口 {}", port);
+    }
+    set_service_port(port)?;
+    
     // 后台启动服务
-    std::process::Command::new(&python_path)
+    let child = std::process::Command::new(&python_path)
         .arg(&script_path)
-        .arg("18765")
+        .arg(port.to_string())
+        .arg("--idle-timeout")
+        .arg(get_service_idle_timeout().to_string())
         .stdout(std::process::Stdio::null())
         .stderr(std::process::Stdio::null())
         .spawn()
         .map_err(|e| format!("启动服务失败: {}", e))?;
+    // 替换前回收上一次启动的进程（例如空闲超时后已自行退出）
+    let previous = SERVICE_CHILD.lock().ok().and_then(|mut slot| slot.replace(child));
+    if let Some(previous) = previous {
+        terminate_service_child(previous);
+    }
     
     // 等待服务启动（缩短等待时间）
     for _ in 0..20 {
@@ -2061,7 +2675,7 @@ pub async fn preload_firered_service() -> Result<String, String> {
         .map_err(|e| format!("创建 HTTP 客户端失败: {}", e))?;
     
     let response = client
-        .get("http://127.0.0.1:18765/preload")
+        .get(service_url("/preload"))
         .send()
         .await
         .map_err(|e| format!("预加载请求失败: {}", e))?;
@@ -2073,8 +2687,8 @@ pub async fn preload_firered_service() -> Result<String, String> {
     }
 }
 
-/// 预加载音频文件到服务缓存
-/// 在打开音频文件时调用，可以加速后续的单条校正
+/// 预加载音频文件到解码缓存
+/// 在打开音频文件时调用，可以加速后续的单条校正（音频片段在 Rust 端切分）
 pub async fn preload_audio_for_correction(audio_path: String) -> Result<String, String> {
     // 检查环境
     let env_status = check_firered_env();
@@ -2085,32 +2699,14 @@ pub async fn preload_audio_for_correction(audio_path: String) -> Result<String,
     // 确保服务运行
     start_service()?;
     
-    // 使用 reqwest 调用预加载音频端点
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(60))
-        .build()
-        .map_err(|e| format!("创建 HTTP 客户端失败: {}", e))?;
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::waveform_generator::decode_audio_mono_cached(&audio_path)
+    })
+    .await
+    .map_err(|e| format!("Task error: {:?}", e))?
+    .map_err(|e| format!("预加载音频失败: {}", e))?;
     
-    // 简单的 URL 编码
-    let encoded_path = audio_path
-        .replace('%', "%25")
-        .replace(' ', "%20")
-        .replace('&', "%26")
-        .replace('?', "%3F")
-        .replace('#', "%23");
-    let url = format!("http://127.0.0.1:18765/preload_audio?path={}", encoded_path);
-    
-    let response = client
-        .get(&url)
-        .send()
-        .await
-        .map_err(|e| format!("预加载音频请求失败: {}", e))?;
-    
-    if response.status().is_success() {
-        Ok("音频已预加载到缓存".to_string())
-    } else {
-        Err(format!("预加载音频失败: HTTP {}", response.status()))
-    }
+    Ok("音频已预加载到缓存".to_string())
 }
 
 /// 校正单条字幕（使用持久化服务）
@@ -2121,13 +2717,21 @@ pub async fn correct_single_entry(
     original_text: String,
     language: String,
     preserve_case: bool,
+    beam_size: Option<u32>,
 ) -> Result<SingleCorrectionResult, String> {
+    let _operation = begin_operation("firered", OperationKind::Correcting);
+    
+    let beam_size = resolve_beam_size(beam_size)?;
+    
     // 检查环境
     let env_status = check_firered_env();
     if !env_status.ready {
         return Err("FireRedASR 环境未安装，请先安装环境".to_string());
     }
     
+    // GPU 环境下排队等待显卡空闲，避免与其他任务同时占用显存
+    let _gpu_permit = crate::gpu_guard::acquire_gpu_permit(&get_firered_active_env_type()).await?;
+    
     // 确保服务运行
     start_service()?;
     
@@ -2141,14 +2745,26 @@ pub async fn correct_single_entry(
         _ => "zh",
     };
     
+    // 在 Rust 端切分音频片段（解码结果会被缓存，连续校正同一文件时无需重复解码）
+    let chunks_dir = TempDirGuard(crate::temp_files::unique_temp_path("firered_single", ""));
+    let chunk_path = {
+        let audio_path = audio_path.clone();
+        let chunks_path = chunks_dir.0.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            crate::waveform_generator::extract_chunks(&audio_path, &[(start_ms as u64, end_ms as u64)], &chunks_path)
+        })
+        .await
+        .map_err(|e| format!("Task error: {:?}", e))??
+        .remove(0)
+    };
+    
     // 构建请求
     let request_body = serde_json::json!({
-        "audio_path": audio_path,
-        "start_ms": start_ms,
-        "end_ms": end_ms,
+        "chunk_path": chunk_path.to_string_lossy(),
         "original_text": original_text,
         "language": lang_code,
-        "preserve_case": preserve_case
+        "preserve_case": preserve_case,
+        "beam_size": beam_size
     });
     
     // 使用 reqwest 发送请求（比 curl 更快，无需启动新进程）
@@ -2158,7 +2774,7 @@ pub async fn correct_single_entry(
         .map_err(|e| format!("创建 HTTP 客户端失败: {}", e))?;
     
     let response = client
-        .post("http://127.0.0.1:18765/")
+        .post(service_url("/"))
         .header("Content-Type", "application/json")
         .body(request_body.to_string())
         .send()
@@ -2178,9 +2794,16 @@ pub async fn correct_single_entry(
     }
     
     // 解析 JSON
-    let result: SingleCorrectionResult = serde_json::from_str(&result_json)
+    let mut result: SingleCorrectionResult = serde_json::from_str(&result_json)
         .map_err(|e| format!("解析校正结果失败: {} - 原始响应: {}", e, result_json))?;
     
+    // 与整文件校正一致，用本地词典修正结果
+    let (corrected, has_diff) = apply_dictionary_to_correction(
+        &result.original, result.corrected, result.has_diff, &load_correction_dictionary(),
+    );
+    result.corrected = corrected;
+    result.has_diff = has_diff;
+    
     Ok(result)
 }
 
@@ -2248,3 +2871,98 @@ pub fn uninstall_firered_env() -> Result<String, String> {
         }
     }
 }
+
+// ============ 自动采纳校正结果 ============
+
+/// 自动采纳规则
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcceptRules {
+    /// 仅标点、空白或大小写不同的修改自动采纳
+    pub punctuation_and_case: bool,
+    /// 编辑距离不超过该值的修改自动采纳（0 表示不按编辑距离采纳）
+    pub max_edit_distance: usize,
+    /// 按编辑距离采纳时要求修改前后字数相同（只替换字，不增删字）
+    pub require_same_length: bool,
+}
+
+/// 去除标点和空白并转小写，用于比较实际文字内容
+fn normalize_for_compare(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// 计算两个字符串的编辑距离（按字符）
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        curr[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+/// 判断一条校正是否满足自动采纳规则
+fn is_safe_correction(original: &str, corrected: &str, rules: &AcceptRules) -> bool {
+    let norm_original = normalize_for_compare(original);
+    let norm_corrected = normalize_for_compare(corrected);
+
+    if rules.punctuation_and_case && norm_original == norm_corrected {
+        return true;
+    }
+
+    if rules.max_edit_distance == 0 {
+        return false;
+    }
+
+    if rules.require_same_length && original.chars().count() != corrected.chars().count() {
+        return false;
+    }
+
+    edit_distance(original, corrected) <= rules.max_edit_distance
+}
+
+/// 按规则自动采纳 FireRedASR 校正结果
+/// 满足规则的条目使用校正后的文本，其余条目保留原文，留待人工审核
+pub fn auto_accept_corrections(entries: &[CorrectionEntry], rules: &AcceptRules) -> Vec<SubtitleEntry> {
+    let mut accepted = 0;
+
+    let result = entries
+        .iter()
+        .map(|entry| {
+            let use_corrected = entry.has_diff
+                && !entry.corrected.trim().is_empty()
+                && is_safe_correction(&entry.original, &entry.corrected, rules);
+            if use_corrected {
+                accepted += 1;
+            }
+
+            SubtitleEntry {
+                id: entry.id,
+                start_time: entry.start_time.clone(),
+                end_time: entry.end_time.clone(),
+                text: if use_corrected { entry.corrected.clone() } else { entry.original.clone() },
+                confidence: None,
+                events: None,
+            }
+        })
+        .collect();
+
+    log::info!(
+        "[FireRed] 自动采纳 {} 条校正，{} 条需要人工审核",
+        accepted,
+        entries.iter().filter(|e| e.has_diff).count() - accepted
+    );
+
+    result
+}
@@ -0,0 +1,64 @@
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+// GPU 任务默认并发数（同时只允许一个任务占用显卡，避免 CUDA OOM）
+const DEFAULT_GPU_CONCURRENCY: usize = 1;
+
+// 全局 GPU 信号量：转录与校正任务共享
+static GPU_SEMAPHORE: Lazy<Arc<Semaphore>> =
+    Lazy::new(|| Arc::new(Semaphore::new(DEFAULT_GPU_CONCURRENCY)));
+
+// 当前配置的 GPU 并发上限
+static GPU_CONCURRENCY: AtomicUsize = AtomicUsize::new(DEFAULT_GPU_CONCURRENCY);
+
+/// 获取 GPU 执行许可
+/// 仅当环境类型为 "gpu" 时排队等待，CPU 任务直接返回 None（不限制并发）
+/// 返回的许可在 drop 时自动释放
+pub async fn acquire_gpu_permit(env_type: &str) -> Result<Option<OwnedSemaphorePermit>, String> {
+    if env_type != "gpu" {
+        return Ok(None);
+    }
+
+    let semaphore = GPU_SEMAPHORE.clone();
+    if semaphore.available_permits() == 0 {
+        log::info!("[GPU] 已有任务占用显卡，当前任务进入排队");
+    }
+
+    semaphore
+        .acquire_owned()
+        .await
+        .map(Some)
+        .map_err(|e| format!("获取 GPU 执行许可失败: {}", e))
+}
+
+/// 设置 GPU 任务并发上限（至少为 1）
+pub fn set_gpu_concurrency(limit: usize) -> usize {
+    let limit = limit.max(1);
+    let old = GPU_CONCURRENCY.swap(limit, Ordering::SeqCst);
+
+    if limit > old {
+        GPU_SEMAPHORE.add_permits(limit - old);
+    } else if limit < old {
+        // 先回收当前空闲的许可
+        let remaining = (old - limit) - GPU_SEMAPHORE.forget_permits(old - limit);
+        if remaining > 0 {
+            // 剩余部分等待正在运行的任务释放后再回收
+            let semaphore = GPU_SEMAPHORE.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Ok(permits) = semaphore.acquire_many_owned(remaining as u32).await {
+                    permits.forget();
+                }
+            });
+        }
+    }
+
+    log::info!("[GPU] 并发上限已设置为 {}", limit);
+    limit
+}
+
+/// 获取当前 GPU 任务并发上限
+pub fn get_gpu_concurrency() -> usize {
+    GPU_CONCURRENCY.load(Ordering::SeqCst)
+}
@@ -1,20 +1,40 @@
+mod app_config;
 mod srt_parser;
 mod waveform_generator;
 mod whisper_python_transcriber;
 mod sensevoice_transcriber;
 mod firered_corrector;
+mod gpu_guard;
+mod backup;
+mod disk_space;
+mod model_registry;
+mod active_operation;
+mod file_backup;
+mod dictionary;
+mod download_config;
+mod recent_files;
+mod temp_files;
+mod python_env;
 
 use srt_parser::{
-    read_srt_file, write_srt_file, SRTFile, SubtitleEntry,
-    export_to_txt, export_to_vtt, export_to_markdown, export_to_fcpxml,
-    check_file_permission, unlock_file, FilePermissionCheck,
+    read_srt_file, write_srt_file, write_srt_file_keep_ids, read_ass_file, read_vtt_file, read_csv_file, SRTFile, replace_in_subtitles, ReplaceResult, scale_subtitles, rescale_subtitles, close_gaps, enforce_min_duration, SubtitleEntry,
+    export_to_txt_with_options, TxtExportOptions, export_to_vtt, export_to_markdown, export_to_fcpxml, TitleStyle, export_cue_sheet, export_to_ass, AssStyle, export_to_lrc, export_to_csv, export_to_ttml, export_all_formats,
+    export_confidence_html,
+    check_file_permission, unlock_file, FilePermissionCheck, detect_subtitle_language,
+    subtitles_from_chapters, normalize_punctuation_style, PunctuationStyleOptions, add_cjk_spaces, word_frequency,
+    detect_empty_content_cues, remove_empty_content_cues,
+    validate_subtitles, ValidationIssue, MAX_TWO_DIGIT_HOUR_MS,
+    split_into_reels, write_reel_files, fix_reversed_timestamps, rebase_timeline, shift_subtitles, merge_subtitles,
+    split_subtitles, compute_reading_speed, CpsInfo, apply_dictionary_to_entries, DictionaryEntry,
+    ensemble_merge, EnsembleStrategy, map_text_to_timing, check_byte_length,
 };
 use whisper_python_transcriber::{
     check_whisper_env, install_whisper_env, transcribe_with_whisper,
     uninstall_whisper_env, uninstall_whisper_env_by_type, switch_whisper_env,
     cancel_whisper_transcription, cancel_whisper_model_download,
     get_whisper_models, delete_whisper_model, open_whisper_model_dir,
-    download_whisper_model,
+    download_whisper_model, get_whisper_python_version, force_align,
+    transcribe_with_fallback, transcribe_with_whisper_report, TranscriptionReport, WhisperOptions,
     WhisperEnvStatus, WhisperModelInfo,
 };
 use sensevoice_transcriber::{
@@ -22,26 +42,38 @@ use sensevoice_transcriber::{
     uninstall_sensevoice_env, uninstall_sensevoice_env_by_type, switch_sensevoice_env,
     cancel_sensevoice_transcription, cancel_sensevoice_model_download, SenseVoiceEnvStatus,
     get_sensevoice_models, download_sensevoice_model, delete_sensevoice_model, open_sensevoice_model_dir,
-    SenseVoiceModelInfo,
+    get_sensevoice_python_version, SenseVoiceModelInfo,
+    transcribe_with_sensevoice_report, transcribe_with_sensevoice_in_env,
 };
 use firered_corrector::{
     check_firered_env, install_firered_env, correct_with_firered, correct_single_entry,
     uninstall_firered_env, uninstall_firered_env_by_type, switch_firered_env,
     cancel_firered_correction, cancel_firered_model_download, preload_firered_service, is_service_running,
+    get_service_idle_timeout, set_service_idle_timeout, stop_service as stop_firered_service,
     preload_audio_for_correction,
     get_firered_models, download_firered_model, delete_firered_model, open_firered_model_dir,
+    get_firered_python_version, auto_accept_corrections, AcceptRules, correct_with_firered_report, CorrectionReport,
     FireRedEnvStatus, CorrectionEntry, SingleCorrectionResult, FireRedModelInfo,
 };
-use waveform_generator::{generate_waveform_with_progress, ProgressCallback};
+use backup::{export_app_backup, import_app_backup, BackupSummary};
+use disk_space::{DiskSpaceInfo, EngineUsage};
+use model_registry::FileStatus;
+use active_operation::OperationKind;
+use waveform_generator::{
+    generate_waveform_with_progress, generate_stereo_waveform_with_progress, StereoWaveform,
+    generate_detailed_waveform_with_progress, DetailedWaveform, generate_waveform_region, compute_cue_loudness, trim_audio_silence, compute_coverage, CoverageReport, detect_silence, ProgressCallback,
+    measure_true_peak, export_flashcards, is_video_file, extract_audio_to_wav,
+};
 use std::fs;
 use std::sync::Mutex;
 use tauri::menu::{MenuBuilder, MenuItem, PredefinedMenuItem, SubmenuBuilder};
 use tauri::tray::TrayIconBuilder;
 use tauri::image::Image;
-use tauri::{Emitter, Manager};
+use tauri::{Emitter, Listener, Manager};
 use tauri_plugin_prevent_default::Flags;
 use tauri_plugin_log::{Target, TargetKind, TimezoneStrategy, RotationStrategy};
 use log::info;
+use recent_files::RecentFileInfo;
 use once_cell::sync::Lazy;
 
 // 全局状态：存储通过文件关联打开的待处理文件路径
@@ -50,6 +82,12 @@ static PENDING_FILE_OPEN: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(
 // 全局状态：tray icon ID
 static PROGRESS_TRAY_ID: &str = "progress-tray";
 
+// 转录 + 校正流水线的取消标志（阶段进行中与两个阶段之间都会检查）
+static PIPELINE_CANCELLED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+// 批量转录的取消标志（在文件之间检查；各引擎的取消命令会同时设置）
+static BATCH_CANCELLED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
 fn greet(name: &str) -> String {
@@ -63,10 +101,180 @@ fn read_srt(file_path: String) -> Result<SRTFile, String> {
     read_srt_file(&file_path)
 }
 
-/// Write SRT file
+/// Read and parse an ASS/SSA file (italic/bold overrides kept as <i>/<b> tags)
+#[tauri::command]
+fn read_ass(file_path: String) -> Result<SRTFile, String> {
+    read_ass_file(&file_path)
+}
+
+/// Read and parse a WebVTT file
+#[tauri::command]
+fn read_vtt(file_path: String) -> Result<SRTFile, String> {
+    read_vtt_file(&file_path)
+}
+
+/// 读取 CSV（id,start,end,text），用于合并表格中审校后的字幕
+#[tauri::command]
+fn read_csv(file_path: String) -> Result<Vec<SubtitleEntry>, String> {
+    read_csv_file(&file_path)
+}
+
+/// 检测字幕语言（基于字符脚本比例和常用词的启发式判断）
+#[tauri::command]
+fn detect_language(entries: Vec<SubtitleEntry>) -> String {
+    detect_subtitle_language(&entries)
+}
+
+/// 从章节时间标记（如 YouTube 简介中的 "MM:SS 标题"）生成占位字幕
+#[tauri::command]
+fn import_chapters_as_srt(text: String, total_duration_ms: u32) -> Vec<SubtitleEntry> {
+    subtitles_from_chapters(&text, total_duration_ms)
+}
+
+/// 统一省略号与破折号写法（"..." / "…"，"--" / "—"）
+#[tauri::command]
+fn normalize_ellipsis_dashes(entries: Vec<SubtitleEntry>, options: PunctuationStyleOptions) -> Vec<SubtitleEntry> {
+    normalize_punctuation_style(&entries, &options)
+}
+
+/// 在汉字与英文字母/数字之间添加空格（字符范围与"批量添加中英文空格"菜单一致，但保留原有换行与空白）
+#[tauri::command]
+fn add_cjk_spaces_cmd(entries: Vec<SubtitleEntry>) -> Vec<SubtitleEntry> {
+    add_cjk_spaces(&entries)
+}
+
+/// 统计字幕词频（中日韩文本按二元组切分，拉丁文本按空白切分）
+#[tauri::command]
+fn subtitle_word_frequency(
+    entries: Vec<SubtitleEntry>,
+    min_length: Option<usize>,
+    stopwords: Option<Vec<String>>,
+) -> Vec<(String, usize)> {
+    word_frequency(&entries, min_length.unwrap_or(1), &stopwords.unwrap_or_default())
+}
+
+/// 检测内容为空的字幕（去除标点、空白后无文字），返回字幕 id 列表
+#[tauri::command]
+fn detect_empty_cues(entries: Vec<SubtitleEntry>) -> Vec<u32> {
+    detect_empty_content_cues(&entries)
+}
+
+/// 删除内容为空的字幕并重新编号
+#[tauri::command]
+fn remove_empty_cues(entries: Vec<SubtitleEntry>) -> Vec<SubtitleEntry> {
+    remove_empty_content_cues(&entries)
+}
+
+/// 校验字幕时间轴（重叠、乱序、零时长、起止颠倒），返回带字幕下标的问题列表
+#[tauri::command]
+fn validate_subtitle_entries(entries: Vec<SubtitleEntry>) -> Vec<ValidationIssue> {
+    validate_subtitles(&entries)
+}
+
+/// 将超出上限的时间戳截断到 max_ms（默认 99:59:59,999）
+#[tauri::command]
+fn clamp_max_time(entries: Vec<SubtitleEntry>, max_ms: Option<u64>) -> Vec<SubtitleEntry> {
+    srt_parser::clamp_max_time(&entries, max_ms.unwrap_or(MAX_TWO_DIGIT_HOUR_MS))
+}
+
+/// 检查 UTF-8 字节数超过上限的字幕，返回字幕 id 列表
+#[tauri::command]
+fn check_cue_byte_length(entries: Vec<SubtitleEntry>, max_bytes: usize) -> Vec<u32> {
+    check_byte_length(&entries, max_bytes)
+}
+
+/// 交换结束时间早于开始时间的字幕的起止时间
+#[tauri::command]
+fn fix_reversed_timestamps_cmd(entries: Vec<SubtitleEntry>) -> Vec<SubtitleEntry> {
+    fix_reversed_timestamps(&entries)
+}
+
+/// Write SRT file (entries are renumbered sequentially from 1)
 #[tauri::command]
 fn write_srt(file_path: String, entries: Vec<SubtitleEntry>) -> Result<(), String> {
-    write_srt_file(&file_path, &entries)
+    backup_existing_srt(&file_path);
+    write_srt_file(&file_path, &entries)?;
+    file_backup::remove_autosave(&file_path);
+    Ok(())
+}
+
+/// 写入 SRT 文件并保留原有序号（默认的 write_srt 会从 1 开始重新编号）
+#[tauri::command]
+fn write_srt_keep_ids(file_path: String, entries: Vec<SubtitleEntry>) -> Result<(), String> {
+    backup_existing_srt(&file_path);
+    write_srt_file_keep_ids(&file_path, &entries)?;
+    file_backup::remove_autosave(&file_path);
+    Ok(())
+}
+
+/// 覆盖保存前备份原文件；备份失败只记录日志，不阻止保存
+fn backup_existing_srt(file_path: &str) {
+    match file_backup::backup_before_overwrite(file_path) {
+        Ok(Some(backup_path)) => log::info!("已备份原文件到 {}", backup_path.display()),
+        Ok(None) => {}
+        Err(e) => log::warn!("备份原文件失败: {}", e),
+    }
+}
+
+/// 设置保存时是否自动备份原文件
+#[tauri::command]
+fn set_backup_enabled(enabled: bool) -> Result<(), String> {
+    file_backup::set_backup_enabled(enabled)
+}
+
+/// 获取保存时是否自动备份原文件
+#[tauri::command]
+fn get_backup_enabled() -> bool {
+    file_backup::is_backup_enabled()
+}
+
+/// 自动保存到 <原文件>.autosave，返回自动保存文件路径
+#[tauri::command]
+fn autosave_srt(original_path: String, entries: Vec<SubtitleEntry>) -> Result<String, String> {
+    file_backup::autosave_srt(&original_path, &entries)
+}
+
+/// 检查是否存在比原文件更新的自动保存
+#[tauri::command]
+fn check_autosave(original_path: String) -> Option<file_backup::AutosaveInfo> {
+    file_backup::check_autosave(&original_path)
+}
+
+/// 添加本地词典词条（错词 -> 正确写法），返回更新后的词典
+#[tauri::command]
+fn add_dictionary_entry(wrong: String, right: String) -> Result<Vec<DictionaryEntry>, String> {
+    dictionary::add_entry(&wrong, &right)
+}
+
+/// 删除本地词典词条，返回更新后的词典
+#[tauri::command]
+fn remove_dictionary_entry(wrong: String) -> Result<Vec<DictionaryEntry>, String> {
+    dictionary::remove_entry(&wrong)
+}
+
+/// 获取本地词典
+#[tauri::command]
+fn get_dictionary() -> Result<Vec<DictionaryEntry>, String> {
+    dictionary::load_dictionary()
+}
+
+/// 在所有字幕中查找替换（regex 为 true 时按正则匹配，替换文本可引用 $1 等分组）
+#[tauri::command]
+fn replace_in_subtitles_cmd(
+    entries: Vec<SubtitleEntry>,
+    find: String,
+    replace: String,
+    regex: bool,
+    case_sensitive: bool,
+) -> Result<ReplaceResult, String> {
+    replace_in_subtitles(&entries, &find, &replace, regex, case_sensitive)
+}
+
+/// 用本地词典替换字幕中的常见识别错误（英文按整词匹配，中日韩文字按子串匹配）
+#[tauri::command]
+fn apply_dictionary(entries: Vec<SubtitleEntry>) -> Result<Vec<SubtitleEntry>, String> {
+    let dictionary = dictionary::load_dictionary()?;
+    Ok(apply_dictionary_to_entries(&entries, &dictionary))
 }
 
 /// 检查文件写入权限
@@ -92,8 +300,49 @@ fn read_audio_file(file_path: String) -> Result<String, String> {
     Ok(base64_data)
 }
 
+// Upper bound for a single chunk, keeps each IPC payload small
+const MAX_AUDIO_CHUNK_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Get the size of an audio file in bytes (used to plan chunked reads)
+#[tauri::command]
+fn get_audio_file_size(file_path: String) -> Result<u64, String> {
+    fs::metadata(&file_path)
+        .map(|m| m.len())
+        .map_err(|e| format!("Failed to read audio file metadata: {}", e))
+}
+
+/// Read a slice of an audio file and return it as base64
+/// Large files should be streamed with this instead of read_audio_file
+#[tauri::command]
+fn read_audio_file_chunked(file_path: String, offset: u64, length: u64) -> Result<String, String> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut file = fs::File::open(&file_path)
+        .map_err(|e| format!("Failed to open audio file: {}", e))?;
+    let size = file.metadata()
+        .map_err(|e| format!("Failed to read audio file metadata: {}", e))?
+        .len();
+
+    if length > MAX_AUDIO_CHUNK_BYTES {
+        return Err(format!("Chunk length {} exceeds the maximum of {} bytes", length, MAX_AUDIO_CHUNK_BYTES));
+    }
+    let end = offset.checked_add(length).ok_or_else(|| "Chunk range overflows".to_string())?;
+    if offset > size || end > size {
+        return Err(format!("Chunk range {}..{} is outside the file size {}", offset, end, size));
+    }
+
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|e| format!("Failed to seek audio file: {}", e))?;
+    let mut buffer = vec![0u8; length as usize];
+    file.read_exact(&mut buffer)
+        .map_err(|e| format!("Failed to read audio file: {}", e))?;
+
+    Ok(base64_encode(&buffer))
+}
+
 /// Generate waveform data from an audio file
 /// Returns a vector of normalized amplitude values (0.0 to 1.0)
+/// The decoded duration (ms) is emitted as a "waveform-duration" event before returning
 /// target_samples: number of data points to generate (default: 2000)
 #[tauri::command]
 async fn generate_audio_waveform(
@@ -125,7 +374,135 @@ async fn generate_audio_waveform(
     .await
     .map_err(|e| format!("Task error: {:?}", e))??;
     
-    Ok(result)
+    let (peaks, duration_ms) = result;
+    let _ = app_handle.emit("waveform-duration", duration_ms);
+    
+    Ok(peaks)
+}
+
+/// Generate separate left/right waveform lanes
+/// channels: 2 (default) keeps channels separate, 1 returns the mono mix in both lanes
+#[tauri::command]
+async fn generate_audio_waveform_stereo(
+    app_handle: tauri::AppHandle,
+    file_path: String,
+    target_samples: Option<usize>,
+    channels: Option<u8>,
+) -> Result<StereoWaveform, String> {
+    let samples = target_samples.unwrap_or(2000);
+    let channels = channels.unwrap_or(2);
+    if channels != 1 && channels != 2 {
+        return Err(format!("Unsupported channel count: {} (expected 1 or 2)", channels));
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let callback: ProgressCallback = Box::new(move |progress| {
+            let _ = app_handle.emit("waveform-progress", progress);
+        });
+
+        if channels == 1 {
+            let (mono, _) = generate_waveform_with_progress(&file_path, samples, Some(callback))?;
+            Ok(StereoWaveform { left: mono.clone(), right: mono })
+        } else {
+            generate_stereo_waveform_with_progress(&file_path, samples, Some(callback))
+        }
+    })
+    .await
+    .map_err(|e| format!("Task error: {:?}", e))?
+}
+
+/// Generate peak and RMS envelopes (bright peak outline over a darker RMS body)
+#[tauri::command]
+async fn generate_audio_waveform_detailed(
+    app_handle: tauri::AppHandle,
+    file_path: String,
+    target_samples: Option<usize>,
+) -> Result<DetailedWaveform, String> {
+    let samples = target_samples.unwrap_or(2000);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let callback: ProgressCallback = Box::new(move |progress| {
+            let _ = app_handle.emit("waveform-progress", progress);
+        });
+        generate_detailed_waveform_with_progress(&file_path, samples, Some(callback))
+    })
+    .await
+    .map_err(|e| format!("Task error: {:?}", e))?
+}
+
+/// Generate a higher-resolution min/max waveform for a zoomed time region
+#[tauri::command]
+async fn generate_waveform_region_cmd(
+    file_path: String,
+    start_ms: u64,
+    end_ms: u64,
+    target_samples: Option<usize>,
+) -> Result<Vec<f32>, String> {
+    let samples = target_samples.unwrap_or(2000);
+    tauri::async_runtime::spawn_blocking(move || generate_waveform_region(&file_path, start_ms, end_ms, samples))
+        .await
+        .map_err(|e| format!("Task error: {:?}", e))?
+}
+
+/// 计算每条字幕对应音频片段的响度（RMS, dBFS）
+/// 返回 (字幕 id, rms_db) 列表，用于标记需要提升电平的台词
+#[tauri::command]
+async fn cue_loudness(audio_path: String, entries: Vec<SubtitleEntry>) -> Result<Vec<(u32, f32)>, String> {
+    tauri::async_runtime::spawn_blocking(move || compute_cue_loudness(&audio_path, &entries))
+        .await
+        .map_err(|e| format!("Task error: {:?}", e))?
+}
+
+/// 测量音频真峰值（dBFS，含样本间峰值），前端据此建议归一化增益
+#[tauri::command]
+async fn audio_peak_db(audio_path: String) -> Result<f32, String> {
+    tauri::async_runtime::spawn_blocking(move || measure_true_peak(&audio_path))
+        .await
+        .map_err(|e| format!("Task error: {:?}", e))?
+}
+
+/// 检测音频中的静音区间（低于 threshold_db 且持续至少 min_silence_ms），用于吸附字幕边界
+#[tauri::command]
+async fn detect_silence_cmd(
+    file_path: String,
+    threshold_db: Option<f32>,
+    min_silence_ms: Option<u32>,
+) -> Result<Vec<(u64, u64)>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        detect_silence(&file_path, threshold_db.unwrap_or(-40.0), min_silence_ms.unwrap_or(300))
+    })
+    .await
+    .map_err(|e| format!("Task error: {:?}", e))?
+}
+
+/// 统计字幕对检测到的语音的覆盖率，并列出没有字幕的语音片段
+#[tauri::command]
+async fn subtitle_coverage(
+    audio_path: String,
+    entries: Vec<SubtitleEntry>,
+    threshold_db: Option<f32>,
+) -> Result<CoverageReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        compute_coverage(&audio_path, &entries, threshold_db.unwrap_or(-40.0))
+    })
+    .await
+    .map_err(|e| format!("Task error: {:?}", e))?
+}
+
+/// 裁剪音频首尾静音，输出 16kHz 单声道 WAV
+/// 返回开头被裁掉的毫秒数，前端据此偏移字幕时间
+#[tauri::command]
+async fn trim_leading_silence(
+    input_path: String,
+    output_path: String,
+    threshold_db: Option<f32>,
+    padding_ms: Option<u64>,
+) -> Result<u64, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        trim_audio_silence(&input_path, &output_path, threshold_db.unwrap_or(-45.0), padding_ms.unwrap_or(200))
+    })
+    .await
+    .map_err(|e| format!("Task error: {:?}", e))?
 }
 
 /// 触发前端打开文件事件
@@ -403,14 +780,103 @@ fn get_whisper_models_cmd() -> Vec<WhisperModelInfo> {
 }
 
 /// 转录音频文件为字幕
+/// fallback_models: 显存不足或模型加载失败时依次降级使用的模型（如 ["medium", "small"]）
 #[tauri::command]
 async fn transcribe_audio_to_subtitles(
     window: tauri::Window,
     audio_path: String,
     model_size: String,
     language: String,
+    fallback_models: Option<Vec<String>>,
+    beam_size: Option<usize>,
+    temperature: Option<f32>,
+    word_level: Option<bool>,
+    word_group_chars: Option<usize>,
+    initial_prompt: Option<String>,
+    task: Option<String>,
 ) -> Result<Vec<SubtitleEntry>, String> {
-    transcribe_with_whisper(audio_path, model_size, language, window).await
+    let options = WhisperOptions { beam_size, temperature, word_level, word_group_chars, initial_prompt, task, env_type: None };
+    let fallback_models = fallback_models.unwrap_or_default();
+
+    // 视频文件先提取音轨，转录完成后删除临时 WAV
+    let report = if is_video_file(&audio_path) {
+        let wav_file = temp_files::TempFileGuard(extract_audio_to_wav_cmd(audio_path).await?.into());
+        let wav_path = wav_file.0.to_string_lossy().to_string();
+        transcribe_with_fallback(wav_path, model_size, language.clone(), fallback_models, options, window.clone()).await?
+    } else {
+        transcribe_with_fallback(audio_path, model_size, language.clone(), fallback_models, options, window.clone()).await?
+    };
+
+    emit_detected_language(&window, &language, &report);
+    Ok(report.entries)
+}
+
+/// 选择"自动"语言时通知前端识别出的语言
+/// 只由交互式转录命令调用，批处理、流水线等共用转录流程的任务不会弹出提示
+fn emit_detected_language(window: &tauri::Window, language: &str, report: &TranscriptionReport) {
+    if language != "auto" {
+        return;
+    }
+    if let Some(detected) = report.language.as_deref() {
+        let _ = window.emit("transcription-language", detected);
+    }
+}
+
+/// 使用 ffmpeg 从视频中提取 16kHz 单声道 WAV，返回临时文件路径
+/// 每次调用生成独立的文件，调用方用完后负责删除
+#[tauri::command]
+async fn extract_audio_to_wav_cmd(video_path: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || extract_audio_to_wav(&video_path))
+        .await
+        .map_err(|e| format!("Task error: {:?}", e))?
+}
+
+/// 将已知文稿逐行对齐到音频，生成带时间轴的字幕
+#[tauri::command]
+async fn align_transcript_to_audio(
+    audio_path: String,
+    transcript_lines: Vec<String>,
+    language: String,
+    model_size: Option<String>,
+) -> Result<Vec<SubtitleEntry>, String> {
+    force_align(audio_path, transcript_lines, language, model_size.unwrap_or_else(|| "base".to_string())).await
+}
+
+/// 转录音频文件为字幕，返回结果及运行信息（设备、模型、耗时）
+#[tauri::command]
+async fn transcribe_audio_with_report(
+    window: tauri::Window,
+    audio_path: String,
+    model_size: String,
+    language: String,
+) -> Result<TranscriptionReport, String> {
+    let report = transcribe_with_whisper_report(audio_path, model_size, language.clone(), WhisperOptions::default(), window.clone()).await?;
+    emit_detected_language(&window, &language, &report);
+    Ok(report)
+}
+
+/// 重新转录但保留现有时间轴：新文本按时间重叠映射到已有字幕，开始/结束时间不变
+#[tauri::command]
+async fn retranscribe_keep_timing(
+    window: tauri::Window,
+    existing: Vec<SubtitleEntry>,
+    audio_path: String,
+    model_size: String,
+    language: String,
+) -> Result<Vec<SubtitleEntry>, String> {
+    if existing.is_empty() {
+        return Err("没有可保留时间轴的字幕".to_string());
+    }
+
+    let transcribed = if is_video_file(&audio_path) {
+        let wav_file = temp_files::TempFileGuard(extract_audio_to_wav_cmd(audio_path).await?.into());
+        let wav_path = wav_file.0.to_string_lossy().to_string();
+        transcribe_with_whisper(wav_path, model_size, language, WhisperOptions::default(), window).await?
+    } else {
+        transcribe_with_whisper(audio_path, model_size, language, WhisperOptions::default(), window).await?
+    };
+
+    Ok(map_text_to_timing(&existing, &transcribed))
 }
 
 /// 下载 Whisper 模型
@@ -419,6 +885,42 @@ async fn download_whisper_model_cmd(window: tauri::Window, model_name: String) -
     download_whisper_model(&model_name, window).await
 }
 
+/// 设置模型的显示名称（label 为空时恢复默认名称）
+#[tauri::command]
+fn relabel_model(backend: String, name: String, label: Option<String>) -> Result<(), String> {
+    model_registry::relabel_model(&backend, &name, label)
+}
+
+/// 从模型列表中移除已登记的模型（不删除模型文件）
+#[tauri::command]
+fn forget_model(backend: String, name: String) -> Result<(), String> {
+    model_registry::forget_model(&backend, &name)
+}
+
+/// 设置模型下载代理（http://、https://），传入空字符串清除
+#[tauri::command]
+fn set_download_proxy(url: String) -> Result<(), String> {
+    download_config::set_download_proxy(&url)
+}
+
+/// 获取当前生效的模型下载代理
+#[tauri::command]
+fn get_download_proxy() -> Option<String> {
+    download_config::get_download_proxy()
+}
+
+/// 设置模型源镜像地址（service: "huggingface" | "modelscope"），传入空字符串恢复官方地址
+#[tauri::command]
+fn set_model_mirror(service: String, base_url: String) -> Result<(), String> {
+    download_config::set_model_mirror(&service, &base_url)
+}
+
+/// 获取模型源当前使用的地址
+#[tauri::command]
+fn get_model_mirror(service: String) -> Result<String, String> {
+    download_config::get_model_mirror(&service)
+}
+
 /// 删除 Whisper 模型
 #[tauri::command]
 fn delete_whisper_model_cmd(model_size: String) -> Result<String, String> {
@@ -428,6 +930,7 @@ fn delete_whisper_model_cmd(model_size: String) -> Result<String, String> {
 /// 取消转录任务
 #[tauri::command]
 fn cancel_whisper_task() {
+    BATCH_CANCELLED.store(true, std::sync::atomic::Ordering::SeqCst);
     cancel_whisper_transcription();
 }
 
@@ -476,13 +979,255 @@ async fn install_sensevoice(window: tauri::Window, use_gpu: Option<bool>) -> Res
 }
 
 /// 使用 SenseVoice 转录音频
+/// keep_events: 为 true 时保留情绪/音频事件标签（如 laughter、happy）到字幕的 events 字段
 #[tauri::command]
 async fn transcribe_with_sensevoice_model(
     window: tauri::Window,
     audio_path: String,
     language: String,
+    keep_events: Option<bool>,
 ) -> Result<Vec<SubtitleEntry>, String> {
-    transcribe_with_sensevoice(audio_path, language, window).await
+    let report = transcribe_with_sensevoice_report(audio_path, language.clone(), keep_events.unwrap_or(false), window.clone()).await?;
+    emit_detected_language(&window, &language, &report);
+    Ok(report.entries)
+}
+
+/// 使用 SenseVoice 转录音频，返回结果及运行信息（设备、模型、耗时）
+#[tauri::command]
+async fn transcribe_with_sensevoice_model_report(
+    window: tauri::Window,
+    audio_path: String,
+    language: String,
+) -> Result<TranscriptionReport, String> {
+    let report = transcribe_with_sensevoice_report(audio_path, language.clone(), false, window.clone()).await?;
+    emit_detected_language(&window, &language, &report);
+    Ok(report)
+}
+
+/// 转录并直接写入 SRT 文件的结果
+#[derive(serde::Serialize)]
+struct TranscribeToSrtResult {
+    output_path: String,
+    cue_count: usize,
+}
+
+/// 转录音频并直接写入 SRT 文件（供批处理脚本/自动化调用，无需前端中转）
+/// engine: "whisper" | "sensevoice"；model 仅对 Whisper 生效
+#[tauri::command]
+async fn transcribe_to_srt(
+    window: tauri::Window,
+    audio_path: String,
+    output_path: String,
+    engine: String,
+    model: Option<String>,
+    language: String,
+) -> Result<TranscribeToSrtResult, String> {
+    let entries = match engine.as_str() {
+        "whisper" => {
+            let model = model.unwrap_or_else(|| "base".to_string());
+            transcribe_with_whisper(audio_path, model, language, WhisperOptions::default(), window).await?
+        }
+        "sensevoice" => transcribe_with_sensevoice(audio_path, language, false, window).await?,
+        _ => return Err(format!("不支持的引擎: {}", engine)),
+    };
+
+    write_srt_file(&output_path, &entries)?;
+
+    Ok(TranscribeToSrtResult {
+        output_path,
+        cue_count: entries.len(),
+    })
+}
+
+// ============ 批量转录 ============
+
+/// 批量转录中单个文件的结果；失败不会中断整个批次
+#[derive(serde::Serialize)]
+struct BatchResult {
+    audio_path: String,
+    output_path: Option<String>,
+    cue_count: usize,
+    error: Option<String>,
+}
+
+/// 批量转录进度（file_progress 为当前文件的 0-100 进度）
+#[derive(Clone, serde::Serialize)]
+struct BatchProgress {
+    file_index: usize,
+    total_files: usize,
+    file_progress: f32,
+}
+
+/// 转录单个文件并在同目录写入同名 .srt，视频文件先提取音轨
+async fn transcribe_batch_file(
+    window: &tauri::Window,
+    audio_path: &str,
+    model_size: &str,
+    language: &str,
+    engine: &str,
+) -> Result<(String, usize), String> {
+    // 视频提取出的临时 WAV 在函数返回时删除
+    let extracted = if is_video_file(audio_path) {
+        Some(temp_files::TempFileGuard(extract_audio_to_wav_cmd(audio_path.to_string()).await?.into()))
+    } else {
+        None
+    };
+    let source = extracted
+        .as_ref()
+        .map(|f| f.0.to_string_lossy().to_string())
+        .unwrap_or_else(|| audio_path.to_string());
+
+    let result = match engine {
+        "whisper" => {
+            transcribe_with_whisper(source.clone(), model_size.to_string(), language.to_string(), WhisperOptions::default(), window.clone()).await
+        }
+        "sensevoice" => transcribe_with_sensevoice(source.clone(), language.to_string(), false, window.clone()).await,
+        _ => Err(format!("不支持的引擎: {}", engine)),
+    };
+    drop(extracted);
+    let entries = result?;
+
+    let output_path = batch_output_path(audio_path);
+    write_srt_file(&output_path, &entries)?;
+    Ok((output_path, entries.len()))
+}
+
+/// 批量转录的输出路径：<媒体>.srt；已有同名字幕（可能是手动校对过的）时改用 <媒体>.1.srt、<媒体>.2.srt…
+fn batch_output_path(audio_path: &str) -> String {
+    let media = std::path::Path::new(audio_path);
+    let mut output = media.with_extension("srt");
+    let mut n = 1;
+    while output.exists() {
+        output = media.with_extension(format!("{}.srt", n));
+        n += 1;
+    }
+    output.to_string_lossy().to_string()
+}
+
+/// 依次转录多个音频/视频文件，每个文件旁生成同名 .srt
+/// engine: "whisper" | "sensevoice"；model_size 仅对 Whisper 生效
+/// 进度通过 batch-progress 事件发送；取消后剩余文件标记为已取消
+#[tauri::command]
+async fn transcribe_batch(
+    window: tauri::Window,
+    audio_paths: Vec<String>,
+    model_size: String,
+    language: String,
+    engine: String,
+) -> Result<Vec<BatchResult>, String> {
+    use std::sync::atomic::Ordering;
+
+    if engine != "whisper" && engine != "sensevoice" {
+        return Err(format!("不支持的引擎: {}", engine));
+    }
+
+    BATCH_CANCELLED.store(false, Ordering::SeqCst);
+    let total_files = audio_paths.len();
+    let mut results = Vec::with_capacity(total_files);
+
+    for (file_index, audio_path) in audio_paths.into_iter().enumerate() {
+        if BATCH_CANCELLED.load(Ordering::SeqCst) {
+            results.push(BatchResult { audio_path, output_path: None, cue_count: 0, error: Some("已取消".to_string()) });
+            continue;
+        }
+
+        let _ = window.emit("batch-progress", BatchProgress { file_index, total_files, file_progress: 0.0 });
+        let target = window.clone();
+        let listener = window.listen_any("transcription-progress", move |event| {
+            if let Ok(value) = serde_json::from_str::<serde_json::Value>(event.payload()) {
+                let progress = value["progress"].as_f64().unwrap_or(0.0) as f32;
+                let _ = target.emit("batch-progress", BatchProgress {
+                    file_index,
+                    total_files,
+                    file_progress: progress.clamp(0.0, 100.0),
+                });
+            }
+        });
+        let outcome = transcribe_batch_file(&window, &audio_path, &model_size, &language, &engine).await;
+        window.unlisten(listener);
+
+        match outcome {
+            Ok((output_path, cue_count)) => {
+                results.push(BatchResult { audio_path, output_path: Some(output_path), cue_count, error: None });
+            }
+            Err(e) => {
+                log::warn!("[Batch] 转录失败 {}: {}", audio_path, e);
+                results.push(BatchResult { audio_path, output_path: None, cue_count: 0, error: Some(e) });
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+// ============ 安装后自检 ============
+
+/// 自检用的内置音频（一句简短的真实语音，16kHz 单声道 WAV）
+const SMOKE_TEST_WAV: &[u8] = include_bytes!("../resources/smoke_test.wav");
+
+/// 自检结果
+#[derive(serde::Serialize)]
+struct SmokeTestResult {
+    engine: String,
+    model: String,
+    /// 识别出的文本（多段以空格连接）
+    text: String,
+    segment_count: usize,
+    elapsed_ms: u64,
+}
+
+/// 用内置音频跑一次完整转录，验证环境、脚本、进度与结果解析全链路可用
+/// engine: "whisper" | "sensevoice"；use_gpu 与当前激活环境不同时临时切换，结束后恢复
+#[tauri::command]
+async fn run_smoke_test(
+    window: tauri::Window,
+    engine: String,
+    model: Option<String>,
+    use_gpu: bool,
+) -> Result<SmokeTestResult, String> {
+    let is_whisper = match engine.as_str() {
+        "whisper" => true,
+        "sensevoice" => false,
+        _ => return Err(format!("不支持的引擎: {}", engine)),
+    };
+    let model = model.unwrap_or_else(|| if is_whisper { "base".to_string() } else { "SenseVoiceSmall".to_string() });
+
+    // 直接使用指定环境运行，不改变用户当前激活的环境
+    let env_type = if use_gpu { "gpu" } else { "cpu" }.to_string();
+
+    // 自检音频写入本次调用独占的临时文件，任何步骤失败返回时都会删除
+    let audio_file = temp_files::TempFileGuard(temp_files::unique_temp_path("vosub_smoke_test", ".wav"));
+    std::fs::write(&audio_file.0, SMOKE_TEST_WAV)
+        .map_err(|e| format!("写入自检音频失败: {}", e))?;
+    let audio_path_str = audio_file.0.to_string_lossy().to_string();
+
+    let start = std::time::Instant::now();
+    let result = if is_whisper {
+        let options = WhisperOptions { env_type: Some(env_type), ..Default::default() };
+        transcribe_with_whisper(audio_path_str, model.clone(), "auto".to_string(), options, window).await
+    } else {
+        transcribe_with_sensevoice_in_env(audio_path_str, "auto".to_string(), false, Some(env_type), window)
+            .await
+            .map(|report| report.entries)
+    };
+    let elapsed_ms = start.elapsed().as_millis() as u64;
+
+    drop(audio_file);
+
+    let entries = result.map_err(|e| format!("自检转录失败: {}", e))?;
+    // 自检音频是一段真实语音，没有识别出任何文字说明识别链路并未正常工作
+    if entries.iter().all(|e| e.text.trim().is_empty()) {
+        return Err("自检转录失败: 未识别出任何文字".to_string());
+    }
+    log::info!("[SmokeTest] {} ({}) 自检通过，耗时 {} ms", engine, model, elapsed_ms);
+
+    Ok(SmokeTestResult {
+        engine,
+        model,
+        text: entries.iter().map(|e| e.text.trim()).collect::<Vec<_>>().join(" "),
+        segment_count: entries.len(),
+        elapsed_ms,
+    })
 }
 
 /// 卸载 SenseVoice 环境
@@ -506,6 +1251,7 @@ fn switch_sensevoice(use_gpu: bool) -> Result<String, String> {
 /// 取消 SenseVoice 转录
 #[tauri::command]
 fn cancel_sensevoice_task() {
+    BATCH_CANCELLED.store(true, std::sync::atomic::Ordering::SeqCst);
     cancel_sensevoice_transcription();
 }
 
@@ -554,6 +1300,8 @@ async fn install_firered(window: tauri::Window, use_gpu: Option<bool>) -> Result
 }
 
 /// 使用 FireRedASR 校正字幕
+/// concurrency: 并发识别线程数，大于 1 时使用常驻服务批量校正
+/// beam_size: 解码 beam 宽度（1-10，默认 1）
 #[tauri::command]
 async fn correct_subtitles_with_firered(
     window: tauri::Window,
@@ -561,8 +1309,142 @@ async fn correct_subtitles_with_firered(
     audio_path: String,
     language: String,
     preserve_case: Option<bool>,
+    concurrency: Option<usize>,
+    beam_size: Option<u32>,
 ) -> Result<Vec<CorrectionEntry>, String> {
-    correct_with_firered(srt_path, audio_path, language, preserve_case.unwrap_or(true), window).await
+    correct_with_firered(srt_path, audio_path, language, preserve_case.unwrap_or(true), concurrency, beam_size, window).await
+}
+
+/// 使用 FireRedASR 校正字幕，返回结果及运行信息（设备、耗时等）
+#[tauri::command]
+async fn correct_subtitles_with_firered_report(
+    window: tauri::Window,
+    srt_path: String,
+    audio_path: String,
+    language: String,
+    preserve_case: Option<bool>,
+) -> Result<CorrectionReport, String> {
+    correct_with_firered_report(srt_path, audio_path, language, preserve_case.unwrap_or(true), None, None, window).await
+}
+
+/// 流水线统一进度（转录 0-50%，校正 50-100%）
+#[derive(Clone, serde::Serialize)]
+struct PipelineProgress {
+    progress: f32,
+    /// "transcribing" | "correcting"
+    phase: String,
+    current_text: String,
+}
+
+/// 将子任务的进度事件映射到流水线进度区间 [base, base + span]，以 pipeline-progress 事件转发
+fn forward_pipeline_progress(
+    window: &tauri::Window,
+    source_event: &str,
+    phase: &'static str,
+    base: f32,
+    span: f32,
+) -> tauri::EventId {
+    let target = window.clone();
+    window.listen_any(source_event, move |event| {
+        if let Ok(value) = serde_json::from_str::<serde_json::Value>(event.payload()) {
+            let progress = value["progress"].as_f64().unwrap_or(0.0) as f32;
+            let _ = target.emit("pipeline-progress", PipelineProgress {
+                progress: base + progress.clamp(0.0, 100.0) * span / 100.0,
+                phase: phase.to_string(),
+                current_text: value["current_text"].as_str().unwrap_or("").to_string(),
+            });
+        }
+    })
+}
+
+/// 运行流水线的一个阶段，期间持续检查 PIPELINE_CANCELLED 并转发给该阶段的取消函数
+/// 各阶段开始时会重置自己的取消标志，只在阶段切换时检查会丢失恰好此时到达的取消
+async fn run_pipeline_phase<T>(phase: impl std::future::Future<Output = T>, cancel_phase: fn()) -> T {
+    use std::sync::atomic::Ordering;
+
+    let watcher = async {
+        loop {
+            if PIPELINE_CANCELLED.load(Ordering::SeqCst) {
+                cancel_phase();
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+    };
+    match futures_util::future::select(std::pin::pin!(phase), std::pin::pin!(watcher)).await {
+        futures_util::future::Either::Left((result, _)) => result,
+        futures_util::future::Either::Right(_) => unreachable!("pipeline watcher never completes"),
+    }
+}
+
+/// 一键转录并校正：先用 Whisper 转录，再将结果交给 FireRedASR 校正
+#[tauri::command]
+async fn transcribe_and_correct(
+    window: tauri::Window,
+    audio_path: String,
+    whisper_model: String,
+    language: String,
+    preserve_case: Option<bool>,
+) -> Result<Vec<CorrectionEntry>, String> {
+    use std::sync::atomic::Ordering;
+
+    PIPELINE_CANCELLED.store(false, Ordering::SeqCst);
+
+    let listener = forward_pipeline_progress(&window, "transcription-progress", "transcribing", 0.0, 50.0);
+    let transcribed = run_pipeline_phase(
+        transcribe_with_whisper(audio_path.clone(), whisper_model, language.clone(), WhisperOptions::default(), window.clone()),
+        cancel_whisper_transcription,
+    ).await;
+    window.unlisten(listener);
+    let entries = transcribed?;
+
+    if PIPELINE_CANCELLED.load(Ordering::SeqCst) {
+        return Err("已取消".to_string());
+    }
+
+    let srt_file = temp_files::TempFileGuard(temp_files::unique_temp_path("pipeline", ".srt"));
+    let srt_path_str = srt_file.0.to_string_lossy().to_string();
+    write_srt_file(&srt_path_str, &entries)?;
+
+    let listener = forward_pipeline_progress(&window, "firered-progress", "correcting", 50.0, 50.0);
+    let corrected = run_pipeline_phase(
+        correct_with_firered(srt_path_str, audio_path, language, preserve_case.unwrap_or(true), None, None, window.clone()),
+        cancel_firered_correction,
+    ).await;
+    window.unlisten(listener);
+
+    corrected
+}
+
+/// 取消一键转录校正（同时取消正在进行的转录或校正阶段）
+#[tauri::command]
+fn cancel_transcribe_and_correct() {
+    PIPELINE_CANCELLED.store(true, std::sync::atomic::Ordering::SeqCst);
+    cancel_whisper_transcription();
+    cancel_firered_correction();
+}
+
+/// 设置所有任务的取消标志（转录、校正、流水线、批处理、模型下载）并停止 FireRedASR 服务
+fn cancel_all_running_tasks() {
+    use std::sync::atomic::Ordering;
+
+    PIPELINE_CANCELLED.store(true, Ordering::SeqCst);
+    BATCH_CANCELLED.store(true, Ordering::SeqCst);
+    cancel_whisper_transcription();
+    cancel_whisper_model_download();
+    cancel_sensevoice_transcription();
+    cancel_sensevoice_model_download();
+    cancel_firered_correction();
+    cancel_firered_model_download();
+    stop_firered_service();
+    info!("已取消所有正在运行的任务");
+}
+
+/// 一键停止所有正在运行的任务
+#[tauri::command]
+async fn cancel_all_tasks() -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(cancel_all_running_tasks)
+        .await
+        .map_err(|e| format!("Task error: {:?}", e))
 }
 
 /// 卸载 FireRedASR 环境
@@ -650,6 +1532,18 @@ fn is_firered_service_running() -> bool {
     is_service_running()
 }
 
+/// 设置 FireRedASR 服务空闲自动退出时长（秒，0 表示不自动退出）
+#[tauri::command]
+fn set_firered_idle_timeout(secs: u32) -> Result<(), String> {
+    set_service_idle_timeout(secs)
+}
+
+/// 获取 FireRedASR 服务空闲自动退出时长（秒）
+#[tauri::command]
+fn get_firered_idle_timeout() -> u32 {
+    get_service_idle_timeout()
+}
+
 /// 预加载音频文件到 FireRedASR 服务缓存
 #[tauri::command]
 async fn preload_audio_for_firered(audio_path: String) -> Result<String, String> {
@@ -681,6 +1575,7 @@ fn open_firered_model_dir_cmd() -> Result<(), String> {
 }
 
 /// 校正单条字幕
+/// beam_size: 解码 beam 宽度（1-10，默认 1），困难片段可调大
 #[tauri::command]
 async fn correct_single_subtitle(
     audio_path: String,
@@ -689,16 +1584,60 @@ async fn correct_single_subtitle(
     original_text: String,
     language: String,
     preserve_case: Option<bool>,
+    beam_size: Option<u32>,
 ) -> Result<SingleCorrectionResult, String> {
-    correct_single_entry(audio_path, start_ms, end_ms, original_text, language, preserve_case.unwrap_or(true)).await
+    correct_single_entry(audio_path, start_ms, end_ms, original_text, language, preserve_case.unwrap_or(true), beam_size).await
+}
+
+/// 按规则自动采纳 FireRedASR 校正结果（仅标点/大小写变化、小编辑距离等）
+/// 其余修改保留原文，留待人工审核
+#[tauri::command]
+fn auto_accept_firered(entries: Vec<CorrectionEntry>, rules: AcceptRules) -> Vec<SubtitleEntry> {
+    auto_accept_corrections(&entries, &rules)
+}
+
+/// 查询引擎当前进行中的操作（"whisper" | "sensevoice" | "firered"）
+/// 前端据此显示对应的取消按钮，避免误触发其他任务的取消；空闲时返回 null
+#[tauri::command]
+fn get_active_operation(engine: String) -> Option<OperationKind> {
+    active_operation::get_active_operation(&engine)
+}
+
+/// 设置 GPU 任务并发上限（默认 1，同一时间只允许一个 GPU 任务）
+#[tauri::command]
+fn set_gpu_concurrency_limit(limit: usize) -> usize {
+    gpu_guard::set_gpu_concurrency(limit)
+}
+
+/// 获取 GPU 任务并发上限
+#[tauri::command]
+fn get_gpu_concurrency_limit() -> usize {
+    gpu_guard::get_gpu_concurrency()
+}
+
+/// 获取指定引擎环境的 Python 版本（解释器无法启动时返回 None）
+/// engine: "whisper" | "sensevoice" | "firered"
+#[tauri::command]
+fn get_env_python_version(engine: String, use_gpu: bool) -> Option<String> {
+    match engine.as_str() {
+        "whisper" => get_whisper_python_version(use_gpu),
+        "sensevoice" => get_sensevoice_python_version(use_gpu),
+        "firered" => get_firered_python_version(use_gpu),
+        _ => None,
+    }
 }
 
 // ============ 导出功能 ============
 
 /// 导出为 TXT 格式（纯文本）
+/// options 可配置分隔符和时间戳前缀（hms / min_sec / seconds），未指定时每行一条字幕、不带时间戳
 #[tauri::command]
-fn export_txt(file_path: String, entries: Vec<SubtitleEntry>) -> Result<(), String> {
-    export_to_txt(&file_path, &entries)
+fn export_txt(
+    file_path: String,
+    entries: Vec<SubtitleEntry>,
+    options: Option<TxtExportOptions>,
+) -> Result<(), String> {
+    export_to_txt_with_options(&file_path, &entries, &options.unwrap_or_default())
 }
 
 /// 导出为 VTT 格式（WebVTT）
@@ -713,7 +1652,7 @@ fn export_markdown(file_path: String, entries: Vec<SubtitleEntry>) -> Result<(),
     export_to_markdown(&file_path, &entries)
 }
 
-/// 导出为 FCPXML 格式（Final Cut Pro）
+/// 导出为 FCPXML 格式（Final Cut Pro），style 未指定时使用 PingFang SC 62 白字居中
 #[tauri::command]
 fn export_fcpxml(
     file_path: String,
@@ -721,6 +1660,7 @@ fn export_fcpxml(
     fps: f64,
     position_x: Option<i32>,
     position_y: Option<i32>,
+    style: Option<TitleStyle>,
 ) -> Result<(), String> {
     export_to_fcpxml(
         &file_path,
@@ -728,15 +1668,266 @@ fn export_fcpxml(
         fps,
         position_x.unwrap_or(0),
         position_y.unwrap_or(-415),
+        &style.unwrap_or_default(),
     )
 }
 
-/// 最近文件信息
-#[derive(serde::Deserialize, Clone)]
-#[allow(dead_code)]
-struct RecentFileInfo {
-    path: String,
-    name: String,
+/// 导出为 CUE 音轨表（每条字幕一个 TRACK）
+#[tauri::command]
+fn export_cuesheet(audio_path: String, entries: Vec<SubtitleEntry>, file_path: String) -> Result<(), String> {
+    export_cue_sheet(&audio_path, &entries, &file_path)
+}
+
+/// 导出为 Audacity 标签轨（制表符分隔的 start/end/label）
+#[tauri::command]
+fn export_audacity_labels(file_path: String, entries: Vec<SubtitleEntry>) -> Result<(), String> {
+    srt_parser::export_audacity_labels(&file_path, &entries)
+}
+
+/// 导出为 LRC 歌词格式
+#[tauri::command]
+fn export_lrc(file_path: String, entries: Vec<SubtitleEntry>) -> Result<(), String> {
+    export_to_lrc(&file_path, &entries)
+}
+
+/// 一次导出多种格式，文件写在 base_path 旁（按格式替换扩展名），返回写入的路径
+/// 单个格式失败不会中断其余格式，失败信息汇总在错误中
+#[tauri::command]
+async fn export_all(
+    base_path: String,
+    entries: Vec<SubtitleEntry>,
+    fps: f64,
+    formats: Vec<String>,
+) -> Result<Vec<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || export_all_formats(&base_path, &entries, fps, &formats))
+        .await
+        .map_err(|e| format!("Task error: {:?}", e))?
+}
+
+/// 导出为 TTML (DFXP) 格式，用于广播电视流程
+#[tauri::command]
+fn export_ttml(file_path: String, entries: Vec<SubtitleEntry>) -> Result<(), String> {
+    export_to_ttml(&file_path, &entries)
+}
+
+/// 导出为 CSV（id,start,end,text），便于在表格软件中审校
+#[tauri::command]
+fn export_csv(file_path: String, entries: Vec<SubtitleEntry>) -> Result<(), String> {
+    export_to_csv(&file_path, &entries)
+}
+
+/// 导出为 ASS 格式（<i>/<b> 标签转换为覆盖标签），style 未指定时使用 Arial 60 白字
+#[tauri::command]
+fn export_ass(file_path: String, entries: Vec<SubtitleEntry>, style: Option<AssStyle>) -> Result<(), String> {
+    export_to_ass(&file_path, &entries, &style.unwrap_or_default())
+}
+
+/// 平移整条时间轴，使第一条字幕从 new_start_ms 开始
+#[tauri::command]
+fn rebase_subtitles(entries: Vec<SubtitleEntry>, new_start_ms: Option<u32>) -> Vec<SubtitleEntry> {
+    rebase_timeline(&entries, new_start_ms.unwrap_or(0))
+}
+
+/// 整体偏移字幕时间（offset_ms 可为负数），from_index 指定时只偏移该条及之后的字幕
+#[tauri::command]
+fn shift_subtitles_cmd(
+    entries: Vec<SubtitleEntry>,
+    offset_ms: i64,
+    from_index: Option<usize>,
+) -> Result<Vec<SubtitleEntry>, String> {
+    shift_subtitles(&entries, offset_ms, from_index)
+}
+
+/// 帧率转换：所有时间乘以 source_fps / target_fps（如 23.976 → 25）
+#[tauri::command]
+fn rescale_subtitles_cmd(entries: Vec<SubtitleEntry>, source_fps: f64, target_fps: f64) -> Result<Vec<SubtitleEntry>, String> {
+    rescale_subtitles(&entries, source_fps, target_fps)
+}
+
+/// 按指定倍率缩放所有时间
+#[tauri::command]
+fn scale_subtitles_cmd(entries: Vec<SubtitleEntry>, scale: f64) -> Result<Vec<SubtitleEntry>, String> {
+    scale_subtitles(&entries, scale)
+}
+
+/// 消除字幕间的空白：间隔不超过 max_gap_ms 时把前一条的结束时间延长到后一条的开始
+#[tauri::command]
+fn close_gaps_cmd(entries: Vec<SubtitleEntry>, max_gap_ms: u32) -> Vec<SubtitleEntry> {
+    close_gaps(&entries, max_gap_ms)
+}
+
+/// 把过短的字幕延长到 min_ms（不超过下一条的开始时间）
+#[tauri::command]
+fn enforce_min_duration_cmd(entries: Vec<SubtitleEntry>, min_ms: u32) -> Result<Vec<SubtitleEntry>, String> {
+    enforce_min_duration(&entries, min_ms)
+}
+
+/// 合并相邻的短字幕：间隔不超过 max_gap_ms 且合并后不超过 max_chars 个字符
+#[tauri::command]
+fn merge_srt(entries: Vec<SubtitleEntry>, max_gap_ms: u32, max_chars: usize) -> Result<Vec<SubtitleEntry>, String> {
+    merge_subtitles(&entries, max_gap_ms, max_chars)
+}
+
+/// 拆分超过字数或时长上限的字幕，时间按字符数比例分配
+#[tauri::command]
+fn split_srt(entries: Vec<SubtitleEntry>, max_chars: usize, max_duration_ms: u32) -> Result<Vec<SubtitleEntry>, String> {
+    split_subtitles(&entries, max_chars, max_duration_ms)
+}
+
+/// 计算每条字幕的阅读速度（每秒字符数），limit 指定时标记超速的字幕
+#[tauri::command]
+fn analyze_reading_speed(entries: Vec<SubtitleEntry>, limit: Option<f32>) -> Vec<CpsInfo> {
+    compute_reading_speed(&entries, limit)
+}
+
+/// 合并多个引擎的转录结果：按时间重叠对齐，再按投票或首选引擎选择文本
+#[tauri::command]
+fn ensemble_subtitles(results: Vec<Vec<SubtitleEntry>>, strategy: EnsembleStrategy) -> Vec<SubtitleEntry> {
+    ensemble_merge(&results, &strategy)
+}
+
+/// 导出置信度报告（HTML，按置信度由红到绿着色）
+#[tauri::command]
+fn export_confidence_report(file_path: String, entries: Vec<SubtitleEntry>) -> Result<(), String> {
+    export_confidence_html(&file_path, &entries)
+}
+
+/// 按卷边界（毫秒）拆分字幕，每卷时间轴从零开始
+#[tauri::command]
+fn split_subtitles_into_reels(entries: Vec<SubtitleEntry>, reel_boundaries_ms: Vec<u32>) -> Vec<Vec<SubtitleEntry>> {
+    split_into_reels(&entries, &reel_boundaries_ms)
+}
+
+/// 分卷导出为 <base>_reel01.srt、<base>_reel02.srt ...，返回写入的文件路径
+#[tauri::command]
+fn export_reels(base_path: String, reels: Vec<Vec<SubtitleEntry>>) -> Result<Vec<String>, String> {
+    write_reel_files(&base_path, &reels)
+}
+
+/// 导出 Anki 卡片：每条字幕一个音频片段，外加可导入 Anki 的 TSV/CSV 文件
+/// 返回卡片文件路径；音频片段需复制到 Anki 的 collection.media 目录
+#[tauri::command]
+async fn export_anki_deck(
+    audio_path: String,
+    entries: Vec<SubtitleEntry>,
+    output_dir: String,
+    format: Option<String>,
+) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let format = format.unwrap_or_else(|| "tsv".to_string());
+        export_flashcards(&audio_path, &entries, std::path::Path::new(&output_dir), &format)
+    })
+    .await
+    .map_err(|e| format!("Task error: {:?}", e))?
+}
+
+// ============ 磁盘空间 ============
+
+/// 检查目标目录（默认为用户主目录）所在磁盘的剩余空间是否足够
+#[tauri::command]
+fn check_disk_space(required_bytes: u64, target_dir: Option<String>) -> Result<DiskSpaceInfo, String> {
+    let target = match target_dir {
+        Some(dir) => std::path::PathBuf::from(dir),
+        None => dirs::home_dir().ok_or_else(|| "无法获取用户主目录".to_string())?,
+    };
+    disk_space::check_disk_space(&target, required_bytes)
+}
+
+/// 统计各引擎模型缓存占用的磁盘空间与模型数量
+#[tauri::command]
+async fn get_model_cache_usage() -> Result<Vec<EngineUsage>, String> {
+    tauri::async_runtime::spawn_blocking(disk_space::get_model_cache_usage)
+        .await
+        .map_err(|e| format!("Task error: {:?}", e))?
+}
+
+/// 按文件清单检查 SenseVoice / FireRed 模型文件是否缺失或大小不符
+/// engine: "sensevoice" | "firered"
+#[tauri::command]
+fn verify_model_integrity(model_name: String, engine: String) -> Result<Vec<FileStatus>, String> {
+    match engine.as_str() {
+        "sensevoice" => sensevoice_transcriber::verify_sensevoice_model(&model_name),
+        "firered" => firered_corrector::verify_firered_model(&model_name),
+        _ => Err(format!("不支持校验的引擎: {}", engine)),
+    }
+}
+
+/// 清理中断下载遗留的 .part / .incomplete 文件（默认只清理 1 小时前的），返回已删除的路径
+#[tauri::command]
+async fn cleanup_partial_downloads(min_age_hours: Option<u64>) -> Result<Vec<String>, String> {
+    let min_age = std::time::Duration::from_secs(min_age_hours.unwrap_or(1) * 3600);
+    tauri::async_runtime::spawn_blocking(move || disk_space::cleanup_partial_downloads(min_age))
+        .await
+        .map_err(|e| format!("Task error: {:?}", e))?
+}
+
+/// 下载模型前预检磁盘空间：返回模型目录所在磁盘的剩余空间与模型所需空间
+/// engine: "whisper" | "sensevoice" | "firered"
+#[tauri::command]
+fn check_disk_space_for_model(model_name: String, engine: String) -> Result<DiskSpaceInfo, String> {
+    match engine.as_str() {
+        "whisper" => whisper_python_transcriber::check_whisper_model_disk_space(&model_name),
+        "sensevoice" => sensevoice_transcriber::check_sensevoice_model_disk_space(&model_name),
+        "firered" => firered_corrector::check_firered_model_disk_space(&model_name),
+        _ => Err(format!("未知的引擎: {}", engine)),
+    }
+}
+
+// ============ 备份与恢复 ============
+
+/// 导出应用备份（配置、脚本、设置等；include_large 时包含 Python 环境和模型）
+#[tauri::command]
+async fn export_app_backup_cmd(output_path: String, include_large: Option<bool>) -> Result<BackupSummary, String> {
+    tauri::async_runtime::spawn_blocking(move || export_app_backup(&output_path, include_large.unwrap_or(false)))
+        .await
+        .map_err(|e| format!("Task error: {:?}", e))?
+}
+
+/// 从备份文件恢复应用配置
+#[tauri::command]
+async fn import_app_backup_cmd(backup_path: String) -> Result<BackupSummary, String> {
+    tauri::async_runtime::spawn_blocking(move || import_app_backup(&backup_path))
+        .await
+        .map_err(|e| format!("Task error: {:?}", e))?
+}
+
+// ============ 最近文件 ============
+
+/// 将文件加入最近文件列表（持久化到配置目录），返回更新后的列表
+#[tauri::command]
+fn add_recent_file(app_handle: tauri::AppHandle, path: String) -> Result<Vec<RecentFileInfo>, String> {
+    let files = recent_files::add_recent_file(&path)?;
+    update_recent_files_menu(app_handle, files.clone())?;
+    Ok(files)
+}
+
+/// 获取最近文件列表（自动移除已不存在的文件）
+#[tauri::command]
+fn get_recent_files() -> Result<Vec<RecentFileInfo>, String> {
+    recent_files::get_recent_files()
+}
+
+/// 后端列表为空时用前端保存的最近文件列表初始化（升级后首次启动），返回当前列表
+#[tauri::command]
+fn seed_recent_files(app_handle: tauri::AppHandle, files: Vec<RecentFileInfo>) -> Result<Vec<RecentFileInfo>, String> {
+    let files = recent_files::seed_recent_files(files)?;
+    update_recent_files_menu(app_handle, files.clone())?;
+    Ok(files)
+}
+
+/// 从最近文件列表中移除指定文件
+#[tauri::command]
+fn remove_recent_file(app_handle: tauri::AppHandle, path: String) -> Result<Vec<RecentFileInfo>, String> {
+    let files = recent_files::remove_recent_file(&path)?;
+    update_recent_files_menu(app_handle, files.clone())?;
+    Ok(files)
+}
+
+/// 清空最近文件列表
+#[tauri::command]
+fn clear_recent_files(app_handle: tauri::AppHandle) -> Result<(), String> {
+    recent_files::clear_recent_files()?;
+    update_recent_files_menu(app_handle, Vec::new())
 }
 
 /// 更新最近文件菜单
@@ -978,6 +2169,11 @@ fn base64_encode(data: &[u8]) -> String {
 pub fn run() {
     tauri::Builder::default()
         .setup(|app| {
+            // 旧版配置目录（~/.config/srt-editor）并入当前配置目录
+            if let Err(e) = app_config::migrate_legacy_config_dir() {
+                log::warn!("迁移旧配置目录失败: {}", e);
+            }
+
             // Windows: 处理命令行参数中的文件路径
             #[cfg(target_os = "windows")]
             {
@@ -1016,6 +2212,10 @@ pub fn run() {
                     .text("export-vtt", "导出为 VTT")
                     .text("export-srt", "导出为 SRT")
                     .text("export-markdown", "导出为 Markdown")
+                    .text("export-audacity", "导出为 Audacity 标签")
+                    .text("export-lrc", "导出为 LRC 歌词")
+                    .text("export-csv", "导出为 CSV 表格")
+                    .text("export-ttml", "导出为 TTML (DFXP)")
                     .separator()
                     .text("export-fcpxml", "导出为 FCPXML...")
                     .build()?;
@@ -1089,6 +2289,10 @@ pub fn run() {
                     .text("export-vtt", "导出为 VTT")
                     .text("export-srt", "导出为 SRT")
                     .text("export-markdown", "导出为 Markdown")
+                    .text("export-audacity", "导出为 Audacity 标签")
+                    .text("export-lrc", "导出为 LRC 歌词")
+                    .text("export-csv", "导出为 CSV 表格")
+                    .text("export-ttml", "导出为 TTML (DFXP)")
                     .separator()
                     .text("export-fcpxml", "导出为 FCPXML...")
                     .build()?;
@@ -1161,6 +2365,10 @@ pub fn run() {
                     .text("export-vtt", "导出为 VTT")
                     .text("export-srt", "导出为 SRT")
                     .text("export-markdown", "导出为 Markdown")
+                    .text("export-audacity", "导出为 Audacity 标签")
+                    .text("export-lrc", "导出为 LRC 歌词")
+                    .text("export-csv", "导出为 CSV 表格")
+                    .text("export-ttml", "导出为 TTML (DFXP)")
                     .separator()
                     .text("export-fcpxml", "导出为 FCPXML...")
                     .build()?;
@@ -1217,6 +2425,17 @@ pub fn run() {
                 app.set_menu(menu)?;
             }
 
+            // 根据持久化的最近文件列表重建菜单
+            match recent_files::get_recent_files() {
+                Ok(files) if !files.is_empty() => {
+                    if let Err(e) = update_recent_files_menu(app.handle().clone(), files) {
+                        log::warn!("重建最近文件菜单失败: {}", e);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("读取最近文件列表失败: {}", e),
+            }
+
             info!("应用启动完成");
 
             // 处理菜单事件
@@ -1450,7 +2669,7 @@ pub fn run() {
                     // 开发环境输出到控制台
                     Target::new(TargetKind::Stdout),
                     // 日志文件，自动存储在系统日志目录
-                    Target::new(TargetKind::LogDir { file_name: Some("vosub".into()) }),
+                    Target::new(TargetKind::LogDir { file_name: Some(app_config::APP_NAME.into()) }),
                 ])
                 .timezone_strategy(TimezoneStrategy::UseLocal)
                 // 日志轮转：保留所有日志文件，文件名包含日期（超过 40KB 后轮转）
@@ -1475,18 +2694,65 @@ pub fn run() {
                 .level_for("symphonia_metadata", log::LevelFilter::Warn)
                 .build(),
         )
+        .on_window_event(|window, event| {
+            // 主窗口关闭后停止所有任务，避免 Python 进程和 FireRedASR 服务残留
+            if let tauri::WindowEvent::Destroyed = event {
+                if window.label() == "main" {
+                    cancel_all_running_tasks();
+                }
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             greet,
             read_srt,
+            read_ass,
+            read_vtt,
+            read_csv,
+            detect_language,
+            import_chapters_as_srt,
+            normalize_ellipsis_dashes,
+            add_cjk_spaces_cmd,
+            subtitle_word_frequency,
+            detect_empty_cues,
+            remove_empty_cues,
+            validate_subtitle_entries,
+            clamp_max_time,
+            fix_reversed_timestamps_cmd,
+            check_cue_byte_length,
             write_srt,
+            write_srt_keep_ids,
+            set_backup_enabled,
+            get_backup_enabled,
+            autosave_srt,
+            check_autosave,
+            add_dictionary_entry,
+            remove_dictionary_entry,
+            get_dictionary,
+            apply_dictionary,
+            replace_in_subtitles_cmd,
             check_file_write_permission,
             unlock_file_cmd,
             read_audio_file,
+            get_audio_file_size,
+            read_audio_file_chunked,
             generate_audio_waveform,
+            generate_audio_waveform_stereo,
+            generate_audio_waveform_detailed,
+            generate_waveform_region_cmd,
+            cue_loudness,
+            trim_leading_silence,
+            audio_peak_db,
+            subtitle_coverage,
+            detect_silence_cmd,
             trigger_open_file,
             check_file_exists,
             get_pending_file_open,
             update_recent_files_menu,
+            add_recent_file,
+            get_recent_files,
+            seed_recent_files,
+            remove_recent_file,
+            clear_recent_files,
             get_log_path,
             show_log_in_folder,
             // Whisper 相关
@@ -1494,9 +2760,19 @@ pub fn run() {
             install_whisper,
             get_whisper_models_cmd,
             download_whisper_model_cmd,
+            set_download_proxy,
+            get_download_proxy,
+            set_model_mirror,
+            get_model_mirror,
+            relabel_model,
+            forget_model,
             delete_whisper_model_cmd,
             open_whisper_model_dir_cmd,
             transcribe_audio_to_subtitles,
+            extract_audio_to_wav_cmd,
+            transcribe_audio_with_report,
+            retranscribe_keep_timing,
+            align_transcript_to_audio,
             cancel_whisper_task,
             cancel_whisper_model_download_cmd,
             uninstall_whisper,
@@ -1506,6 +2782,10 @@ pub fn run() {
             check_sensevoice_env_status,
             install_sensevoice,
             transcribe_with_sensevoice_model,
+            transcribe_with_sensevoice_model_report,
+            run_smoke_test,
+            transcribe_to_srt,
+            transcribe_batch,
             uninstall_sensevoice,
             uninstall_sensevoice_by_type,
             switch_sensevoice,
@@ -1519,9 +2799,20 @@ pub fn run() {
             check_firered_env_status,
             install_firered,
             correct_subtitles_with_firered,
+            correct_subtitles_with_firered_report,
+            transcribe_and_correct,
+            cancel_transcribe_and_correct,
+            cancel_all_tasks,
             correct_single_subtitle,
+            auto_accept_firered,
+            get_active_operation,
+            set_gpu_concurrency_limit,
+            get_gpu_concurrency_limit,
+            get_env_python_version,
             preload_firered,
             is_firered_service_running,
+            set_firered_idle_timeout,
+            get_firered_idle_timeout,
             preload_audio_for_firered,
             uninstall_firered,
             uninstall_firered_by_type,
@@ -1538,6 +2829,36 @@ pub fn run() {
             export_vtt,
             export_markdown,
             export_fcpxml,
+            export_cuesheet,
+            export_ass,
+            export_confidence_report,
+            rebase_subtitles,
+            shift_subtitles_cmd,
+            rescale_subtitles_cmd,
+            scale_subtitles_cmd,
+            close_gaps_cmd,
+            enforce_min_duration_cmd,
+            merge_srt,
+            split_srt,
+            analyze_reading_speed,
+            ensemble_subtitles,
+            split_subtitles_into_reels,
+            export_reels,
+            export_anki_deck,
+            export_audacity_labels,
+            export_lrc,
+            export_csv,
+            export_ttml,
+            export_all,
+            // 磁盘空间
+            check_disk_space,
+            check_disk_space_for_model,
+            cleanup_partial_downloads,
+            verify_model_integrity,
+            get_model_cache_usage,
+            // 备份与恢复
+            export_app_backup_cmd,
+            import_app_backup_cmd,
             // 版本信息
             get_app_version,
             // 更新下载
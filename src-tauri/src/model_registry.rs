@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::app_config::app_config_dir;
+
+/// 已下载/导入模型的登记信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisteredModel {
+    /// 模型名称（内置名称、HuggingFace 仓库 ID 或本地目录）
+    pub name: String,
+    /// 来源仓库，本地模型为 None
+    pub repo: Option<String>,
+    /// 占用空间（字节）
+    pub size: u64,
+    /// 模型所在目录
+    pub path: String,
+    /// 所属引擎："whisper" | "sensevoice" | "firered"
+    pub backend: String,
+    /// 用户自定义的显示名称
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// 模型登记表路径（~/.config/vosub/model_registry.json）
+fn get_registry_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("model_registry.json"))
+}
+
+fn load_registry() -> Vec<RegisteredModel> {
+    get_registry_path()
+        .ok()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save_registry(models: &[RegisteredModel]) -> Result<(), String> {
+    let path = get_registry_path()?;
+    let json = serde_json::to_string_pretty(models)
+        .map_err(|e| format!("序列化模型登记表失败: {}", e))?;
+    std::fs::write(&path, json)
+        .map_err(|e| format!("写入模型登记表失败: {}", e))
+}
+
+/// 登记模型（同一引擎下同名模型会被更新，保留已有的显示名称）
+pub fn register_model(mut model: RegisteredModel) -> Result<(), String> {
+    let mut models = load_registry();
+
+    match models.iter_mut().find(|m| m.backend == model.backend && m.name == model.name) {
+        Some(existing) => {
+            if model.label.is_none() {
+                model.label = existing.label.take();
+            }
+            *existing = model;
+        }
+        None => models.push(model),
+    }
+
+    save_registry(&models)
+}
+
+/// 获取指定引擎的已登记模型
+pub fn get_registered_models(backend: &str) -> Vec<RegisteredModel> {
+    load_registry()
+        .into_iter()
+        .filter(|m| m.backend == backend)
+        .collect()
+}
+
+/// 获取模型的自定义显示名称
+pub fn get_model_label(backend: &str, name: &str) -> Option<String> {
+    load_registry()
+        .into_iter()
+        .find(|m| m.backend == backend && m.name == name)
+        .and_then(|m| m.label)
+}
+
+/// 设置模型显示名称（label 为空时恢复默认名称）
+/// 内置模型尚未登记时会新建一条仅含名称的记录
+pub fn relabel_model(backend: &str, name: &str, label: Option<String>) -> Result<(), String> {
+    let label = label.map(|l| l.trim().to_string()).filter(|l| !l.is_empty());
+    let mut models = load_registry();
+
+    match models.iter_mut().find(|m| m.backend == backend && m.name == name) {
+        Some(existing) => existing.label = label,
+        None => models.push(RegisteredModel {
+            name: name.to_string(),
+            repo: None,
+            size: 0,
+            path: String::new(),
+            backend: backend.to_string(),
+            label,
+        }),
+    }
+
+    save_registry(&models)
+}
+
+/// 从登记表中移除模型（不删除模型文件）
+pub fn forget_model(backend: &str, name: &str) -> Result<(), String> {
+    let mut models = load_registry();
+    let before = models.len();
+    models.retain(|m| !(m.backend == backend && m.name == name));
+
+    if models.len() == before {
+        return Err(format!("模型未登记: {}", name));
+    }
+
+    save_registry(&models)
+}
+
+/// 格式化模型体积，与内置列表的 "~145 MB" 风格一致
+pub fn format_model_size(bytes: u64) -> String {
+    const MB: f64 = 1024.0 * 1024.0;
+    let mb = bytes as f64 / MB;
+    if mb >= 1024.0 {
+        format!("~{:.1} GB", mb / 1024.0)
+    } else {
+        format!("~{:.0} MB", mb)
+    }
+}
+
+/// 模型文件完整性检查结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileStatus {
+    pub name: String,
+    pub expected_size: u64,
+    /// 实际大小，文件不存在时为 None
+    pub actual_size: Option<u64>,
+    /// "ok" | "missing" | "size_mismatch"
+    pub status: String,
+}
+
+/// 按文件清单（文件名, 期望大小）逐个检查模型目录中的文件
+pub fn verify_model_files(model_dir: &Path, manifest: &[(&str, u64)]) -> Vec<FileStatus> {
+    manifest
+        .iter()
+        .map(|&(name, expected_size)| {
+            let actual_size = std::fs::metadata(model_dir.join(name))
+                .ok()
+                .filter(|m| m.is_file())
+                .map(|m| m.len());
+            let status = match actual_size {
+                None => "missing",
+                Some(size) if size == expected_size => "ok",
+                Some(_) => "size_mismatch",
+            };
+            FileStatus {
+                name: name.to_string(),
+                expected_size,
+                actual_size,
+                status: status.to_string(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,41 @@
+use std::path::Path;
+use std::process::Command;
+
+/// 获取指定 Python 解释器的版本（如 "Python 3.11.9"）
+/// 解释器不存在或无法启动时返回 None
+pub fn python_version(python_path: &Path) -> Option<String> {
+    if !python_path.exists() {
+        return None;
+    }
+    
+    #[cfg(target_os = "windows")]
+    let output = {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        Command::new(python_path)
+            .arg("--version")
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .ok()?
+    };
+    
+    #[cfg(not(target_os = "windows"))]
+    let output = Command::new(python_path)
+        .arg("--version")
+        .output()
+        .ok()?;
+    
+    if !output.status.success() {
+        return None;
+    }
+    
+    // 旧版本 Python 将版本号输出到 stderr
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let version = if stdout.is_empty() {
+        String::from_utf8_lossy(&output.stderr).trim().to_string()
+    } else {
+        stdout
+    };
+    
+    if version.is_empty() { None } else { Some(version) }
+}
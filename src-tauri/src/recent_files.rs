@@ -0,0 +1,214 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::app_config::app_config_dir;
+
+/// 最近文件列表的最大长度
+const MAX_RECENT_FILES: usize = 10;
+
+/// 最近文件信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentFileInfo {
+    pub path: String,
+    pub name: String,
+    /// 最近打开时间（Unix 毫秒）
+    #[serde(default, rename = "lastOpened")]
+    pub last_opened: u64,
+}
+
+/// 最近文件列表路径（~/.config/vosub/recent-files.json）
+fn get_recent_files_path() -> Result<PathBuf, String> {
+    Ok(app_config_dir()?.join("recent-files.json"))
+}
+
+fn load_recent_files(store: &Path) -> Result<Vec<RecentFileInfo>, String> {
+    if !store.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(store)
+        .map_err(|e| format!("读取最近文件列表失败: {}", e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("解析最近文件列表失败: {}", e))
+}
+
+fn save_recent_files(store: &Path, files: &[RecentFileInfo]) -> Result<(), String> {
+    if let Some(parent) = store.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("创建配置目录失败: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(files)
+        .map_err(|e| format!("序列化最近文件列表失败: {}", e))?;
+    std::fs::write(store, content)
+        .map_err(|e| format!("写入最近文件列表失败: {}", e))
+}
+
+/// 读取列表并移除已不存在的文件（有变化时写回）
+fn get_recent_files_in(store: &Path) -> Result<Vec<RecentFileInfo>, String> {
+    let mut files = load_recent_files(store)?;
+    let before = files.len();
+    files.retain(|f| Path::new(&f.path).exists());
+    if files.len() != before {
+        save_recent_files(store, &files)?;
+    }
+    Ok(files)
+}
+
+fn add_recent_file_in(store: &Path, path: &str) -> Result<Vec<RecentFileInfo>, String> {
+    let name = Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string());
+    let last_opened = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+
+    let mut files = get_recent_files_in(store)?;
+    files.retain(|f| f.path != path);
+    files.insert(0, RecentFileInfo {
+        path: path.to_string(),
+        name,
+        last_opened,
+    });
+    files.truncate(MAX_RECENT_FILES);
+    save_recent_files(store, &files)?;
+    Ok(files)
+}
+
+fn seed_recent_files_in(store: &Path, files: Vec<RecentFileInfo>) -> Result<Vec<RecentFileInfo>, String> {
+    let existing = get_recent_files_in(store)?;
+    if !existing.is_empty() {
+        return Ok(existing);
+    }
+
+    let mut seeded: Vec<RecentFileInfo> = Vec::new();
+    for file in files {
+        if Path::new(&file.path).exists() && !seeded.iter().any(|f| f.path == file.path) {
+            seeded.push(file);
+        }
+    }
+    seeded.truncate(MAX_RECENT_FILES);
+    if !seeded.is_empty() {
+        save_recent_files(store, &seeded)?;
+    }
+    Ok(seeded)
+}
+
+/// 读取最近文件列表（最近打开的在前），并移除已不存在的文件
+pub fn get_recent_files() -> Result<Vec<RecentFileInfo>, String> {
+    get_recent_files_in(&get_recent_files_path()?)
+}
+
+/// 将文件加入最近文件列表头部（去重并限制数量），返回更新后的列表
+pub fn add_recent_file(path: &str) -> Result<Vec<RecentFileInfo>, String> {
+    add_recent_file_in(&get_recent_files_path()?, path)
+}
+
+/// 后端列表为空时（如升级后首次启动）用前端保存的列表初始化，保留原有顺序与打开时间
+/// 后端已有记录时不做修改，返回当前列表
+pub fn seed_recent_files(files: Vec<RecentFileInfo>) -> Result<Vec<RecentFileInfo>, String> {
+    seed_recent_files_in(&get_recent_files_path()?, files)
+}
+
+/// 从最近文件列表中移除指定文件，返回更新后的列表
+pub fn remove_recent_file(path: &str) -> Result<Vec<RecentFileInfo>, String> {
+    let store = get_recent_files_path()?;
+    let mut files = get_recent_files_in(&store)?;
+    files.retain(|f| f.path != path);
+    save_recent_files(&store, &files)?;
+    Ok(files)
+}
+
+/// 清空最近文件列表
+pub fn clear_recent_files() -> Result<(), String> {
+    save_recent_files(&get_recent_files_path()?, &[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试用临时目录，drop 时删除
+    struct TestDir(PathBuf);
+
+    impl TestDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("vosub_recent_files_{}_{}", name, std::process::id()));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            TestDir(dir)
+        }
+
+        /// 创建一个真实存在的文件并返回路径
+        fn touch(&self, name: &str) -> String {
+            let path = self.0.join(name);
+            std::fs::write(&path, "").unwrap();
+            path.to_string_lossy().to_string()
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_add_recent_file_dedup_and_cap() {
+        let dir = TestDir::new("cap");
+        let store = dir.0.join("recent-files.json");
+        let paths: Vec<String> = (0..12).map(|i| dir.touch(&format!("{}.srt", i))).collect();
+
+        for path in &paths {
+            add_recent_file_in(&store, path).unwrap();
+        }
+        let files = add_recent_file_in(&store, &paths[5]).unwrap();
+
+        assert_eq!(files.len(), MAX_RECENT_FILES);
+        assert_eq!(files[0].path, paths[5]);
+        assert_eq!(files[0].name, "5.srt");
+        assert_eq!(files.iter().filter(|f| f.path == paths[5]).count(), 1);
+        assert_eq!(files[1].path, paths[11]);
+        // The two oldest entries fell off the end
+        assert!(!files.iter().any(|f| f.path == paths[0] || f.path == paths[1]));
+    }
+
+    #[test]
+    fn test_get_recent_files_prunes_missing() {
+        let dir = TestDir::new("prune");
+        let store = dir.0.join("recent-files.json");
+        let kept = dir.touch("kept.srt");
+        let removed = dir.touch("removed.srt");
+        add_recent_file_in(&store, &kept).unwrap();
+        add_recent_file_in(&store, &removed).unwrap();
+
+        std::fs::remove_file(&removed).unwrap();
+        let files = get_recent_files_in(&store).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, kept);
+        // The pruned list is written back
+        assert_eq!(load_recent_files(&store).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_seed_recent_files_only_when_empty() {
+        let dir = TestDir::new("seed");
+        let store = dir.0.join("recent-files.json");
+        let a = dir.touch("a.srt");
+        let b = dir.touch("b.srt");
+        let info = |path: &str, last_opened: u64| RecentFileInfo {
+            path: path.to_string(),
+            name: String::new(),
+            last_opened,
+        };
+
+        let seeded = seed_recent_files_in(&store, vec![info(&a, 2), info("/missing.srt", 1), info(&b, 1)]).unwrap();
+        assert_eq!(seeded.iter().map(|f| f.last_opened).collect::<Vec<_>>(), vec![2, 1]);
+
+        // A non-empty store is left alone
+        let again = seed_recent_files_in(&store, vec![info(&b, 9)]).unwrap();
+        assert_eq!(again.len(), 2);
+        assert_eq!(again[0].path, a);
+    }
+}
@@ -1,4 +1,7 @@
+use crate::active_operation::{begin_operation, OperationKind};
+use crate::model_registry::{verify_model_files, FileStatus};
 use crate::srt_parser::{SubtitleEntry, TimeStamp};
+use crate::whisper_python_transcriber::TranscriptionReport;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::process::Command;
@@ -57,6 +60,7 @@ pub struct SenseVoiceProgress {
 pub struct SenseVoiceEnvInfo {
     pub installed: bool,
     pub ready: bool,
+    pub broken: bool,  // 依赖已安装但 Python 解释器无法启动（需要修复）
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -214,6 +218,28 @@ fn check_model_files_exist(model_path: &std::path::Path) -> bool {
     model_pt.exists() && config.exists()
 }
 
+/// 下载模型还需要的磁盘空间（扣除已下载部分）
+fn sensevoice_model_required_bytes(model_name: &str) -> u64 {
+    let total_size: u64 = SENSEVOICE_SMALL_FILES.iter().map(|f| f.size).sum();
+    total_size.saturating_sub(get_sensevoice_partial_size(model_name))
+}
+
+/// 下载前预检模型所在磁盘的剩余空间（已下载的模型不需要额外空间）
+pub fn check_sensevoice_model_disk_space(model_name: &str) -> Result<crate::disk_space::DiskSpaceInfo, String> {
+    let required = if is_sensevoice_model_downloaded(model_name) {
+        0
+    } else {
+        sensevoice_model_required_bytes(model_name)
+    };
+    crate::disk_space::check_disk_space(&get_sensevoice_model_path(model_name)?, required)
+}
+
+/// 按文件清单检查模型文件是否缺失或大小不符（用于排查手动拷贝不完整的模型）
+pub fn verify_sensevoice_model(model_name: &str) -> Result<Vec<FileStatus>, String> {
+    let manifest: Vec<(&str, u64)> = SENSEVOICE_SMALL_FILES.iter().map(|f| (f.name, f.size)).collect();
+    Ok(verify_model_files(&get_sensevoice_model_path(model_name)?, &manifest))
+}
+
 /// 获取已下载的部分大小
 pub fn get_sensevoice_partial_size(model_name: &str) -> u64 {
     let model_path = match get_sensevoice_model_path(model_name) {
@@ -267,6 +293,8 @@ pub async fn download_sensevoice_model(model_name: &str, window: Window) -> Resu
     use std::fs::{self, OpenOptions};
     use std::io::Write;
     
+    let _operation = begin_operation("sensevoice", OperationKind::Downloading);
+    
     // 生成新的任务ID，使之前的下载任务失效
     let task_id = new_sensevoice_model_download_task_id();
     
@@ -293,6 +321,9 @@ pub async fn download_sensevoice_model(model_name: &str, window: Window) -> Resu
     let total_size: u64 = SENSEVOICE_SMALL_FILES.iter().map(|f| f.size).sum();
     let mut downloaded_total: u64 = 0;
     
+    // 预检磁盘空间（扣除已下载部分）
+    crate::disk_space::ensure_disk_space(&model_path, sensevoice_model_required_bytes(model_name))?;
+    
     // 发送初始进度
     let _ = window.emit("sensevoice-model-progress", SenseVoiceProgress {
         progress: 0.0,
@@ -300,10 +331,12 @@ pub async fn download_sensevoice_model(model_name: &str, window: Window) -> Resu
         status: "downloading".to_string(),
     });
     
-    let client = reqwest::Client::builder()
+    let modelscope_url = crate::download_config::get_model_mirror("modelscope")?;
+    let client = crate::download_config::http_client_builder()?
         .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
         .build()
         .map_err(|e| format!("创建 HTTP 客户端失败: {}", e))?;
+    let mut speed_sampler = crate::download_config::SpeedSampler::default();
     
     // 下载每个文件
     for (file_idx, file_info) in SENSEVOICE_SMALL_FILES.iter().enumerate() {
@@ -334,8 +367,8 @@ pub async fn download_sensevoice_model(model_name: &str, window: Window) -> Resu
         
         // 构建下载 URL
         let download_url = format!(
-            "https://modelscope.cn/models/iic/{}/resolve/master/{}",
-            model_name, file_info.name
+            "{}/models/iic/{}/resolve/master/{}",
+            modelscope_url, model_name, file_info.name
         );
         
         // 发送进度
@@ -397,14 +430,18 @@ pub async fn download_sensevoice_model(model_name: &str, window: Window) -> Resu
                 .map_err(|e| format!("写入文件失败: {}", e))?;
             
             file_downloaded += chunk.len() as u64;
+            let speed_bps = speed_sampler.record(chunk.len() as u64);
             
             // 更新进度
             let current_total = downloaded_total + file_downloaded;
             let progress = (current_total as f32 / total_size as f32) * 100.0;
-            let _ = window.emit("sensevoice-model-progress", SenseVoiceProgress {
+            let _ = window.emit("sensevoice-model-progress", crate::download_config::DownloadProgress {
                 progress,
                 current_text: format!("{:.1}%", progress),
                 status: "downloading".to_string(),
+                downloaded_bytes: current_total,
+                total_bytes: total_size,
+                speed_bps,
             });
         }
         
@@ -492,6 +529,9 @@ pub fn open_sensevoice_model_dir() -> Result<(), String> {
 #[derive(Debug, Deserialize)]
 struct TranscriptionResult {
     segments: Vec<TranscriptionSegment>,
+    /// 识别出的主要语言（zh / en / yue / ja / ko）
+    #[serde(default)]
+    language: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -499,14 +539,14 @@ struct TranscriptionSegment {
     start: f64,  // 秒
     end: f64,    // 秒
     text: String,
+    /// 情绪/音频事件标签（仅在 --keep-events 时输出）
+    #[serde(default)]
+    events: Vec<String>,
 }
 
 /// 获取 SenseVoice 环境基础目录
 fn get_sensevoice_base_dir() -> Result<PathBuf, String> {
-    let home_dir = dirs::home_dir()
-        .ok_or_else(|| "Failed to get home directory".to_string())?;
-    
-    Ok(home_dir.join(".config").join("vosub"))
+    crate::app_config::app_config_dir()
 }
 
 /// 获取旧版 SenseVoice 环境目录（用于迁移）
@@ -619,13 +659,7 @@ pub fn get_sensevoice_env_dir() -> Result<PathBuf, String> {
 
 /// 获取 Python 脚本目录
 pub fn get_scripts_dir() -> Result<PathBuf, String> {
-    let home_dir = dirs::home_dir()
-        .ok_or_else(|| "Failed to get home directory".to_string())?;
-    
-    let scripts_dir = home_dir
-        .join(".config")
-        .join("vosub")
-        .join("scripts");
+    let scripts_dir = crate::app_config::app_config_dir()?.join("scripts");
     
     // 创建目录
     if !scripts_dir.exists() {
@@ -768,8 +802,24 @@ fn get_uv_path() -> Option<PathBuf> {
     }
 }
 
-/// 检查指定环境是否就绪
+/// 获取指定环境的 Python 版本，解释器不存在或无法启动时返回 None
+fn get_python_version_for_env(env_dir: &PathBuf) -> Option<String> {
+    crate::python_env::python_version(&get_python_path_for_env(env_dir))
+}
+
+/// 获取 SenseVoice 指定环境（CPU/GPU）的 Python 版本
+pub fn get_sensevoice_python_version(use_gpu: bool) -> Option<String> {
+    let env_dir = if use_gpu { get_sensevoice_gpu_env_dir() } else { get_sensevoice_cpu_env_dir() }.ok()?;
+    get_python_version_for_env(&env_dir)
+}
+
+/// 检查指定环境是否就绪（依赖已安装且 Python 解释器可以启动）
 fn check_env_ready(env_dir: &PathBuf) -> bool {
+    check_env_files_ready(env_dir) && get_python_version_for_env(env_dir).is_some()
+}
+
+/// 检查指定环境的依赖文件是否存在（不启动 Python）
+fn check_env_files_ready(env_dir: &PathBuf) -> bool {
     let python_path = get_python_path_for_env(env_dir);
     if !env_dir.exists() || !python_path.exists() {
         return false;
@@ -805,7 +855,7 @@ fn check_env_ready(env_dir: &PathBuf) -> bool {
     }
 }
 
-/// 检查 SenseVoice 环境状态（快速检查，不启动 Python）
+/// 检查 SenseVoice 环境状态（快速检查，仅通过 python --version 确认解释器可用）
 pub fn check_sensevoice_env() -> SenseVoiceEnvStatus {
     // 先尝试迁移旧版环境
     let _ = migrate_legacy_env();
@@ -815,12 +865,16 @@ pub fn check_sensevoice_env() -> SenseVoiceEnvStatus {
     // 检查 CPU 环境
     let cpu_dir = get_sensevoice_cpu_env_dir().unwrap_or_default();
     let cpu_installed = cpu_dir.exists();
+    let cpu_files_ready = check_env_files_ready(&cpu_dir);
     let cpu_ready = check_env_ready(&cpu_dir);
+    let cpu_broken = cpu_files_ready && !cpu_ready;
     
     // 检查 GPU 环境
     let gpu_dir = get_sensevoice_gpu_env_dir().unwrap_or_default();
     let gpu_installed = gpu_dir.exists();
+    let gpu_files_ready = check_env_files_ready(&gpu_dir);
     let gpu_ready = check_env_ready(&gpu_dir);
+    let gpu_broken = gpu_files_ready && !gpu_ready;
     
     // 获取当前激活的环境
     let mut active_env = get_active_env_type();
@@ -863,10 +917,12 @@ pub fn check_sensevoice_env() -> SenseVoiceEnvStatus {
         cpu_env: SenseVoiceEnvInfo {
             installed: cpu_installed,
             ready: cpu_ready,
+            broken: cpu_broken,
         },
         gpu_env: SenseVoiceEnvInfo {
             installed: gpu_installed,
             ready: gpu_ready,
+            broken: gpu_broken,
         },
         active_env,
         env_exists,
@@ -879,6 +935,8 @@ pub fn check_sensevoice_env() -> SenseVoiceEnvStatus {
 /// 安装 SenseVoice 环境
 /// use_gpu: 是否安装 GPU 版本（需要 NVIDIA 显卡和 CUDA）
 pub async fn install_sensevoice_env(window: Window, use_gpu: bool) -> Result<String, String> {
+    let _operation = begin_operation("sensevoice", OperationKind::Installing);
+    
     reset_cancellation();
     
     // 获取 uv 路径
@@ -892,6 +950,12 @@ pub async fn install_sensevoice_env(window: Window, use_gpu: bool) -> Result<Str
         get_sensevoice_cpu_env_dir()?
     };
     
+    // 预检磁盘空间，避免安装到一半磁盘写满
+    crate::disk_space::ensure_disk_space(
+        &env_dir,
+        if use_gpu { crate::disk_space::GPU_ENV_REQUIRED_BYTES } else { crate::disk_space::CPU_ENV_REQUIRED_BYTES },
+    )?;
+    
     let version_type = if use_gpu { "GPU" } else { "CPU" };
     
     // 发送进度
@@ -975,7 +1039,7 @@ pub async fn install_sensevoice_env(window: Window, use_gpu: bool) -> Result<Str
         .args([
             "pip", "install",
             "--python", python_path.to_str().unwrap(),
-            "funasr", "modelscope", "pydub"
+            "funasr", "modelscope"
         ])
         .output()
         .map_err(|e| format!("安装 FunASR 失败: {}", e))?;
@@ -1089,7 +1153,7 @@ if sys.platform == 'win32':
     sys.stdout = io.TextIOWrapper(sys.stdout.buffer, encoding='utf-8', errors='replace')
 
 import torch
-from pydub import AudioSegment
+import wave
 
 # 自动检测设备：优先使用 CUDA，否则使用 CPU
 def get_device():
@@ -1133,7 +1197,31 @@ def clean_text(text):
     text = re.sub(r'[。.]+$', '', text)
     return text
 
-def transcribe(audio_path, language="auto"):
+# SenseVoice 输出的情绪与音频事件标签（NEUTRAL、Speech 等默认标签不保留）
+EMOTION_TAGS = {"HAPPY", "SAD", "ANGRY", "FEARFUL", "DISGUSTED", "SURPRISED"}
+EVENT_TAGS = {"BGM", "Laughter", "Applause", "Cry", "Sneeze", "Breath", "Cough"}
+
+def extract_events(text):
+    """提取 <|HAPPY|>、<|Laughter|> 等标签，按出现顺序去重并转为小写"""
+    events = []
+    for tag in re.findall(r'<\|([^|]+)\|>', text):
+        if tag in EMOTION_TAGS or tag in EVENT_TAGS:
+            name = tag.lower()
+            if name not in events:
+                events.append(name)
+    return events
+
+# SenseVoice 在每段输出开头标注识别出的语言
+LANGUAGE_TAGS = {"zh", "en", "yue", "ja", "ko"}
+
+def extract_language(text):
+    """提取 <|zh|> 等语言标签，没有时返回 None"""
+    for tag in re.findall(r'<\|([^|]+)\|>', text):
+        if tag in LANGUAGE_TAGS:
+            return tag
+    return None
+
+def transcribe(audio_path, language="auto", keep_events=False):
     from funasr import AutoModel
     from funasr.utils.postprocess_utils import rich_transcription_postprocess
     
@@ -1164,14 +1252,18 @@ def transcribe(audio_path, language="auto"):
     # VAD 分段
     vad_res = vad_model.generate(input=audio_path)
     if not vad_res or not vad_res[0].get("value"):
-        return {"segments": []}
+        return {"segments": [], "language": None}
     
     segments = vad_res[0]["value"]
     total_segments = len(segments)
     
-    # 计算音频总时长用于预估
-    audio = AudioSegment.from_file(audio_path)
-    audio_duration_sec = len(audio) / 1000.0
+    # 输入已由 Rust 端转换为 16kHz 单声道 16-bit WAV，直接按字节切片
+    with wave.open(audio_path, "rb") as wav:
+        sample_rate = wav.getframerate()
+        sample_width = wav.getsampwidth()
+        frames = wav.readframes(wav.getnframes())
+    bytes_per_ms = sample_rate * sample_width // 1000
+    audio_duration_sec = len(frames) / (bytes_per_ms * 1000.0)
     
     emit_progress(15, 100, "transcribing", "正在识别语音内容...")
     
@@ -1179,6 +1271,7 @@ def transcribe(audio_path, language="auto"):
     tmp_dir = tempfile.mkdtemp()
     
     all_segments = []
+    language_counts = {}
     start_time = time.time()
     
     try:
@@ -1191,9 +1284,12 @@ def transcribe(audio_path, language="auto"):
             emit_progress(progress, 100, "transcribing", "正在识别语音内容...")
             
             # 切分音频片段
-            chunk = audio[start_ms:end_ms]
             chunk_file = os.path.join(tmp_dir, f"{start_ms}_{end_ms}.wav")
-            chunk.export(chunk_file, format="wav")
+            with wave.open(chunk_file, "wb") as chunk:
+                chunk.setnchannels(1)
+                chunk.setsampwidth(sample_width)
+                chunk.setframerate(sample_rate)
+                chunk.writeframes(frames[start_ms * bytes_per_ms:end_ms * bytes_per_ms])
             
             # 转录
             res = model.generate(input=chunk_file, language=language, use_itn=True)
@@ -1206,18 +1302,28 @@ def transcribe(audio_path, language="auto"):
                 os.remove(chunk_file)
                 continue
             
+            # 标签需在后处理之前提取（后处理会把标签转换为表情符号）
+            events = extract_events(text) if keep_events else []
+            segment_language = extract_language(text)
+            if segment_language:
+                language_counts[segment_language] = language_counts.get(segment_language, 0) + 1
+            
             try:
                 text = rich_transcription_postprocess(text)
             except:
                 text = clean_text(text)
             
             text = clean_text(text)
-            if text:
-                all_segments.append({
+            # 保留事件时，只有事件（如纯笑声）的片段也输出
+            if text or events:
+                segment = {
                     "start": round(start_ms / 1000.0, 3),
                     "end": round(end_ms / 1000.0, 3),
                     "text": text
-                })
+                }
+                if keep_events:
+                    segment["events"] = events
+                all_segments.append(segment)
             
             # 删除临时文件
             os.remove(chunk_file)
@@ -1229,17 +1335,20 @@ def transcribe(audio_path, language="auto"):
             pass
     
     emit_progress(100, 100, "completed", "转录完成")
-    return {"segments": all_segments}
+    # 以出现次数最多的语言作为整段音频的语言
+    detected_language = max(language_counts, key=language_counts.get) if language_counts else None
+    return {"segments": all_segments, "language": detected_language}
 
 def main():
     parser = argparse.ArgumentParser()
     parser.add_argument("audio_path")
     parser.add_argument("--language", default="auto")
     parser.add_argument("--output")
+    parser.add_argument("--keep-events", action="store_true", help="输出情绪与音频事件标签")
     args = parser.parse_args()
     
     try:
-        result = transcribe(args.audio_path, args.language)
+        result = transcribe(args.audio_path, args.language, args.keep_events)
         if args.output:
             with open(args.output, "w", encoding="utf-8") as f:
                 json.dump(result, f, ensure_ascii=False)
@@ -1286,20 +1395,65 @@ struct PythonProgress {
 pub async fn transcribe_with_sensevoice(
     audio_path: String,
     language: String,
+    keep_events: bool,
     window: Window,
 ) -> Result<Vec<SubtitleEntry>, String> {
+    transcribe_with_sensevoice_report(audio_path, language, keep_events, window)
+        .await
+        .map(|report| report.entries)
+}
+
+/// 使用 SenseVoice 转录音频，同时返回运行信息
+pub async fn transcribe_with_sensevoice_report(
+    audio_path: String,
+    language: String,
+    keep_events: bool,
+    window: Window,
+) -> Result<TranscriptionReport, String> {
+    transcribe_with_sensevoice_in_env(audio_path, language, keep_events, None, window).await
+}
+
+/// 使用指定环境（"cpu" | "gpu"）转录，不改变已激活的环境；env_type 为 None 时使用激活的环境
+pub async fn transcribe_with_sensevoice_in_env(
+    audio_path: String,
+    language: String,
+    keep_events: bool,
+    env_type: Option<String>,
+    window: Window,
+) -> Result<TranscriptionReport, String> {
+    let _operation = begin_operation("sensevoice", OperationKind::Transcribing);
+    
     reset_cancellation();
     
     // 记录开始时间
     let start_time = std::time::Instant::now();
     
     // 检查环境
-    let env_status = check_sensevoice_env();
-    if !env_status.ready {
-        return Err("SenseVoice 环境未安装，请先安装环境".to_string());
-    }
+    let (env_type, python_path, is_gpu) = match env_type.as_deref() {
+        Some(env_type) => {
+            let env_dir = match env_type {
+                "gpu" => get_sensevoice_gpu_env_dir()?,
+                "cpu" => get_sensevoice_cpu_env_dir()?,
+                _ => return Err(format!("未知的环境类型: {}", env_type)),
+            };
+            let python_path = get_python_path_for_env(&env_dir);
+            if !python_path.exists() {
+                return Err(format!("SenseVoice {} 环境未安装，请先安装环境", env_type.to_uppercase()));
+            }
+            (env_type.to_string(), python_path, env_type == "gpu")
+        }
+        None => {
+            let env_status = check_sensevoice_env();
+            if !env_status.ready {
+                return Err("SenseVoice 环境未安装，请先安装环境".to_string());
+            }
+            (get_active_env_type(), get_python_path()?, env_status.is_gpu)
+        }
+    };
+    
+    // GPU 环境下排队等待显卡空闲，避免与其他任务同时占用显存
+    let _gpu_permit = crate::gpu_guard::acquire_gpu_permit(&env_type).await?;
     
-    let python_path = get_python_path()?;
     let scripts_dir = get_scripts_dir()?;
     let script_path = scripts_dir.join("sensevoice_transcribe.py");
     
@@ -1317,8 +1471,23 @@ pub async fn transcribe_with_sensevoice(
         return Err("转录已取消".to_string());
     }
     
-    // 创建临时输出文件
-    let output_path = std::env::temp_dir().join(format!("sensevoice_output_{}.json", std::process::id()));
+    // 临时文件每次调用独占，函数返回时（包括出错提前返回）自动删除
+    let output_file = crate::temp_files::TempFileGuard(crate::temp_files::unique_temp_path("sensevoice_output", ".json"));
+    let output_path = output_file.0.clone();
+    
+    // 在 Rust 端解码并转换为 16kHz 单声道 WAV，Python 端直接切片，无需 pydub/ffmpeg
+    let wav_file = crate::temp_files::TempFileGuard(crate::temp_files::unique_temp_path("sensevoice_input", ".wav"));
+    let wav_path = wav_file.0.clone();
+    {
+        let audio_path = audio_path.clone();
+        let wav_path = wav_path.to_string_lossy().to_string();
+        tauri::async_runtime::spawn_blocking(move || {
+            crate::waveform_generator::convert_to_16k_wav(&audio_path, &wav_path)
+        })
+        .await
+        .map_err(|e| format!("Task error: {:?}", e))?
+        .map_err(|e| format!("转换音频失败: {}", e))?;
+    }
     
     // 映射语言代码
     let lang_code = match language.as_str() {
@@ -1331,7 +1500,7 @@ pub async fn transcribe_with_sensevoice(
     };
     
     // 确定设备（用于传递给 Python 脚本，实际设备信息由 Python 返回）
-    let _device = if env_status.is_gpu { "cuda" } else { "cpu" };
+    let _device = if is_gpu { "cuda" } else { "cpu" };
     
     // 使用 spawn 启动进程，以便异步读取 stderr
     use std::process::Stdio;
@@ -1345,10 +1514,11 @@ pub async fn transcribe_with_sensevoice(
             .args([
                 "-u",  // 强制无缓冲模式
                 script_path.to_str().unwrap(),
-                &audio_path,
+                wav_path.to_str().unwrap(),
                 "--language", lang_code,
                 "--output", output_path.to_str().unwrap(),
             ])
+            .args(keep_events.then_some("--keep-events"))
             .env("PYTHONUNBUFFERED", "1")
             .stderr(Stdio::piped())
             .stdout(Stdio::piped())
@@ -1362,10 +1532,11 @@ pub async fn transcribe_with_sensevoice(
         .args([
             "-u",  // 强制无缓冲模式
             script_path.to_str().unwrap(),
-            &audio_path,
+            wav_path.to_str().unwrap(),
             "--language", lang_code,
             "--output", output_path.to_str().unwrap(),
         ])
+        .args(keep_events.then_some("--keep-events"))
         .env("PYTHONUNBUFFERED", "1")
         .stderr(Stdio::piped())
         .stdout(Stdio::piped())
@@ -1379,6 +1550,10 @@ pub async fn transcribe_with_sensevoice(
     // 获取 stdout 用于读取设备信息
     let stdout = child.stdout.take();
     
+    // 实际使用的设备（由脚本输出的 DEVICE_INFO 解析）
+    let device_used = Arc::new(std::sync::Mutex::new(String::new()));
+    let device_used_clone = device_used.clone();
+    
     // 用于日志的参数
     let audio_path_for_log = audio_path.clone();
     let lang_code_for_log = lang_code.to_string();
@@ -1401,6 +1576,9 @@ pub async fn transcribe_with_sensevoice(
                     } else {
                         "CPU".to_string()
                     };
+                    if let Ok(mut device) = device_used_clone.lock() {
+                        *device = device_str.clone();
+                    }
                     log::info!(
                         "开始语音转录: 音频文件={}, 模型=SenseVoiceSmall, 语言={}, 设备={}",
                         audio_path_for_log, lang_code_for_log, device_str
@@ -1439,6 +1617,7 @@ pub async fn transcribe_with_sensevoice(
     // 等待进程完成
     let status = child.wait()
         .map_err(|e| format!("等待进程失败: {}", e))?;
+    drop(wav_file);
     
     // 等待 stdout 线程完成
     let _ = stdout_handle.join();
@@ -1448,13 +1627,10 @@ pub async fn transcribe_with_sensevoice(
         .map_err(|_| "读取 stderr 线程失败".to_string())?;
     
     if is_cancelled() {
-        // 清理临时文件
-        let _ = std::fs::remove_file(&output_path);
         return Err("转录已取消".to_string());
     }
     
     if !status.success() {
-        let _ = std::fs::remove_file(&output_path);
         if !last_error.is_empty() {
             // 尝试解析错误 JSON
             if let Ok(err_json) = serde_json::from_str::<serde_json::Value>(&last_error) {
@@ -1470,39 +1646,28 @@ pub async fn transcribe_with_sensevoice(
     // 读取结果
     let result_json = std::fs::read_to_string(&output_path)
         .map_err(|e| format!("读取转录结果失败: {}", e))?;
-    
-    // 清理临时文件
-    let _ = std::fs::remove_file(&output_path);
+    drop(output_file);
     
     // 解析 JSON
     let result: TranscriptionResult = serde_json::from_str(&result_json)
         .map_err(|e| format!("解析转录结果失败: {}", e))?;
     
+    // 识别出的语言，由交互式转录命令在选择"自动"时通知前端
+    let detected_language = result.language.clone().filter(|l| !l.is_empty());
+    
     // 转换为字幕条目
     let mut entries = Vec::new();
     for (i, segment) in result.segments.iter().enumerate() {
-        let start_ms = (segment.start * 1000.0) as u32;
-        let end_ms = (segment.end * 1000.0) as u32;
-        
-        let start_time = TimeStamp {
-            hours: start_ms / 3600000,
-            minutes: (start_ms % 3600000) / 60000,
-            seconds: (start_ms % 60000) / 1000,
-            milliseconds: start_ms % 1000,
-        };
-        
-        let end_time = TimeStamp {
-            hours: end_ms / 3600000,
-            minutes: (end_ms % 3600000) / 60000,
-            seconds: (end_ms % 60000) / 1000,
-            milliseconds: end_ms % 1000,
-        };
+        let start_time = TimeStamp::from_millis((segment.start * 1000.0) as u64);
+        let end_time = TimeStamp::from_millis((segment.end * 1000.0) as u64);
         
         entries.push(SubtitleEntry {
             id: (i + 1) as u32,
             start_time,
             end_time,
             text: segment.text.trim().to_string(),
+            confidence: None,
+            events: (!segment.events.is_empty()).then(|| segment.events.clone()),
         });
     }
     
@@ -1522,7 +1687,21 @@ pub async fn transcribe_with_sensevoice(
         status: "completed".to_string(),
     });
     
-    Ok(entries)
+    let device = device_used.lock().map(|d| d.clone()).unwrap_or_default();
+    let device = if device.is_empty() {
+        if is_gpu { "CUDA".to_string() } else { "CPU".to_string() }
+    } else {
+        device
+    };
+    
+    Ok(TranscriptionReport {
+        entries,
+        engine: "sensevoice".to_string(),
+        model: "SenseVoiceSmall".to_string(),
+        device,
+        elapsed_secs,
+        language: detected_language,
+    })
 }
 
 /// 卸载 SenseVoice 环境（兼容旧接口，卸载当前激活的环境）
@@ -19,6 +19,12 @@ pub struct SubtitleEntry {
     #[serde(rename = "endTime")]
     pub end_time: TimeStamp,
     pub text: String,
+    /// Recognition confidence in 0..1, when the transcription engine provides one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+    /// Emotion / audio-event tags (e.g. "laughter", "happy"), when the engine provides them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub events: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -27,6 +33,7 @@ pub struct SRTFile {
     pub path: String,
     pub entries: Vec<SubtitleEntry>,
     pub encoding: Option<String>,
+    pub language: Option<String>,
 }
 
 impl TimeStamp {
@@ -63,6 +70,9 @@ impl TimeStamp {
     }
 
     /// Convert timestamp to string in SRT format
+    /// Hours are zero-padded to two digits; 100 hours and above are written with
+    /// three or more digits (e.g. "100:00:00,000") rather than clamped, and
+    /// validate_subtitles reports them so they can be fixed with clamp_max_time
     pub fn to_string(&self) -> String {
         format!(
             "{:02}:{:02}:{:02},{:03}",
@@ -74,71 +84,1690 @@ impl TimeStamp {
 /// Parse SRT file content
 pub fn parse_srt(content: &str) -> Result<Vec<SubtitleEntry>, String> {
     let mut entries = Vec::new();
-    let blocks: Vec<&str> = content.split("\n\n").collect();
+    // Strip a leading BOM and normalize CRLF / CR line endings before splitting blocks
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let content = content.replace("\r\n", "\n").replace('\r', "\n");
+
+    // Group lines into blocks separated by empty lines, remembering the
+    // 1-based line number each block starts at for error messages
+    let mut blocks: Vec<(usize, Vec<&str>)> = Vec::new();
+    let mut current: Option<(usize, Vec<&str>)> = None;
+    for (index, line) in content.split('\n').enumerate() {
+        if line.is_empty() {
+            if let Some(block) = current.take() {
+                blocks.push(block);
+            }
+        } else if let Some((_, lines)) = current.as_mut() {
+            lines.push(line);
+        } else if !line.trim().is_empty() {
+            current = Some((index + 1, vec![line]));
+        }
+    }
+    if let Some(block) = current.take() {
+        blocks.push(block);
+    }
+
+    for (line_number, mut lines) in blocks {
+        while lines.last().is_some_and(|l| l.trim().is_empty()) {
+            lines.pop();
+        }
+        if lines.len() < 3 {
+            continue; // Skip invalid blocks
+        }
+
+        // Parse ID
+        let id = match lines[0].trim().parse::<u32>() {
+            Ok(id) => id,
+            Err(e) => {
+                if lines[..2].iter().any(|l| l.contains("-->")) {
+                    return Err(format!("line {}: invalid subtitle ID: {}", line_number, e));
+                }
+                // No timing line: this is the continuation of a cue whose text
+                // contained a blank line, not a new cue
+                log::warn!("line {}: skipping block without subtitle ID", line_number);
+                continue;
+            }
+        };
+
+        // Parse timestamps
+        let timestamp_line = lines[1].trim();
+        let timestamp_line_number = line_number + 1;
+        let times: Vec<&str> = timestamp_line.split(" --> ").collect();
+        if times.len() != 2 {
+            return Err(format!("line {}: invalid timestamp line: {}", timestamp_line_number, timestamp_line));
+        }
+
+        let start_time = TimeStamp::parse(times[0].trim())
+            .map_err(|e| format!("line {}: invalid start timestamp: {}", timestamp_line_number, e))?;
+        let end_time = TimeStamp::parse(times[1].trim())
+            .map_err(|e| format!("line {}: invalid end timestamp: {}", timestamp_line_number, e))?;
+
+        // Parse text (all remaining lines)
+        let text = lines[2..].join("\n").trim_end().to_string();
+
+        entries.push(SubtitleEntry {
+            id,
+            start_time,
+            end_time,
+            text,
+            confidence: None,
+            events: None,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Read a text file and decode it with the detected encoding
+/// Returns the decoded content and the encoding label (e.g. "UTF-8", "GBK", "Shift_JIS", "Big5").
+/// Falls back to lossy UTF-8 when detection is ambiguous instead of failing.
+pub fn read_text_detect_encoding(file_path: &str) -> Result<(String, String), String> {
+    let bytes = fs::read(file_path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    // Valid UTF-8 (with or without BOM) needs no detection
+    if let Ok(text) = std::str::from_utf8(&bytes) {
+        return Ok((text.to_string(), "UTF-8".to_string()));
+    }
+
+    let mut detector = chardetng::EncodingDetector::new();
+    detector.feed(&bytes, true);
+    let encoding = detector.guess(None, true);
+
+    let (text, actual, had_errors) = encoding.decode(&bytes);
+    if had_errors {
+        log::warn!("Encoding detection ambiguous for {}, decoding as UTF-8", file_path);
+        return Ok((String::from_utf8_lossy(&bytes).into_owned(), "UTF-8".to_string()));
+    }
+
+    Ok((text.into_owned(), actual.name().to_string()))
+}
+
+/// Read and parse SRT file
+pub fn read_srt_file(file_path: &str) -> Result<SRTFile, String> {
+    let path = Path::new(file_path);
+
+    if !path.exists() {
+        return Err(format!("File not found: {}", file_path));
+    }
+
+    let (content, encoding) = read_text_detect_encoding(file_path)?;
+
+    let entries = parse_srt(&content)?;
+
+    let name = path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown")
+        .to_string();
+
+    let language = match detect_subtitle_language(&entries) {
+        lang if lang == "auto" => None,
+        lang => Some(lang),
+    };
+
+    Ok(SRTFile {
+        name,
+        path: file_path.to_string(),
+        entries,
+        encoding: Some(encoding),
+        language,
+    })
+}
+
+/// Cantonese-specific characters that rarely appear in Mandarin text
+const YUE_MARKERS: &[char] = &['嘅', '咗', '喺', '唔', '佢', '冇', '啲', '嘢', '咁', '嚟', '乜', '睇'];
+
+/// Common English function words used to confirm Latin-script text is English
+const EN_COMMON_WORDS: &[&str] = &[
+    "the", "and", "is", "are", "you", "to", "of", "a", "in", "that", "it", "i", "this", "we", "for",
+];
+
+/// Detect subtitle language from script ratios and common words (no model required)
+/// Returns a language code matching the correction options: "zh", "yue", "ja", "ko", "en",
+/// or "auto" when the text gives no usable signal
+pub fn detect_subtitle_language(entries: &[SubtitleEntry]) -> String {
+    let mut han = 0usize;
+    let mut kana = 0usize;
+    let mut hangul = 0usize;
+    let mut latin = 0usize;
+    let mut yue_markers = 0usize;
+
+    for entry in entries {
+        for c in entry.text.chars() {
+            match c {
+                '\u{3040}'..='\u{30ff}' => kana += 1,
+                '\u{ac00}'..='\u{d7af}' | '\u{1100}'..='\u{11ff}' => hangul += 1,
+                '\u{4e00}'..='\u{9fff}' | '\u{3400}'..='\u{4dbf}' => {
+                    han += 1;
+                    if YUE_MARKERS.contains(&c) {
+                        yue_markers += 1;
+                    }
+                }
+                c if c.is_ascii_alphabetic() => latin += 1,
+                _ => {}
+            }
+        }
+    }
+
+    let total = han + kana + hangul + latin;
+    if total == 0 {
+        return "auto".to_string();
+    }
+
+    // Any meaningful amount of kana means Japanese (Japanese text mixes kanji and kana)
+    if kana * 10 >= total {
+        return "ja".to_string();
+    }
+    if hangul * 2 >= total {
+        return "ko".to_string();
+    }
+    if han * 2 >= total {
+        // Cantonese marker characters above ~1% of Han characters
+        if yue_markers * 100 >= han {
+            return "yue".to_string();
+        }
+        return "zh".to_string();
+    }
+
+    // Latin-script text: confirm with common English words
+    let words: Vec<String> = entries
+        .iter()
+        .flat_map(|e| e.text.split_whitespace())
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let english_hits = words.iter().filter(|w| EN_COMMON_WORDS.contains(&w.as_str())).count();
+    if !words.is_empty() && english_hits * 20 >= words.len() {
+        return "en".to_string();
+    }
+
+    // Fall back to the dominant script
+    if han >= latin {
+        "zh".to_string()
+    } else if latin > 0 && english_hits > 0 {
+        "en".to_string()
+    } else {
+        "auto".to_string()
+    }
+}
+
+// ============ ASS 导入 ============
+
+/// Convert ASS dialogue text to SRT text
+/// Keeps italic/bold overrides (\i1, \i0, \b1, \b0) as <i>/<b> tags, drops all other override blocks
+fn ass_text_to_srt(text: &str) -> String {
+    let mut result = String::new();
+    let mut italic = false;
+    let mut bold = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                // Collect the override block
+                let mut block = String::new();
+                for inner in chars.by_ref() {
+                    if inner == '}' {
+                        break;
+                    }
+                    block.push(inner);
+                }
+
+                for tag in block.split('\\').map(str::trim) {
+                    match tag {
+                        "i1" if !italic => {
+                            result.push_str("<i>");
+                            italic = true;
+                        }
+                        "i0" | "i" if italic => {
+                            result.push_str("</i>");
+                            italic = false;
+                        }
+                        // \b1 or a font weight (\b700) both mean bold
+                        t if t.starts_with('b') && t[1..].parse::<u32>().map(|w| w == 1 || w >= 700).unwrap_or(false) => {
+                            if !bold {
+                                result.push_str("<b>");
+                                bold = true;
+                            }
+                        }
+                        "b0" | "b" if bold => {
+                            result.push_str("</b>");
+                            bold = false;
+                        }
+                        "r" => {
+                            // Style reset closes everything
+                            if bold {
+                                result.push_str("</b>");
+                                bold = false;
+                            }
+                            if italic {
+                                result.push_str("</i>");
+                                italic = false;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            '\\' => match chars.peek() {
+                Some('N') | Some('n') => {
+                    chars.next();
+                    result.push('\n');
+                }
+                Some('h') => {
+                    chars.next();
+                    result.push(' ');
+                }
+                _ => result.push(c),
+            },
+            _ => result.push(c),
+        }
+    }
+
+    // Close tags left open at the end of the line
+    if bold {
+        result.push_str("</b>");
+    }
+    if italic {
+        result.push_str("</i>");
+    }
+
+    result.trim().to_string()
+}
+
+/// Parse ASS/SSA file content (Dialogue lines of the [Events] section)
+pub fn parse_ass(content: &str) -> Result<Vec<SubtitleEntry>, String> {
+    let mut entries = Vec::new();
+    let mut in_events = false;
+    // Default V4+ event format, overridden by the Format line when present
+    let mut format: Vec<String> = ["Layer", "Start", "End", "Style", "Name", "MarginL", "MarginR", "MarginV", "Effect", "Text"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+    for line in content.lines() {
+        let line = line.trim_start_matches('\u{feff}').trim();
+
+        if line.starts_with('[') {
+            in_events = line.eq_ignore_ascii_case("[Events]");
+            continue;
+        }
+        if !in_events {
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("Format:") {
+            format = rest.split(',').map(|s| s.trim().to_string()).collect();
+            continue;
+        }
+
+        let Some(rest) = line.strip_prefix("Dialogue:") else {
+            continue;
+        };
+
+        // Text is always the last field and may contain commas
+        let fields: Vec<&str> = rest.trim_start().splitn(format.len(), ',').collect();
+        if fields.len() != format.len() {
+            return Err(format!("Invalid Dialogue line: {}", line));
+        }
+
+        let field = |name: &str| -> Option<&str> {
+            format.iter().position(|f| f.eq_ignore_ascii_case(name)).map(|i| fields[i])
+        };
+
+        let start_time = TimeStamp::parse_ass(field("Start").unwrap_or(""))?;
+        let end_time = TimeStamp::parse_ass(field("End").unwrap_or(""))?;
+        let text = ass_text_to_srt(field("Text").unwrap_or(""));
+
+        entries.push(SubtitleEntry {
+            id: entries.len() as u32 + 1,
+            start_time,
+            end_time,
+            text,
+            confidence: None,
+            events: None,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Read and parse ASS/SSA file
+pub fn read_ass_file(file_path: &str) -> Result<SRTFile, String> {
+    let path = Path::new(file_path);
+
+    if !path.exists() {
+        return Err(format!("File not found: {}", file_path));
+    }
+
+    let (content, encoding) = read_text_detect_encoding(file_path)?;
+
+    let entries = parse_ass(&content)?;
+
+    let name = path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown")
+        .to_string();
+
+    let language = match detect_subtitle_language(&entries) {
+        lang if lang == "auto" => None,
+        lang => Some(lang),
+    };
+
+    Ok(SRTFile {
+        name,
+        path: file_path.to_string(),
+        entries,
+        encoding: Some(encoding),
+        language,
+    })
+}
+
+// ============ WebVTT 导入 ============
+
+/// Parse a WebVTT timestamp: HH:MM:SS.mmm or MM:SS.mmm
+fn parse_vtt_timestamp(s: &str) -> Result<TimeStamp, String> {
+    let parts: Vec<&str> = s.split(':').collect();
+    let (hours, minutes, rest) = match parts.as_slice() {
+        [h, m, rest] => (
+            h.parse::<u32>().map_err(|e| format!("Invalid hours: {}", e))?,
+            m.parse::<u32>().map_err(|e| format!("Invalid minutes: {}", e))?,
+            *rest,
+        ),
+        [m, rest] => (0, m.parse::<u32>().map_err(|e| format!("Invalid minutes: {}", e))?, *rest),
+        _ => return Err(format!("Invalid timestamp format: {}", s)),
+    };
+
+    let (sec, ms) = rest
+        .split_once('.')
+        .ok_or_else(|| format!("Invalid seconds format: {}", rest))?;
+    let seconds = sec.parse::<u32>().map_err(|e| format!("Invalid seconds: {}", e))?;
+    let milliseconds = ms.parse::<u32>().map_err(|e| format!("Invalid milliseconds: {}", e))?;
+
+    Ok(TimeStamp { hours, minutes, seconds, milliseconds })
+}
+
+/// Drop WebVTT-only markup (class spans, voice tags, inline karaoke timestamps), keep <i>/<b>/<u>
+fn vtt_text_to_srt(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(open) = rest.find('<') {
+        result.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('>') else {
+            result.push_str(&rest[open..]);
+            return result;
+        };
+        let tag = &rest[open..open + close + 1];
+        let name = tag.trim_start_matches(['<', '/']).trim_end_matches('>');
+        if matches!(name, "i" | "b" | "u") {
+            result.push_str(tag);
+        }
+        rest = &rest[open + close + 1..];
+    }
+    result.push_str(rest);
+
+    result
+}
+
+/// Parse WebVTT content; header, NOTE, STYLE and REGION blocks are skipped
+/// and cue settings (e.g. "align:start position:50%") are ignored
+pub fn parse_vtt(content: &str) -> Result<Vec<SubtitleEntry>, String> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let content = content.replace("\r\n", "\n").replace('\r', "\n");
+    let mut entries = Vec::new();
+
+    for block in content.split("\n\n") {
+        let block = block.trim_matches('\n');
+        if block.trim().is_empty()
+            || block.starts_with("WEBVTT")
+            || block.starts_with("NOTE")
+            || block.starts_with("STYLE")
+            || block.starts_with("REGION")
+        {
+            continue;
+        }
+
+        let lines: Vec<&str> = block.lines().collect();
+        // The timing line may be preceded by an optional cue identifier
+        let Some(timing_index) = lines.iter().position(|l| l.contains("-->")) else {
+            continue;
+        };
+
+        let (start, rest) = lines[timing_index]
+            .split_once("-->")
+            .ok_or_else(|| format!("Invalid timestamp line: {}", lines[timing_index]))?;
+        let end = rest.split_whitespace().next()
+            .ok_or_else(|| format!("Invalid timestamp line: {}", lines[timing_index]))?;
+
+        let text = lines[timing_index + 1..]
+            .iter()
+            .map(|l| vtt_text_to_srt(l))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        entries.push(SubtitleEntry {
+            id: entries.len() as u32 + 1,
+            start_time: parse_vtt_timestamp(start.trim())?,
+            end_time: parse_vtt_timestamp(end)?,
+            text,
+            confidence: None,
+            events: None,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Read and parse a WebVTT file
+pub fn read_vtt_file(file_path: &str) -> Result<SRTFile, String> {
+    let path = Path::new(file_path);
+
+    if !path.exists() {
+        return Err(format!("File not found: {}", file_path));
+    }
+
+    let (content, encoding) = read_text_detect_encoding(file_path)?;
+
+    let entries = parse_vtt(&content)?;
+
+    let name = path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown")
+        .to_string();
+
+    let language = match detect_subtitle_language(&entries) {
+        lang if lang == "auto" => None,
+        lang => Some(lang),
+    };
+
+    Ok(SRTFile {
+        name,
+        path: file_path.to_string(),
+        entries,
+        encoding: Some(encoding),
+        language,
+    })
+}
+
+// ============ CSV 导入 ============
+
+/// Split CSV content into records per RFC 4180 (quoted fields may contain commas,
+/// doubled quotes and line breaks). Returns each record with its 1-based starting line.
+fn parse_csv_records(content: &str) -> Vec<(usize, Vec<String>)> {
+    let mut records = Vec::new();
+    let mut fields: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\r' if chars.peek() == Some(&'\n') => {}
+                '\n' => {
+                    line += 1;
+                    field.push('\n');
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_quotes = true,
+            ',' => fields.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                fields.push(std::mem::take(&mut field));
+                records.push((record_line, std::mem::take(&mut fields)));
+                line += 1;
+                record_line = line;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        records.push((record_line, fields));
+    }
+
+    records
+}
+
+/// Parse the "id,start,end,text" CSV written by export_to_csv
+/// An optional header row is skipped; blank rows are ignored
+pub fn parse_csv(content: &str) -> Result<Vec<SubtitleEntry>, String> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut entries = Vec::new();
+
+    for (index, (line, fields)) in parse_csv_records(content).into_iter().enumerate() {
+        if fields.iter().all(|f| f.trim().is_empty()) {
+            continue;
+        }
+        if index == 0 && fields[0].trim().eq_ignore_ascii_case("id") {
+            continue;
+        }
+        if fields.len() < 4 {
+            return Err(format!("line {}: expected 4 columns (id,start,end,text), found {}", line, fields.len()));
+        }
+
+        let id = fields[0].trim().parse::<u32>()
+            .map_err(|e| format!("line {}: invalid subtitle ID: {}", line, e))?;
+        let start_time = TimeStamp::parse(fields[1].trim())
+            .map_err(|e| format!("line {}: invalid start timestamp: {}", line, e))?;
+        let end_time = TimeStamp::parse(fields[2].trim())
+            .map_err(|e| format!("line {}: invalid end timestamp: {}", line, e))?;
+
+        // Extra columns mean an unquoted comma in the text; keep them as part of it
+        let text = fields[3..].join(",");
+
+        entries.push(SubtitleEntry {
+            id,
+            start_time,
+            end_time,
+            text,
+            confidence: None,
+            events: None,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Read and parse a CSV file exported for spreadsheet review
+pub fn read_csv_file(file_path: &str) -> Result<Vec<SubtitleEntry>, String> {
+    if !Path::new(file_path).exists() {
+        return Err(format!("File not found: {}", file_path));
+    }
+
+    let (content, _) = read_text_detect_encoding(file_path)?;
+    parse_csv(&content)
+}
+
+// ============ 章节导入 ============
+
+/// Parse a chapter marker timestamp: MM:SS or H:MM:SS, returns milliseconds
+fn parse_chapter_time(s: &str) -> Option<u64> {
+    let parts: Vec<u64> = s
+        .split(':')
+        .map(|p| p.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    match parts.as_slice() {
+        [m, sec] if *sec < 60 => Some((m * 60 + sec) * 1000),
+        [h, m, sec] if *m < 60 && *sec < 60 => Some((h * 3600 + m * 60 + sec) * 1000),
+        _ => None,
+    }
+}
+
+/// Build placeholder subtitles from chapter markers (e.g. a YouTube description)
+/// Each line looks like "MM:SS Title", "H:MM:SS - Title" or "(MM:SS) Title"; lines without a timestamp are skipped
+/// Each cue spans from its marker to the next one, the last one ends at total_duration_ms
+pub fn subtitles_from_chapters(text: &str, total_duration_ms: u32) -> Vec<SubtitleEntry> {
+    let mut markers: Vec<(u64, String)> = text
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let (time_part, rest) = line.split_once(char::is_whitespace)?;
+            let time_part = time_part.trim_matches(|c| c == '(' || c == ')' || c == '[' || c == ']');
+            let start_ms = parse_chapter_time(time_part)?;
+            let title = rest
+                .trim()
+                .trim_start_matches(|c: char| c == '-' || c == '–' || c == '—' || c == ':' || c == '|')
+                .trim()
+                .to_string();
+            Some((start_ms, title))
+        })
+        .collect();
+
+    markers.sort_by_key(|(start, _)| *start);
+
+    let total_ms = total_duration_ms as u64;
+    let mut entries = Vec::new();
+
+    for (index, (start_ms, title)) in markers.iter().enumerate() {
+        let end_ms = markers
+            .get(index + 1)
+            .map(|(next, _)| *next)
+            .unwrap_or(total_ms.max(*start_ms));
+
+        // Skip markers that collapse to zero length (duplicates or past the end)
+        if end_ms <= *start_ms {
+            continue;
+        }
+
+        entries.push(SubtitleEntry {
+            id: entries.len() as u32 + 1,
+            start_time: TimeStamp::from_millis(*start_ms),
+            end_time: TimeStamp::from_millis(end_ms),
+            text: title.clone(),
+            confidence: None,
+            events: None,
+        });
+    }
+
+    entries
+}
+
+// ============ 标点规范化 ============
+
+/// Options for ellipsis/dash normalization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PunctuationStyleOptions {
+    /// "unicode" converts dot runs to "…", "ascii" converts "…" to "..."
+    pub ellipsis: String,
+    /// "em" converts "--" to "—", "ascii" converts "—" to "--"
+    pub dash: String,
+}
+
+/// Normalize one run of ellipsis characters ('.' and '…')
+fn normalize_ellipsis_run(run: &str, unicode: bool) -> String {
+    let dots = run.chars().filter(|&c| c == '.').count();
+    let ellipses = run.chars().count() - dots;
+
+    // One or two dots are ordinary punctuation (end of sentence, abbreviations)
+    if ellipses == 0 && dots < 3 {
+        return run.to_string();
+    }
+
+    if dots == 0 {
+        // Pure "…" runs keep their length (Chinese uses the doubled "……")
+        return if unicode { run.to_string() } else { "...".repeat(ellipses) };
+    }
+
+    // Dot runs and mixed runs like "...…" collapse into a single ellipsis
+    if unicode { "…".to_string() } else { "...".to_string() }
+}
+
+/// Normalize one run of dash characters ('-' and '—')
+fn normalize_dash_run(run: &str, em: bool) -> String {
+    let hyphens = run.chars().filter(|&c| c == '-').count();
+    let em_dashes = run.chars().count() - hyphens;
+
+    // A single hyphen is a word joiner; long hyphen runs are separators, leave both alone
+    if em_dashes == 0 && !(2..=3).contains(&hyphens) {
+        return run.to_string();
+    }
+
+    if hyphens == 0 {
+        // Pure "—" runs keep their length (Chinese uses the doubled "——")
+        return if em { run.to_string() } else { "--".repeat(em_dashes) };
+    }
+
+    if em { "—".to_string() } else { "--".to_string() }
+}
+
+/// Normalize ellipsis and dash conventions in a single text
+fn normalize_punctuation_text(text: &str, options: &PunctuationStyleOptions) -> String {
+    let unicode_ellipsis = options.ellipsis != "ascii";
+    let em_dash = options.dash != "ascii";
+
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let is_ellipsis = |ch: char| ch == '.' || ch == '…';
+        let is_dash = |ch: char| ch == '-' || ch == '—';
+
+        if is_ellipsis(c) || is_dash(c) {
+            let same_kind = if is_ellipsis(c) { is_ellipsis } else { is_dash };
+            let mut run = String::from(c);
+            while let Some(&next) = chars.peek() {
+                if !same_kind(next) {
+                    break;
+                }
+                run.push(next);
+                chars.next();
+            }
+
+            if is_ellipsis(c) {
+                result.push_str(&normalize_ellipsis_run(&run, unicode_ellipsis));
+            } else {
+                result.push_str(&normalize_dash_run(&run, em_dash));
+            }
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// Normalize inconsistent ellipsis ("...", "…") and dash ("--", "—") conventions
+/// Existing "…"/"—" are never doubled up; mixed runs collapse to a single mark
+pub fn normalize_punctuation_style(entries: &[SubtitleEntry], options: &PunctuationStyleOptions) -> Vec<SubtitleEntry> {
+    entries
+        .iter()
+        .map(|entry| SubtitleEntry {
+            text: normalize_punctuation_text(&entry.text, options),
+            ..entry.clone()
+        })
+        .collect()
+}
+
+/// Han ideographs covered by the spacing rule (same range as the editor's \u4e00-\u9fa5)
+/// Kana and Hangul are excluded: Korean counters such as "3개" / "2024년" take no space
+fn is_han_for_spacing(c: char) -> bool {
+    ('\u{4e00}'..='\u{9fa5}').contains(&c)
+}
+
+/// Insert a space wherever a Han character directly touches an ASCII letter or digit
+/// Unlike the editor's version, existing whitespace and line breaks are left as they are
+fn add_cjk_spaces_to_text(text: &str) -> String {
+    let mut result = String::with_capacity(text.len() + 8);
+    let mut prev: Option<char> = None;
+    for c in text.chars() {
+        if let Some(p) = prev {
+            if (is_han_for_spacing(p) && c.is_ascii_alphanumeric())
+                || (p.is_ascii_alphanumeric() && is_han_for_spacing(c))
+            {
+                result.push(' ');
+            }
+        }
+        result.push(c);
+        prev = Some(c);
+    }
+    result
+}
+
+/// Apply CJK/alphanumeric spacing ("这是AI技术" → "这是 AI 技术") to every cue
+pub fn add_cjk_spaces(entries: &[SubtitleEntry]) -> Vec<SubtitleEntry> {
+    entries
+        .iter()
+        .map(|entry| SubtitleEntry {
+            text: add_cjk_spaces_to_text(&entry.text),
+            ..entry.clone()
+        })
+        .collect()
+}
+
+// ============ 词频统计 ============
+
+/// Whether a character belongs to a CJK script (segmented by bigrams)
+fn is_cjk_char(c: char) -> bool {
+    matches!(c,
+        '\u{4e00}'..='\u{9fff}'
+        | '\u{3400}'..='\u{4dbf}'
+        | '\u{3040}'..='\u{30ff}'
+        | '\u{ac00}'..='\u{d7af}')
+}
+
+/// Split text into terms: whitespace/punctuation-separated words for Latin text,
+/// overlapping character bigrams for CJK runs (no dictionary required)
+fn tokenize_terms(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    let mut word = String::new();
+    let mut cjk_run: Vec<char> = Vec::new();
+
+    let flush_cjk = |run: &mut Vec<char>, terms: &mut Vec<String>| {
+        if run.len() == 1 {
+            terms.push(run[0].to_string());
+        } else {
+            for pair in run.windows(2) {
+                terms.push(pair.iter().collect());
+            }
+        }
+        run.clear();
+    };
+
+    for c in text.chars() {
+        if is_cjk_char(c) {
+            if !word.is_empty() {
+                terms.push(std::mem::take(&mut word));
+            }
+            cjk_run.push(c);
+        } else if c.is_alphanumeric() || c == '\'' {
+            if !cjk_run.is_empty() {
+                flush_cjk(&mut cjk_run, &mut terms);
+            }
+            word.extend(c.to_lowercase());
+        } else {
+            if !word.is_empty() {
+                terms.push(std::mem::take(&mut word));
+            }
+            if !cjk_run.is_empty() {
+                flush_cjk(&mut cjk_run, &mut terms);
+            }
+        }
+    }
+
+    if !word.is_empty() {
+        terms.push(word);
+    }
+    if !cjk_run.is_empty() {
+        flush_cjk(&mut cjk_run, &mut terms);
+    }
+
+    terms
+        .into_iter()
+        .map(|t| t.trim_matches('\'').to_string())
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+/// Count term frequencies across all subtitles
+/// min_length: minimum term length in characters; stopwords are matched case-insensitively
+/// Returns terms sorted by count (descending), then alphabetically
+pub fn word_frequency(entries: &[SubtitleEntry], min_length: usize, stopwords: &[String]) -> Vec<(String, usize)> {
+    use std::collections::{HashMap, HashSet};
+
+    let stopwords: HashSet<String> = stopwords.iter().map(|w| w.trim().to_lowercase()).collect();
+    let mut counts: HashMap<String, usize> = HashMap::new();
+
+    for entry in entries {
+        // Strip formatting tags like <i> before tokenizing
+        let text = entry.text.replace("<i>", " ").replace("</i>", " ").replace("<b>", " ").replace("</b>", " ");
+        for term in tokenize_terms(&text) {
+            if term.chars().count() < min_length || stopwords.contains(&term) {
+                continue;
+            }
+            *counts.entry(term).or_insert(0) += 1;
+        }
+    }
+
+    let mut result: Vec<(String, usize)> = counts.into_iter().collect();
+    result.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    result
+}
+
+// ============ 空内容检测 ============
+
+/// Remove formatting tags such as <i>, </b>, <font color="..."> from text
+fn strip_tags(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut in_tag = false;
+
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => result.push(c),
+            _ => {}
+        }
+    }
+
+    result
+}
+
+/// Whether a subtitle has no real content: only punctuation (ASCII or CJK), symbols,
+/// whitespace (including full-width spaces) or empty formatting tags
+fn is_empty_content(text: &str) -> bool {
+    !strip_tags(text).chars().any(|c| c.is_alphanumeric())
+}
+
+/// Find subtitles whose text is empty after stripping punctuation and whitespace
+/// Returns the ids of the offending cues
+pub fn detect_empty_content_cues(entries: &[SubtitleEntry]) -> Vec<u32> {
+    entries
+        .iter()
+        .filter(|e| is_empty_content(&e.text))
+        .map(|e| e.id)
+        .collect()
+}
+
+/// Remove subtitles without real content and renumber the rest from 1
+pub fn remove_empty_content_cues(entries: &[SubtitleEntry]) -> Vec<SubtitleEntry> {
+    entries
+        .iter()
+        .filter(|e| !is_empty_content(&e.text))
+        .enumerate()
+        .map(|(index, e)| SubtitleEntry {
+            id: index as u32 + 1,
+            ..e.clone()
+        })
+        .collect()
+}
+
+// ============ 字幕校验 ============
+
+/// Largest timestamp that fits the two-digit hour field: 99:59:59,999
+pub const MAX_TWO_DIGIT_HOUR_MS: u64 = 100 * 3_600_000 - 1;
+
+/// A problem found by validate_subtitles
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    /// Position of the offending entry in the list (for jumping to the cue)
+    pub index: usize,
+    pub id: u32,
+    /// Issue type: "hours_overflow" | "reversed_timing" | "zero_duration" | "overlap" | "out_of_order"
+    pub kind: String,
+    pub message: String,
+}
+
+/// Check subtitles for timing problems (report only, entries are not modified)
+pub fn validate_subtitles(entries: &[SubtitleEntry]) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    for (index, entry) in entries.iter().enumerate() {
+        // Timestamps beyond 99:59:59,999 don't fit the two-digit hour field
+        if entry.start_time.hours >= 100 || entry.end_time.hours >= 100 {
+            issues.push(ValidationIssue {
+                index,
+                id: entry.id,
+                kind: "hours_overflow".to_string(),
+                message: format!(
+                    "Timestamp exceeds 99:59:59,999 ({} --> {})",
+                    entry.start_time.to_string(),
+                    entry.end_time.to_string()
+                ),
+            });
+        }
+
+        let start = entry.start_time.to_millis();
+        let end = entry.end_time.to_millis();
+
+        // End before start: usually the two sides of "-->" swapped by hand
+        if end < start {
+            issues.push(ValidationIssue {
+                index,
+                id: entry.id,
+                kind: "reversed_timing".to_string(),
+                message: format!(
+                    "End time precedes start time ({} --> {})",
+                    entry.start_time.to_string(),
+                    entry.end_time.to_string()
+                ),
+            });
+        } else if end == start {
+            issues.push(ValidationIssue {
+                index,
+                id: entry.id,
+                kind: "zero_duration".to_string(),
+                message: format!("Cue has zero duration ({})", entry.start_time.to_string()),
+            });
+        }
+
+        // Compare with the next cue; reported on the later cue of the pair
+        if let Some(next) = entries.get(index + 1) {
+            let next_start = next.start_time.to_millis();
+            if next_start < start {
+                issues.push(ValidationIssue {
+                    index: index + 1,
+                    id: next.id,
+                    kind: "out_of_order".to_string(),
+                    message: format!(
+                        "Starts at {} before the previous cue ({})",
+                        next.start_time.to_string(),
+                        entry.start_time.to_string()
+                    ),
+                });
+            } else if end > next_start {
+                issues.push(ValidationIssue {
+                    index: index + 1,
+                    id: next.id,
+                    kind: "overlap".to_string(),
+                    message: format!(
+                        "Starts at {} before the previous cue ends ({})",
+                        next.start_time.to_string(),
+                        entry.end_time.to_string()
+                    ),
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+/// Swap start and end of cues whose end precedes their start
+/// The swap keeps the intended span; zero-duration cues are left untouched
+pub fn fix_reversed_timestamps(entries: &[SubtitleEntry]) -> Vec<SubtitleEntry> {
+    entries
+        .iter()
+        .map(|entry| {
+            if entry.end_time.to_millis() < entry.start_time.to_millis() {
+                SubtitleEntry {
+                    start_time: entry.end_time.clone(),
+                    end_time: entry.start_time.clone(),
+                    ..entry.clone()
+                }
+            } else {
+                entry.clone()
+            }
+        })
+        .collect()
+}
+
+/// Find cues whose UTF-8 text exceeds max_bytes (line breaks count as one byte)
+/// Byte-oriented decoders (e.g. DVB) truncate such cues; CJK characters take 3 bytes each
+pub fn check_byte_length(entries: &[SubtitleEntry], max_bytes: usize) -> Vec<u32> {
+    entries
+        .iter()
+        .filter(|e| e.text.len() > max_bytes)
+        .map(|e| e.id)
+        .collect()
+}
+
+/// Clamp all timestamps to max_ms (e.g. MAX_TWO_DIGIT_HOUR_MS)
+pub fn clamp_max_time(entries: &[SubtitleEntry], max_ms: u64) -> Vec<SubtitleEntry> {
+    entries
+        .iter()
+        .map(|entry| SubtitleEntry {
+            start_time: TimeStamp::from_millis(entry.start_time.to_millis().min(max_ms)),
+            end_time: TimeStamp::from_millis(entry.end_time.to_millis().min(max_ms)),
+            ..entry.clone()
+        })
+        .collect()
+}
+
+// ============ 时间轴平移 ============
+
+/// Move the whole track so that its earliest cue starts at new_start_ms
+/// Useful after cutting a segment out of a longer file (e.g. cues starting at 00:45:00)
+pub fn rebase_timeline(entries: &[SubtitleEntry], new_start_ms: u32) -> Vec<SubtitleEntry> {
+    let Some(first_start) = entries.iter().map(|e| e.start_time.to_millis()).min() else {
+        return Vec::new();
+    };
+    let offset = new_start_ms as i64 - first_start as i64;
+    let apply = |t: &TimeStamp| TimeStamp::from_millis((t.to_millis() as i64 + offset).max(0) as u64);
+
+    entries
+        .iter()
+        .map(|entry| SubtitleEntry {
+            start_time: apply(&entry.start_time),
+            end_time: apply(&entry.end_time),
+            ..entry.clone()
+        })
+        .collect()
+}
+
+/// Shift cue timings by offset_ms (may be negative), optionally only from from_index onward
+/// Results before zero are clamped to 00:00:00,000
+pub fn shift_subtitles(
+    entries: &[SubtitleEntry],
+    offset_ms: i64,
+    from_index: Option<usize>,
+) -> Result<Vec<SubtitleEntry>, String> {
+    let from = from_index.unwrap_or(0);
+    if from > entries.len() {
+        return Err(format!("Index {} out of range ({} entries)", from, entries.len()));
+    }
+    let apply = |t: &TimeStamp| TimeStamp::from_millis((t.to_millis() as i64 + offset_ms).max(0) as u64);
+
+    Ok(entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            if i < from {
+                return entry.clone();
+            }
+            SubtitleEntry {
+                start_time: apply(&entry.start_time),
+                end_time: apply(&entry.end_time),
+                ..entry.clone()
+            }
+        })
+        .collect())
+}
+
+/// Multiply every timestamp by scale (e.g. 1.001 for 23.976 -> 24 fps drift fixes)
+pub fn scale_subtitles(entries: &[SubtitleEntry], scale: f64) -> Result<Vec<SubtitleEntry>, String> {
+    if !scale.is_finite() || scale <= 0.0 {
+        return Err(format!("Invalid scale factor: {}", scale));
+    }
+    let apply = |t: &TimeStamp| TimeStamp::from_millis((t.to_millis() as f64 * scale).round() as u64);
+
+    Ok(entries
+        .iter()
+        .map(|entry| SubtitleEntry {
+            start_time: apply(&entry.start_time),
+            end_time: apply(&entry.end_time),
+            ..entry.clone()
+        })
+        .collect())
+}
+
+/// Retime subtitles from source_fps to target_fps (scale = source_fps / target_fps)
+/// e.g. cues timed on 23.976 fps footage played back at 25 fps (PAL speed-up)
+pub fn rescale_subtitles(entries: &[SubtitleEntry], source_fps: f64, target_fps: f64) -> Result<Vec<SubtitleEntry>, String> {
+    if !source_fps.is_finite() || source_fps <= 0.0 {
+        return Err(format!("Invalid source fps: {}", source_fps));
+    }
+    if !target_fps.is_finite() || target_fps <= 0.0 {
+        return Err(format!("Invalid target fps: {}", target_fps));
+    }
+    scale_subtitles(entries, source_fps / target_fps)
+}
+
+// ============ 间隔与时长调整 ============
+
+/// Extend each cue's end to the next cue's start when the gap between them is at most max_gap_ms
+/// Produces gapless subtitles for continuous-display players; overlapping cues are left as they are
+pub fn close_gaps(entries: &[SubtitleEntry], max_gap_ms: u32) -> Vec<SubtitleEntry> {
+    let mut result = entries.to_vec();
+
+    for i in 0..result.len().saturating_sub(1) {
+        let end = result[i].end_time.to_millis();
+        let next_start = result[i + 1].start_time.to_millis();
+        if next_start > end && next_start - end <= max_gap_ms as u64 {
+            result[i].end_time = result[i + 1].start_time.clone();
+        }
+    }
+
+    result
+}
+
+/// Extend cues shorter than min_ms, never past the next cue's start (the cue is clamped there)
+/// Entries must be sorted by start time so the result stays monotonic
+pub fn enforce_min_duration(entries: &[SubtitleEntry], min_ms: u32) -> Result<Vec<SubtitleEntry>, String> {
+    if let Some(pair) = entries.windows(2).find(|w| w[1].start_time.to_millis() < w[0].start_time.to_millis()) {
+        return Err(format!(
+            "Subtitles are not sorted by start time (cue {} starts before cue {})",
+            pair[1].id, pair[0].id
+        ));
+    }
 
-    for block in blocks {
-        let block = block.trim();
-        if block.is_empty() {
+    let mut result = entries.to_vec();
+    for i in 0..result.len() {
+        let start = result[i].start_time.to_millis();
+        let end = result[i].end_time.to_millis();
+        if end >= start + min_ms as u64 {
             continue;
         }
+        let mut new_end = start + min_ms as u64;
+        if let Some(next) = result.get(i + 1) {
+            new_end = new_end.min(next.start_time.to_millis());
+        }
+        if new_end > end {
+            result[i].end_time = TimeStamp::from_millis(new_end);
+        }
+    }
 
-        let lines: Vec<&str> = block.lines().collect();
-        if lines.len() < 3 {
-            continue; // Skip invalid blocks
+    Ok(result)
+}
+
+// ============ 多引擎结果合并 ============
+
+/// How ensemble_merge picks the text of each aligned group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnsembleStrategy {
+    /// "majority": the text most engines agree on, ties go to the preferred engine;
+    /// "preferred": the preferred engine's text, falling back to majority where it has no cue
+    pub mode: String,
+    /// Index into the results list of the preferred engine
+    pub preferred: usize,
+}
+
+/// Key used to compare texts across engines: case, whitespace and punctuation are ignored
+fn vote_key(text: &str) -> String {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+/// Join two text fragments, without a space between CJK characters
+fn join_fragments(a: &str, b: &str) -> String {
+    match (a.chars().last(), b.chars().next()) {
+        (None, _) => b.to_string(),
+        (_, None) => a.to_string(),
+        (Some(x), Some(y)) if is_cjk_char(x) && is_cjk_char(y) => format!("{}{}", a, b),
+        _ => format!("{} {}", a, b),
+    }
+}
+
+/// Merge transcriptions of the same audio from several engines
+/// Cues are grouped with the earliest cue of each group (its anchor): a cue joins the group only
+/// if it overlaps the anchor, so a chain of slightly overlapping cues doesn't collapse into one.
+/// Each group becomes one cue spanning the union of its members, with text chosen by the strategy.
+pub fn ensemble_merge(results: &[Vec<SubtitleEntry>], strategy: &EnsembleStrategy) -> Vec<SubtitleEntry> {
+    // (engine index, cue) sorted by start time
+    let mut cues: Vec<(usize, &SubtitleEntry)> = results
+        .iter()
+        .enumerate()
+        .flat_map(|(engine, entries)| entries.iter().map(move |e| (engine, e)))
+        .collect();
+    cues.sort_by_key(|(engine, e)| (e.start_time.to_millis(), *engine));
+
+    // Group cues that overlap the group's anchor (not transitively)
+    let mut groups: Vec<Vec<(usize, &SubtitleEntry)>> = Vec::new();
+    let mut anchor_end = 0u64;
+    for (engine, cue) in cues {
+        match groups.last_mut() {
+            Some(group) if cue.start_time.to_millis() < anchor_end => group.push((engine, cue)),
+            _ => {
+                groups.push(vec![(engine, cue)]);
+                anchor_end = cue.end_time.to_millis();
+            }
         }
+    }
 
-        // Parse ID
-        let id = lines[0].trim().parse::<u32>()
-            .map_err(|e| format!("Invalid subtitle ID: {}", e))?;
+    groups
+        .iter()
+        .enumerate()
+        .map(|(index, group)| {
+            // Text per engine: an engine may split the group into several cues
+            let mut texts: Vec<(usize, String)> = Vec::new();
+            for (engine, cue) in group {
+                let text = cue.text.trim();
+                match texts.iter_mut().find(|(e, _)| e == engine) {
+                    Some((_, joined)) => *joined = join_fragments(joined, text),
+                    None => texts.push((*engine, text.to_string())),
+                }
+            }
+
+            let votes = |key: &str| texts.iter().filter(|(_, t)| vote_key(t) == key).count();
+            let preferred = texts.iter().find(|(e, _)| *e == strategy.preferred);
+
+            let text = match preferred {
+                Some((_, t)) if strategy.mode == "preferred" => t.clone(),
+                _ => {
+                    let best = texts.iter().map(|(_, t)| votes(&vote_key(t))).max().unwrap_or(0);
+                    match preferred {
+                        Some((_, t)) if votes(&vote_key(t)) == best => t.clone(),
+                        _ => texts
+                            .iter()
+                            .find(|(_, t)| votes(&vote_key(t)) == best)
+                            .map(|(_, t)| t.clone())
+                            .unwrap_or_default(),
+                    }
+                }
+            };
 
-        // Parse timestamps
-        let timestamp_line = lines[1].trim();
-        let times: Vec<&str> = timestamp_line.split(" --> ").collect();
-        if times.len() != 2 {
-            return Err(format!("Invalid timestamp line: {}", timestamp_line));
+            let start = group.iter().map(|(_, c)| c.start_time.to_millis()).min().unwrap_or(0);
+            let end = group.iter().map(|(_, c)| c.end_time.to_millis()).max().unwrap_or(start);
+
+            SubtitleEntry {
+                id: index as u32 + 1,
+                start_time: TimeStamp::from_millis(start),
+                end_time: TimeStamp::from_millis(end),
+                text,
+                confidence: None,
+                events: None,
+            }
+        })
+        .collect()
+}
+
+/// Move the text of a fresh transcription onto existing cues, keeping their timing
+/// Each new cue goes to the existing cue it overlaps most (or the nearest one when it overlaps
+/// none); fragments landing on the same cue are joined in order. Cues that receive no new text
+/// keep their original text.
+pub fn map_text_to_timing(existing: &[SubtitleEntry], transcribed: &[SubtitleEntry]) -> Vec<SubtitleEntry> {
+    let mut texts: Vec<Option<String>> = vec![None; existing.len()];
+
+    for cue in transcribed {
+        let start = cue.start_time.to_millis();
+        let end = cue.end_time.to_millis();
+        // (overlap, -distance) so the largest overlap wins, then the smallest gap
+        let best = existing
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, e)| {
+                let (e_start, e_end) = (e.start_time.to_millis(), e.end_time.to_millis());
+                let overlap = end.min(e_end).saturating_sub(start.max(e_start));
+                let distance = start.saturating_sub(e_end).max(e_start.saturating_sub(end));
+                (overlap, std::cmp::Reverse(distance))
+            })
+            .map(|(i, _)| i);
+
+        let text = cue.text.trim();
+        if let (Some(i), false) = (best, text.is_empty()) {
+            texts[i] = Some(match &texts[i] {
+                Some(joined) => join_fragments(joined, text),
+                None => text.to_string(),
+            });
         }
+    }
 
-        let start_time = TimeStamp::parse(times[0].trim())?;
-        let end_time = TimeStamp::parse(times[1].trim())?;
+    existing
+        .iter()
+        .zip(texts)
+        .map(|(entry, text)| match text {
+            Some(text) => SubtitleEntry {
+                text,
+                confidence: None,
+                ..entry.clone()
+            },
+            None => entry.clone(),
+        })
+        .collect()
+}
 
-        // Parse text (all remaining lines)
-        let text = lines[2..].join("\n");
+// ============ 字幕合并与拆分 ============
 
-        entries.push(SubtitleEntry {
-            id,
-            start_time,
-            end_time,
-            text,
-        });
+/// Merge adjacent cues whose gap is at most max_gap_ms and whose combined text fits in max_chars
+/// Text is joined with a space (no space between CJK characters); ids are renumbered from 1
+pub fn merge_subtitles(
+    entries: &[SubtitleEntry],
+    max_gap_ms: u32,
+    max_chars: usize,
+) -> Result<Vec<SubtitleEntry>, String> {
+    if max_chars == 0 {
+        return Err("max_chars must be greater than 0".to_string());
     }
 
-    Ok(entries)
+    let mut merged: Vec<SubtitleEntry> = Vec::new();
+    for entry in entries {
+        if let Some(last) = merged.last_mut() {
+            let gap = entry.start_time.to_millis() as i64 - last.end_time.to_millis() as i64;
+            let text = join_fragments(last.text.trim(), entry.text.trim());
+            if gap <= max_gap_ms as i64 && text.chars().count() <= max_chars {
+                if entry.start_time.to_millis() < last.start_time.to_millis() {
+                    last.start_time = entry.start_time.clone();
+                }
+                if entry.end_time.to_millis() > last.end_time.to_millis() {
+                    last.end_time = entry.end_time.clone();
+                }
+                last.text = text;
+                last.confidence = match (last.confidence, entry.confidence) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    _ => None,
+                };
+                continue;
+            }
+        }
+        merged.push(entry.clone());
+    }
+
+    for (i, entry) in merged.iter_mut().enumerate() {
+        entry.id = (i + 1) as u32;
+    }
+    Ok(merged)
 }
 
-/// Read and parse SRT file
-pub fn read_srt_file(file_path: &str) -> Result<SRTFile, String> {
-    let path = Path::new(file_path);
+/// Characters after which a cue may be split
+fn is_split_boundary(c: char) -> bool {
+    c.is_whitespace() || ",.!?;:，。！？；：、…".contains(c)
+}
 
-    if !path.exists() {
-        return Err(format!("File not found: {}", file_path));
+/// Split text into pieces of at most target characters, preferring word/punctuation boundaries
+/// Text without boundaries (e.g. CJK without punctuation) is split between characters
+fn split_text(text: &str, target: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().map(|c| if c == '\n' { ' ' } else { c }).collect();
+    let mut pieces = Vec::new();
+    let mut start = 0;
+
+    while start < chars.len() {
+        let mut end = if chars.len() - start <= target {
+            chars.len()
+        } else {
+            // Break after the last boundary in the second half of the window, else hard-cut
+            (start + target / 2 + 1..=start + target)
+                .rev()
+                .find(|&i| is_split_boundary(chars[i - 1]))
+                .unwrap_or(start + target)
+        };
+        end = end.max(start + 1);
+
+        let piece: String = chars[start..end].iter().collect::<String>().trim().to_string();
+        if !piece.is_empty() {
+            pieces.push(piece);
+        }
+        start = end;
     }
 
-    let content = fs::read_to_string(file_path)
-        .map_err(|e| format!("Failed to read file: {}", e))?;
+    pieces
+}
 
-    let entries = parse_srt(&content)?;
+/// Split cues longer than max_chars characters or max_duration_ms
+/// Time is divided proportionally to each piece's character count; ids are renumbered from 1
+pub fn split_subtitles(
+    entries: &[SubtitleEntry],
+    max_chars: usize,
+    max_duration_ms: u32,
+) -> Result<Vec<SubtitleEntry>, String> {
+    if max_chars == 0 || max_duration_ms == 0 {
+        return Err("max_chars and max_duration_ms must be greater than 0".to_string());
+    }
 
-    let name = path.file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("unknown")
-        .to_string();
+    let mut result = Vec::new();
+    for entry in entries {
+        let start = entry.start_time.to_millis();
+        let duration = entry.end_time.to_millis().saturating_sub(start);
+        let text = entry.text.trim();
+        let total_chars = text.chars().count();
 
-    Ok(SRTFile {
-        name,
-        path: file_path.to_string(),
-        entries,
-        encoding: Some("UTF-8".to_string()),
-    })
+        if (total_chars <= max_chars && duration <= max_duration_ms as u64) || total_chars <= 1 {
+            result.push(entry.clone());
+            continue;
+        }
+
+        // Increase the piece count until every piece fits both limits
+        let mut count = total_chars.div_ceil(max_chars).max(duration.div_ceil(max_duration_ms as u64) as usize);
+        let (pieces, weights) = loop {
+            let pieces = split_text(text, total_chars.div_ceil(count));
+            let weights: Vec<usize> = pieces.iter().map(|p| p.chars().count()).collect();
+            let weight_sum: usize = weights.iter().sum();
+            let longest = weights.iter().max().copied().unwrap_or(0) as u64;
+            if count >= total_chars || duration * longest <= max_duration_ms as u64 * weight_sum as u64 {
+                break (pieces, weights);
+            }
+            count += 1;
+        };
+
+        let weight_sum: usize = weights.iter().sum();
+        let mut cumulative = 0usize;
+        for (piece, weight) in pieces.into_iter().zip(weights) {
+            let piece_start = start + duration * cumulative as u64 / weight_sum as u64;
+            cumulative += weight;
+            let piece_end = start + duration * cumulative as u64 / weight_sum as u64;
+            result.push(SubtitleEntry {
+                id: 0,
+                start_time: TimeStamp::from_millis(piece_start),
+                end_time: TimeStamp::from_millis(piece_end),
+                text: piece,
+                confidence: entry.confidence,
+                events: entry.events.clone(),
+            });
+        }
+    }
+
+    for (i, entry) in result.iter_mut().enumerate() {
+        entry.id = (i + 1) as u32;
+    }
+    Ok(result)
+}
+
+// ============ 阅读速度 ============
+
+/// Reading speed of one cue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CpsInfo {
+    pub index: usize,
+    /// Visible characters per second
+    pub cps: f32,
+    /// Visible characters (tags and whitespace excluded, each CJK character counts as one)
+    pub chars: usize,
+    pub duration_ms: u32,
+    /// True when cps exceeds the requested limit
+    pub too_fast: bool,
+}
+
+/// Compute characters-per-second for every cue; too_fast is set when cps > limit
+/// Zero-length cues are treated as lasting 1 ms so any text in them is flagged
+pub fn compute_reading_speed(entries: &[SubtitleEntry], limit: Option<f32>) -> Vec<CpsInfo> {
+    entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let chars = strip_tags(&entry.text).chars().filter(|c| !c.is_whitespace()).count();
+            let duration_ms = entry.end_time.to_millis().saturating_sub(entry.start_time.to_millis());
+            let cps = if chars == 0 { 0.0 } else { chars as f32 * 1000.0 / duration_ms.max(1) as f32 };
+            CpsInfo {
+                index,
+                cps,
+                chars,
+                duration_ms: duration_ms.min(u32::MAX as u64) as u32,
+                too_fast: limit.is_some_and(|l| cps > l),
+            }
+        })
+        .collect()
+}
+
+// ============ 词典替换 ============
+
+/// A word-replacement rule: occurrences of `wrong` are rewritten to `right`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DictionaryEntry {
+    pub wrong: String,
+    pub right: String,
+}
+
+/// Whether a character is part of a Latin/digit word; CJK characters are not,
+/// so "OpenAI" still matches inside "用OpenAI的"
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() && !is_cjk_char(c)
+}
+
+/// Replace whole-word occurrences of `wrong` with `right`
+/// Word boundaries are only enforced on edges of `wrong` that are Latin/digit characters;
+/// CJK text has no word boundaries, so CJK edges match anywhere
+pub fn replace_whole_word(text: &str, wrong: &str, right: &str) -> String {
+    if wrong.is_empty() {
+        return text.to_string();
+    }
+    let check_start = wrong.chars().next().is_some_and(is_word_char);
+    let check_end = wrong.chars().next_back().is_some_and(is_word_char);
+
+    let mut result = String::with_capacity(text.len());
+    let mut copied = 0;
+    let mut search = 0;
+    while let Some(pos) = text[search..].find(wrong) {
+        let start = search + pos;
+        let end = start + wrong.len();
+        let start_ok = !check_start || !text[..start].chars().next_back().is_some_and(is_word_char);
+        let end_ok = !check_end || !text[end..].chars().next().is_some_and(is_word_char);
+        if start_ok && end_ok {
+            result.push_str(&text[copied..start]);
+            result.push_str(right);
+            copied = end;
+            search = end;
+        } else {
+            search = start + text[start..].chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    result.push_str(&text[copied..]);
+    result
+}
+
+/// Apply every dictionary rule to a piece of text; longer `wrong` strings go first
+/// so a rule for "Open AI" wins over one for "AI"
+pub fn apply_dictionary_to_text(text: &str, dictionary: &[DictionaryEntry]) -> String {
+    let mut rules: Vec<&DictionaryEntry> = dictionary.iter().filter(|e| !e.wrong.is_empty()).collect();
+    rules.sort_by(|a, b| b.wrong.chars().count().cmp(&a.wrong.chars().count()));
+    rules
+        .iter()
+        .fold(text.to_string(), |acc, rule| replace_whole_word(&acc, &rule.wrong, &rule.right))
+}
+
+/// Apply dictionary rules to the text of every cue
+pub fn apply_dictionary_to_entries(entries: &[SubtitleEntry], dictionary: &[DictionaryEntry]) -> Vec<SubtitleEntry> {
+    entries
+        .iter()
+        .map(|entry| SubtitleEntry {
+            text: apply_dictionary_to_text(&entry.text, dictionary),
+            ..entry.clone()
+        })
+        .collect()
+}
+
+// ============ 查找替换 ============
+
+/// Result of replace_in_subtitles
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplaceResult {
+    pub entries: Vec<SubtitleEntry>,
+    /// Total number of replacements across all cues
+    pub count: usize,
+}
+
+/// Find and replace across all cue texts
+/// In regex mode `replace` may reference capture groups ($1, ${name}); otherwise both are literal.
+pub fn replace_in_subtitles(
+    entries: &[SubtitleEntry],
+    find: &str,
+    replace: &str,
+    regex: bool,
+    case_sensitive: bool,
+) -> Result<ReplaceResult, String> {
+    if find.is_empty() {
+        return Err("Search text must not be empty".to_string());
+    }
+
+    let pattern = if regex { find.to_string() } else { regex::escape(find) };
+    let re = regex::RegexBuilder::new(&pattern)
+        .case_insensitive(!case_sensitive)
+        .build()
+        .map_err(|e| format!("Invalid regular expression: {}", e))?;
+
+    let mut count = 0;
+    let entries = entries
+        .iter()
+        .map(|entry| {
+            let matches = re.find_iter(&entry.text).count();
+            if matches == 0 {
+                return entry.clone();
+            }
+            count += matches;
+            let text = if regex {
+                re.replace_all(&entry.text, replace).into_owned()
+            } else {
+                re.replace_all(&entry.text, regex::NoExpand(replace)).into_owned()
+            };
+            SubtitleEntry { text, ..entry.clone() }
+        })
+        .collect();
+
+    Ok(ReplaceResult { entries, count })
+}
+
+// ============ 分卷导出 ============
+
+/// Partition subtitles into reels at the given boundaries (ms)
+/// Reel N covers [boundary N-1, boundary N); a cue belongs to the reel its start falls in.
+/// Each reel is rebased to start at zero and renumbered from 1; a cue crossing
+/// the next boundary is cut off at the end of its reel.
+pub fn split_into_reels(entries: &[SubtitleEntry], reel_boundaries_ms: &[u32]) -> Vec<Vec<SubtitleEntry>> {
+    let mut boundaries: Vec<u64> = reel_boundaries_ms.iter().map(|&b| b as u64).filter(|&b| b > 0).collect();
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    let mut reels: Vec<Vec<SubtitleEntry>> = vec![Vec::new(); boundaries.len() + 1];
+
+    for entry in entries {
+        let start = entry.start_time.to_millis();
+        let end = entry.end_time.to_millis();
+        let reel = boundaries.iter().take_while(|&&b| b <= start).count();
+        let reel_start = if reel == 0 { 0 } else { boundaries[reel - 1] };
+        let reel_end = boundaries.get(reel).copied().unwrap_or(u64::MAX);
+
+        let id = reels[reel].len() as u32 + 1;
+        reels[reel].push(SubtitleEntry {
+            id,
+            start_time: TimeStamp::from_millis(start - reel_start),
+            end_time: TimeStamp::from_millis(end.min(reel_end).saturating_sub(reel_start)),
+            text: entry.text.clone(),
+            confidence: entry.confidence,
+            events: entry.events.clone(),
+        });
+    }
+
+    reels
+}
+
+/// Write each reel to `<base>_reel01.srt`, `<base>_reel02.srt`, ...
+/// A trailing ".srt" on base_path is ignored. Returns the written paths.
+pub fn write_reel_files(base_path: &str, reels: &[Vec<SubtitleEntry>]) -> Result<Vec<String>, String> {
+    let base = base_path.strip_suffix(".srt").unwrap_or(base_path);
+    let mut paths = Vec::new();
+
+    for (index, reel) in reels.iter().enumerate() {
+        let path = format!("{}_reel{:02}.srt", base, index + 1);
+        write_srt_file(&path, reel)?;
+        paths.push(path);
+    }
+
+    Ok(paths)
 }
 
 /// 文件权限检查结果
@@ -250,14 +1879,23 @@ pub fn check_file_permission(file_path: &str) -> FilePermissionCheck {
     }
 }
 
-/// Write SRT file
+/// Write SRT file, renumbering entries sequentially from 1 (incoming ids are ignored)
 pub fn write_srt_file(file_path: &str, entries: &[SubtitleEntry]) -> Result<(), String> {
+    write_srt_entries(file_path, entries, false)
+}
+
+/// Write SRT file keeping each entry's original id (may produce gaps or duplicates)
+pub fn write_srt_file_keep_ids(file_path: &str, entries: &[SubtitleEntry]) -> Result<(), String> {
+    write_srt_entries(file_path, entries, true)
+}
+
+fn write_srt_entries(file_path: &str, entries: &[SubtitleEntry], keep_ids: bool) -> Result<(), String> {
     let mut content = String::new();
 
     for (index, entry) in entries.iter().enumerate() {
-        // Add subtitle ID (sequence number starting from 1)
-        // Always use sequential numbering regardless of original id
-        content.push_str(&format!("{}\n", index + 1));
+        // Add subtitle ID: sequential numbering starting from 1 unless original ids are kept
+        let id = if keep_ids { entry.id } else { index as u32 + 1 };
+        content.push_str(&format!("{}\n", id));
 
         // Add timestamp line
         content.push_str(&format!(
@@ -301,12 +1939,79 @@ impl TimeStamp {
         )
     }
 
+    /// Convert to ASS format: H:MM:SS.cc (centiseconds)
+    pub fn to_ass_string(&self) -> String {
+        format!(
+            "{}:{:02}:{:02}.{:02}",
+            self.hours, self.minutes, self.seconds, self.milliseconds / 10
+        )
+    }
+
+    /// Convert to CUE sheet index format: MM:SS:FF (75 frames per second)
+    pub fn to_cue_string(&self) -> String {
+        let total_ms = self.to_millis();
+        let minutes = total_ms / 60000;
+        let seconds = (total_ms % 60000) / 1000;
+        let frames = (total_ms % 1000) * 75 / 1000;
+        format!("{:02}:{:02}:{:02}", minutes, seconds, frames)
+    }
+
+    /// Convert to LRC format: mm:ss.xx (total minutes, two-digit centiseconds)
+    pub fn to_lrc_string(&self) -> String {
+        let total_ms = self.to_millis();
+        format!(
+            "{:02}:{:02}.{:02}",
+            total_ms / 60000,
+            (total_ms % 60000) / 1000,
+            (total_ms % 1000) / 10
+        )
+    }
+
     /// Convert to total milliseconds
-    pub fn to_ms(&self) -> u64 {
+    pub fn to_millis(&self) -> u64 {
         (self.hours as u64 * 3600 + self.minutes as u64 * 60 + self.seconds as u64) * 1000
             + self.milliseconds as u64
     }
 
+    /// Build a timestamp from total milliseconds
+    pub fn from_millis(ms: u64) -> Self {
+        TimeStamp {
+            hours: (ms / 3_600_000) as u32,
+            minutes: ((ms % 3_600_000) / 60_000) as u32,
+            seconds: ((ms % 60_000) / 1000) as u32,
+            milliseconds: (ms % 1000) as u32,
+        }
+    }
+
+    /// Parse timestamp from ASS format: H:MM:SS.cc (centiseconds)
+    pub fn parse_ass(s: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = s.trim().split(':').collect();
+        if parts.len() != 3 {
+            return Err(format!("Invalid ASS timestamp format: {}", s));
+        }
+
+        let hours = parts[0].parse::<u32>()
+            .map_err(|e| format!("Invalid hours: {}", e))?;
+        let minutes = parts[1].parse::<u32>()
+            .map_err(|e| format!("Invalid minutes: {}", e))?;
+
+        let sec_parts: Vec<&str> = parts[2].split('.').collect();
+        let seconds = sec_parts[0].parse::<u32>()
+            .map_err(|e| format!("Invalid seconds: {}", e))?;
+        let centiseconds = match sec_parts.get(1) {
+            Some(cs) => cs.parse::<u32>()
+                .map_err(|e| format!("Invalid centiseconds: {}", e))?,
+            None => 0,
+        };
+
+        Ok(TimeStamp {
+            hours,
+            minutes,
+            seconds,
+            milliseconds: centiseconds * 10,
+        })
+    }
+
     /// Convert to frames at given frame rate
     pub fn to_frames(&self, fps: f64) -> u64 {
         let total_seconds = self.hours as f64 * 3600.0
@@ -315,15 +2020,73 @@ impl TimeStamp {
             + self.milliseconds as f64 / 1000.0;
         (total_seconds * fps).round() as u64
     }
+
+    /// Format as a TXT line prefix in the given style
+    pub fn to_prefix_string(&self, style: TimestampStyle) -> String {
+        match style {
+            TimestampStyle::Hms => format!("[{}]", self.to_simple_string()),
+            TimestampStyle::MinSec => format!("[{:02}:{:02}]", self.hours * 60 + self.minutes, self.seconds),
+            TimestampStyle::Seconds => format!("[{:.3}]", self.to_millis() as f64 / 1000.0),
+        }
+    }
+}
+
+/// Timestamp prefix for TXT export lines
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampStyle {
+    /// [HH:MM:SS]
+    Hms,
+    /// [MM:SS], minutes keep counting past 59
+    MinSec,
+    /// [83.456], seconds from the start
+    Seconds,
+}
+
+/// Export to TXT (plain text, subtitles only)
+pub fn export_to_txt(file_path: &str, entries: &[SubtitleEntry]) -> Result<(), String> {
+    export_to_txt_with_options(file_path, entries, &TxtExportOptions::default())
+}
+
+/// Options for export_to_txt_with_options
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TxtExportOptions {
+    /// Prefix each cue with its start time
+    pub include_timestamps: bool,
+    /// Prefix format when include_timestamps is set: "hms" ([HH:MM:SS], default) | "min_sec" | "seconds"
+    pub timestamp_style: TimestampStyle,
+    /// Written between cues (e.g. "\n" for one cue per line, "\n\n" for paragraphs, " " for prose)
+    pub line_separator: String,
+}
+
+impl Default for TxtExportOptions {
+    fn default() -> Self {
+        TxtExportOptions {
+            include_timestamps: false,
+            timestamp_style: TimestampStyle::Hms,
+            line_separator: "\n".to_string(),
+        }
+    }
 }
 
-/// Export to TXT (plain text, subtitles only)
-pub fn export_to_txt(file_path: &str, entries: &[SubtitleEntry]) -> Result<(), String> {
+/// Export to TXT with a configurable separator and optional timestamp prefixes
+pub fn export_to_txt_with_options(
+    file_path: &str,
+    entries: &[SubtitleEntry],
+    options: &TxtExportOptions,
+) -> Result<(), String> {
     let content: String = entries
         .iter()
-        .map(|e| e.text.clone())
+        .map(|e| {
+            if options.include_timestamps {
+                format!("{} {}", e.start_time.to_prefix_string(options.timestamp_style), e.text)
+            } else {
+                e.text.clone()
+            }
+        })
         .collect::<Vec<_>>()
-        .join("\n");
+        .join(&options.line_separator);
 
     fs::write(file_path, content)
         .map_err(|e| format!("Failed to write TXT file: {}", e))?;
@@ -383,17 +2146,346 @@ pub fn export_to_markdown(file_path: &str, entries: &[SubtitleEntry]) -> Result<
     Ok(())
 }
 
+/// Style for the single "Default" ASS style written by export_to_ass
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssStyle {
+    pub font_name: String,
+    pub font_size: u32,
+    /// "#RRGGBB" or ASS "&HAABBGGRR"
+    pub primary_colour: String,
+    /// Outline width in pixels
+    pub outline: f32,
+}
+
+impl Default for AssStyle {
+    fn default() -> Self {
+        AssStyle {
+            font_name: "Arial".to_string(),
+            font_size: 60,
+            primary_colour: "&H00FFFFFF".to_string(),
+            outline: 2.0,
+        }
+    }
+}
+
+/// Normalize a colour to ASS "&HAABBGGRR" (ASS stores channels as blue-green-red)
+fn ass_colour(colour: &str) -> Result<String, String> {
+    let colour = colour.trim();
+    if let Some(hex) = colour.strip_prefix('#') {
+        if hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            let hex = hex.to_uppercase();
+            return Ok(format!("&H00{}{}{}", &hex[4..6], &hex[2..4], &hex[0..2]));
+        }
+    } else if let Some(hex) = colour.strip_prefix("&H").or_else(|| colour.strip_prefix("&h")) {
+        let hex = hex.trim_end_matches('&');
+        if (hex.len() == 6 || hex.len() == 8) && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Ok(format!("&H{:0>8}", hex.to_uppercase()));
+        }
+    }
+    Err(format!("Invalid colour: {}", colour))
+}
+
+/// Convert SRT text to ASS dialogue text
+/// <i>/<b> tags become \i1/\i0 and \b1/\b0 overrides, line breaks become \N
+fn srt_text_to_ass(text: &str) -> String {
+    text.replace("<i>", "{\\i1}")
+        .replace("</i>", "{\\i0}")
+        .replace("<b>", "{\\b1}")
+        .replace("</b>", "{\\b0}")
+        .replace("\r\n", "\n")
+        .replace('\n', "\\N")
+}
+
+/// Export to ASS (Advanced SubStation Alpha) with a single default style
+pub fn export_to_ass(file_path: &str, entries: &[SubtitleEntry], style: &AssStyle) -> Result<(), String> {
+    let primary_colour = ass_colour(&style.primary_colour)?;
+    let mut content = format!(
+        "[Script Info]\n\
+         ScriptType: v4.00+\n\
+         PlayResX: 1920\n\
+         PlayResY: 1080\n\
+         WrapStyle: 0\n\
+         \n\
+         [V4+ Styles]\n\
+         Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding\n\
+         Style: Default,{},{},{},&H000000FF,&H00000000,&H80000000,0,0,0,0,100,100,0,0,1,{},1,2,20,20,40,1\n\
+         \n\
+         [Events]\n\
+         Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n",
+        style.font_name.replace(',', " "),
+        style.font_size,
+        primary_colour,
+        style.outline
+    );
+
+    for entry in entries {
+        content.push_str(&format!(
+            "Dialogue: 0,{},{},Default,,0,0,0,,{}\n",
+            entry.start_time.to_ass_string(),
+            entry.end_time.to_ass_string(),
+            srt_text_to_ass(&entry.text)
+        ));
+    }
+
+    fs::write(file_path, content)
+        .map_err(|e| format!("Failed to write ASS file: {}", e))?;
+
+    println!("Successfully exported {} subtitles to ASS: {}", entries.len(), file_path);
+    Ok(())
+}
+
+/// Export to Audacity label track (tab-separated "start\tend\tlabel", seconds with 6 decimals)
+pub fn export_audacity_labels(file_path: &str, entries: &[SubtitleEntry]) -> Result<(), String> {
+    let mut content = String::new();
+
+    for entry in entries {
+        // Labels are single-line; tabs would break the column format
+        let label = entry.text.replace('\n', " ").replace('\t', " ");
+        content.push_str(&format!(
+            "{:.6}\t{:.6}\t{}\n",
+            entry.start_time.to_millis() as f64 / 1000.0,
+            entry.end_time.to_millis() as f64 / 1000.0,
+            label
+        ));
+    }
+
+    fs::write(file_path, content)
+        .map_err(|e| format!("Failed to write Audacity label file: {}", e))?;
+
+    println!("Successfully exported {} subtitles to Audacity labels: {}", entries.len(), file_path);
+    Ok(())
+}
+
+/// Export to LRC lyrics ("[mm:ss.xx]text" per cue, multi-line text joined with spaces)
+/// A [length:mm:ss] tag is written from the last cue's end time
+pub fn export_to_lrc(file_path: &str, entries: &[SubtitleEntry]) -> Result<(), String> {
+    let mut content = String::new();
+
+    if let Some(length_ms) = entries.iter().map(|e| e.end_time.to_millis()).max() {
+        content.push_str(&format!(
+            "[length:{:02}:{:02}]\n",
+            length_ms / 60000,
+            (length_ms % 60000) / 1000
+        ));
+    }
+
+    for entry in entries {
+        let line = entry.text.lines().map(str::trim).filter(|l| !l.is_empty()).collect::<Vec<_>>().join(" ");
+        content.push_str(&format!("[{}]{}\n", entry.start_time.to_lrc_string(), line));
+    }
+
+    fs::write(file_path, content)
+        .map_err(|e| format!("Failed to write LRC file: {}", e))?;
+
+    println!("Successfully exported {} subtitles to LRC: {}", entries.len(), file_path);
+    Ok(())
+}
+
+/// Export to TTML / DFXP (W3C Timed Text) for broadcast workflows
+/// Times use the HH:MM:SS.mmm clock format; line breaks become <br/>
+pub fn export_to_ttml(file_path: &str, entries: &[SubtitleEntry]) -> Result<(), String> {
+    // xml:lang is mandatory on <tt>; an empty value means undetermined
+    let lang = match detect_subtitle_language(entries) {
+        lang if lang == "auto" => String::new(),
+        lang => lang,
+    };
+    let mut content = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<tt xmlns="http://www.w3.org/ns/ttml" xml:lang="{}">
+  <body>
+    <div>
+"#,
+        lang
+    );
+
+    for entry in entries {
+        let text = entry.text
+            .replace("\r\n", "\n")
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&apos;")
+            .lines()
+            .collect::<Vec<_>>()
+            .join("<br/>");
+        content.push_str(&format!(
+            "      <p begin=\"{}\" end=\"{}\">{}</p>\n",
+            entry.start_time.to_vtt_string(),
+            entry.end_time.to_vtt_string(),
+            text
+        ));
+    }
+
+    content.push_str("    </div>\n  </body>\n</tt>\n");
+
+    fs::write(file_path, content)
+        .map_err(|e| format!("Failed to write TTML file: {}", e))?;
+
+    println!("Successfully exported {} subtitles to TTML: {}", entries.len(), file_path);
+    Ok(())
+}
+
+/// Quote a CSV field per RFC 4180 when it contains a comma, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Export to CSV for spreadsheet review (header row "id,start,end,text", CRLF record separators)
+/// A UTF-8 BOM is written so Excel detects the encoding; line breaks within a cue stay inside the quoted field
+pub fn export_to_csv(file_path: &str, entries: &[SubtitleEntry]) -> Result<(), String> {
+    let mut content = String::from("\u{feff}id,start,end,text\r\n");
+
+    for entry in entries {
+        content.push_str(&format!(
+            "{},{},{},{}\r\n",
+            entry.id,
+            csv_field(&entry.start_time.to_string()),
+            csv_field(&entry.end_time.to_string()),
+            csv_field(&entry.text)
+        ));
+    }
+
+    fs::write(file_path, content)
+        .map_err(|e| format!("Failed to write CSV file: {}", e))?;
+
+    println!("Successfully exported {} subtitles to CSV: {}", entries.len(), file_path);
+    Ok(())
+}
+
+/// Largest track number a CUE sheet can hold
+const MAX_CUE_TRACKS: usize = 99;
+
+/// Export to CUE sheet (one TRACK per subtitle, titled by the cue text)
+/// audio_path: the audio file referenced by the FILE line (written as a bare file name)
+/// The CUE format allows at most 99 tracks, so longer subtitle files are rejected
+pub fn export_cue_sheet(audio_path: &str, entries: &[SubtitleEntry], file_path: &str) -> Result<(), String> {
+    if entries.len() > MAX_CUE_TRACKS {
+        return Err(format!(
+            "CUE sheets support at most {} tracks, but there are {} subtitles",
+            MAX_CUE_TRACKS,
+            entries.len()
+        ));
+    }
+
+    let audio_name = Path::new(audio_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(audio_path);
+
+    // CUE file types: WAVE / MP3 / AIFF, other formats are declared as WAVE by convention
+    let file_type = match Path::new(audio_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .as_deref()
+    {
+        Some("mp3") => "MP3",
+        Some("aif") | Some("aiff") => "AIFF",
+        _ => "WAVE",
+    };
+
+    let mut content = format!("FILE \"{}\" {}\n", audio_name.replace('"', "'"), file_type);
+
+    for (index, entry) in entries.iter().enumerate() {
+        // CUE titles are single-line and double-quoted
+        let title = entry.text.replace('\n', " ").replace('"', "'");
+
+        content.push_str(&format!("  TRACK {:02} AUDIO\n", index + 1));
+        content.push_str(&format!("    TITLE \"{}\"\n", title));
+        content.push_str(&format!("    INDEX 01 {}\n", entry.start_time.to_cue_string()));
+    }
+
+    fs::write(file_path, content)
+        .map_err(|e| format!("Failed to write CUE file: {}", e))?;
+
+    println!("Successfully exported {} subtitles to CUE sheet: {}", entries.len(), file_path);
+    Ok(())
+}
+
+/// Typography for FCPXML titles (written into each title's <text-style>)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TitleStyle {
+    pub font_name: String,
+    pub font_size: u32,
+    /// "#RRGGBB", "#RRGGBBAA" or FCPXML "r g b a" (0-1 floats)
+    pub font_color_rgba: String,
+    /// "left" | "center" | "right"
+    pub alignment: String,
+}
+
+impl Default for TitleStyle {
+    fn default() -> Self {
+        TitleStyle {
+            font_name: "PingFang SC".to_string(),
+            font_size: 62,
+            font_color_rgba: "1 1 1 1".to_string(),
+            alignment: "center".to_string(),
+        }
+    }
+}
+
+/// Normalize a colour to the FCPXML "r g b a" form
+fn fcpxml_colour(colour: &str) -> Result<String, String> {
+    let colour = colour.trim();
+    if let Some(hex) = colour.strip_prefix('#') {
+        if (hex.len() == 6 || hex.len() == 8) && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap_or(255) as f64 / 255.0;
+            let alpha = if hex.len() == 8 { channel(6) } else { 1.0 };
+            let format_channel = |v: f64| format!("{:.4}", v).trim_end_matches('0').trim_end_matches('.').to_string();
+            return Ok(format!(
+                "{} {} {} {}",
+                format_channel(channel(0)),
+                format_channel(channel(2)),
+                format_channel(channel(4)),
+                format_channel(alpha)
+            ));
+        }
+    } else {
+        let parts: Vec<f64> = colour.split_whitespace().filter_map(|p| p.parse().ok()).collect();
+        if parts.len() == 4 && colour.split_whitespace().count() == 4 && parts.iter().all(|v| (0.0..=1.0).contains(v)) {
+            return Ok(colour.split_whitespace().collect::<Vec<_>>().join(" "));
+        }
+    }
+    Err(format!("Invalid colour: {}", colour))
+}
+
 /// Export to FCPXML (Final Cut Pro XML)
 /// fps: frame rate (e.g., 24.0, 25.0, 29.97, 30.0, 60.0)
 /// position_x: subtitle X position (default: 0)
 /// position_y: subtitle Y position (default: -415)
+/// style: font, size, colour and alignment of every title
 pub fn export_to_fcpxml(
     file_path: &str,
     entries: &[SubtitleEntry],
     fps: f64,
     position_x: i32,
     position_y: i32,
+    style: &TitleStyle,
 ) -> Result<(), String> {
+    if !fps.is_finite() || fps <= 0.0 {
+        return Err(format!("Invalid fps: {}", fps));
+    }
+    if style.font_size == 0 {
+        return Err("Font size must be greater than 0".to_string());
+    }
+    let font_color = fcpxml_colour(&style.font_color_rgba)?;
+    let (alignment, alignment_param) = match style.alignment.trim().to_lowercase().as_str() {
+        "left" => ("left", "0 (左)"),
+        "center" | "centre" | "" => ("center", "1 (居中)"),
+        "right" => ("right", "2 (右)"),
+        other => return Err(format!("Invalid alignment: {}", other)),
+    };
+    let font_name = style.font_name
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('"', "&quot;");
+
     // Calculate frame duration and format name based on fps
     let (frame_duration, format_name) = match fps as u32 {
         24 => ("100/2400s", "FFVideoFormat1080p24"),
@@ -446,14 +2538,14 @@ pub fn export_to_fcpxml(
         content.push_str(&format!(
             r#"<title name="{} - 自定" lane="1" offset="{}/{}s" ref="r2" duration="{}/{}s">
 <param name="位置" key="9999/10199/10201/1/100/101" value="{} {}"/>
-<param name="对齐" key="9999/10199/10201/2/354/1002961760/401" value="1 (居中)"/>
+<param name="对齐" key="9999/10199/10201/2/354/1002961760/401" value="{}"/>
 <param name="Out Sequencing" key="9999/10199/10201/4/10233/201/202" value="0 (到)"/>
 
 <text>
   <text-style ref="ts{}">{}</text-style>
 </text>
 <text-style-def id="ts{}">
-  <text-style font="PingFang SC" fontSize="62" fontFace="Semibold" fontColor="1 1 1 1" bold="1" strokeColor="0.329705 0.329721 0.329713 1" strokeWidth="-1" shadowColor="0 0 0 0.75" shadowOffset="3 315" kerning="1.24" alignment="center"/>
+  <text-style font="{}" fontSize="{}" fontFace="Semibold" fontColor="{}" bold="1" strokeColor="0.329705 0.329721 0.329713 1" strokeWidth="-1" shadowColor="0 0 0 0.75" shadowOffset="3 315" kerning="1.24" alignment="{}"/>
 </text-style-def>
 </title>
 "#,
@@ -461,9 +2553,11 @@ pub fn export_to_fcpxml(
             start_units, time_base,
             duration_units, time_base,
             position_x, position_y,
+            alignment_param,
             index + 1,
             escaped_text,
-            index + 1
+            index + 1,
+            font_name, style.font_size, font_color, alignment
         ));
     }
 
@@ -482,10 +2576,143 @@ pub fn export_to_fcpxml(
     Ok(())
 }
 
+/// Write several formats at once next to base_path (its extension is replaced per format)
+/// Supported: txt, vtt, srt, markdown, fcpxml, lrc, csv, ttml, ass, audacity.
+/// Every format is attempted; failures are collected and reported together.
+pub fn export_all_formats(
+    base_path: &str,
+    entries: &[SubtitleEntry],
+    fps: f64,
+    formats: &[String],
+) -> Result<Vec<String>, String> {
+    let base = Path::new(base_path);
+    let mut written = Vec::new();
+    let mut errors = Vec::new();
+
+    for format in formats {
+        let format = format.trim().to_lowercase();
+        let extension = match format.as_str() {
+            "txt" => "txt",
+            "vtt" => "vtt",
+            "srt" => "srt",
+            "markdown" | "md" => "md",
+            "fcpxml" => "fcpxml",
+            "lrc" => "lrc",
+            "csv" => "csv",
+            "ttml" => "ttml",
+            "ass" => "ass",
+            // Avoid clashing with the plain TXT export
+            "audacity" => "labels.txt",
+            _ => {
+                errors.push(format!("{}: unsupported format", format));
+                continue;
+            }
+        };
+        let path = base.with_extension(extension);
+        let path_str = path.to_string_lossy().to_string();
+
+        let result = match format.as_str() {
+            "txt" => export_to_txt(&path_str, entries),
+            "vtt" => export_to_vtt(&path_str, entries),
+            "srt" => write_srt_file(&path_str, entries),
+            "markdown" | "md" => export_to_markdown(&path_str, entries),
+            "fcpxml" => export_to_fcpxml(&path_str, entries, fps, 0, -415, &TitleStyle::default()),
+            "lrc" => export_to_lrc(&path_str, entries),
+            "csv" => export_to_csv(&path_str, entries),
+            "ttml" => export_to_ttml(&path_str, entries),
+            "ass" => export_to_ass(&path_str, entries, &AssStyle::default()),
+            _ => export_audacity_labels(&path_str, entries),
+        };
+
+        match result {
+            Ok(()) => written.push(path_str),
+            Err(e) => errors.push(format!("{}: {}", format, e)),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(written)
+    } else {
+        Err(format!(
+            "Failed to export {} format(s): {}. Written: {}",
+            errors.len(),
+            errors.join("; "),
+            if written.is_empty() { "none".to_string() } else { written.join(", ") }
+        ))
+    }
+}
+
+/// Export an HTML confidence report for visual QA
+/// Each cue is shaded from red (low confidence) to green (high); cues without a score are grey
+pub fn export_confidence_html(file_path: &str, entries: &[SubtitleEntry]) -> Result<(), String> {
+    let mut content = String::from(
+        r#"<!DOCTYPE html>
+<html lang="zh">
+<head>
+<meta charset="utf-8">
+<title>字幕置信度报告</title>
+<style>
+body { font-family: -apple-system, "PingFang SC", "Microsoft YaHei", sans-serif; margin: 24px; }
+.cue { display: flex; gap: 12px; padding: 6px 10px; margin: 2px 0; border-radius: 4px; }
+.time { font-family: monospace; color: #555; white-space: nowrap; }
+.score { font-family: monospace; width: 4em; text-align: right; }
+.text { flex: 1; white-space: pre-wrap; }
+</style>
+</head>
+<body>
+<h1>字幕置信度报告</h1>
+"#,
+    );
+
+    for entry in entries {
+        let escaped_text = entry.text
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;");
+
+        // Hue 0 = red, 120 = green
+        let (background, score) = match entry.confidence {
+            Some(c) => {
+                let c = c.clamp(0.0, 1.0);
+                (format!("hsl({:.0}, 70%, 85%)", c * 120.0), format!("{:.2}", c))
+            }
+            None => ("#eeeeee".to_string(), "—".to_string()),
+        };
+
+        content.push_str(&format!(
+            "<div class=\"cue\" style=\"background: {}\"><span class=\"time\">{} --> {}</span><span class=\"score\">{}</span><span class=\"text\">{}</span></div>\n",
+            background,
+            entry.start_time.to_string(),
+            entry.end_time.to_string(),
+            score,
+            escaped_text
+        ));
+    }
+
+    content.push_str("</body>\n</html>\n");
+
+    fs::write(file_path, content)
+        .map_err(|e| format!("Failed to write HTML file: {}", e))?;
+
+    println!("Successfully exported confidence report for {} subtitles: {}", entries.len(), file_path);
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn cue(id: u32, start_ms: u64, end_ms: u64, text: &str) -> SubtitleEntry {
+        SubtitleEntry {
+            id,
+            start_time: TimeStamp::from_millis(start_ms),
+            end_time: TimeStamp::from_millis(end_ms),
+            text: text.to_string(),
+            confidence: None,
+            events: None,
+        }
+    }
+
     #[test]
     fn test_timestamp_parse() {
         let ts = TimeStamp::parse("00:01:23,456").unwrap();
@@ -506,6 +2733,333 @@ mod tests {
         assert_eq!(ts.to_string(), "00:01:23,456");
     }
 
+    #[test]
+    fn test_parse_srt_with_bom() {
+        let content = "\u{feff}1\n00:00:01,000 --> 00:00:02,000\nHello\n\n2\n00:00:03,000 --> 00:00:04,000\nWorld\n";
+        let entries = parse_srt(content).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, 1);
+        assert_eq!(entries[1].text, "World");
+    }
+
+    #[test]
+    fn test_parse_srt_error_line_numbers() {
+        let content = "1\n00:00:01,000 --> 00:00:02,000\nHello\n\n2\n00:00:03,000 --> 00:00:xx,000\nWorld\n";
+        let err = parse_srt(content).unwrap_err();
+        assert!(err.starts_with("line 6:"), "{}", err);
+
+        // A blank line inside cue text yields a block without ID, which is skipped
+        let content = "1\n00:00:01,000 --> 00:00:02,000\nHello\n\nmore\ntext\nhere\n\n2\n00:00:03,000 --> 00:00:04,000\nWorld\n";
+        let entries = parse_srt(content).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].text, "World");
+    }
+
+    #[test]
+    fn test_parse_srt_with_crlf() {
+        let content = "1\r\n00:00:01,000 --> 00:00:02,000\r\nLine one\r\nLine two\r\n\r\n2\r\n00:00:03,000 --> 00:00:04,500\r\nNext\r\n";
+        let entries = parse_srt(content).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].text, "Line one\nLine two");
+        assert_eq!(entries[1].end_time.milliseconds, 500);
+    }
+
+    #[test]
+    fn test_timestamp_millis_roundtrip() {
+        for ms in [0u64, 999, 59_999, 3_599_999, 3_600_000, 3_600_001, 7_265_432, 360_000_000] {
+            assert_eq!(TimeStamp::from_millis(ms).to_millis(), ms);
+        }
+        let ts = TimeStamp::from_millis(3_600_000);
+        assert_eq!((ts.hours, ts.minutes, ts.seconds, ts.milliseconds), (1, 0, 0, 0));
+        let ts = TimeStamp::from_millis(3_599_999);
+        assert_eq!((ts.hours, ts.minutes, ts.seconds, ts.milliseconds), (0, 59, 59, 999));
+    }
+
+    #[test]
+    fn test_csv_roundtrip() {
+        let entries = vec![cue(1, 1500, 3000, "Hello, \"world\"\nsecond line")];
+        let path = std::env::temp_dir().join("srt_parser_csv_roundtrip.csv");
+        export_to_csv(path.to_str().unwrap(), &entries).unwrap();
+        let parsed = read_csv_file(path.to_str().unwrap()).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].text, entries[0].text);
+        assert_eq!(parsed[0].start_time.to_millis(), 1500);
+
+        let err = parse_csv("id,start,end,text\n1,00:00:01,000,00:00:02,000,Hi\n").unwrap_err();
+        assert!(err.starts_with("line 2:"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_vtt() {
+        let content = "WEBVTT\nKind: captions\n\nNOTE a comment\n\nSTYLE\n::cue { color: white }\n\ncue-1\n00:00:01.000 --> 00:00:02.500 align:start position:50%\n<c.yellow>Hello</c> <i>there</i>\n\n01:02.250 --> 01:03.000\nShort form\n";
+        let entries = parse_vtt(content).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, 1);
+        assert_eq!(entries[0].end_time.milliseconds, 500);
+        assert_eq!(entries[0].text, "Hello <i>there</i>");
+        assert_eq!(entries[1].id, 2);
+        assert_eq!(entries[1].start_time.to_millis(), 62_250);
+    }
+
+    #[test]
+    fn test_timestamp_to_lrc_string() {
+        let ts = TimeStamp::parse("01:02:03,456").unwrap();
+        assert_eq!(ts.to_lrc_string(), "62:03.45");
+    }
+
+    #[test]
+    fn test_timestamp_to_cue_string() {
+        let ts = TimeStamp::parse("01:02:03,500").unwrap();
+        // 62 minutes, 3 seconds, 500ms = 37.5 frames -> 37
+        assert_eq!(ts.to_cue_string(), "62:03:37");
+    }
+
+    #[test]
+    fn test_detect_subtitle_language() {
+        let make = |text: &str| vec![cue(1, 1000, 2000, text)];
+        assert_eq!(detect_subtitle_language(&make("这是一个测试字幕")), "zh");
+        assert_eq!(detect_subtitle_language(&make("我哋今日去咗边度")), "yue");
+        assert_eq!(detect_subtitle_language(&make("これはテストです")), "ja");
+        assert_eq!(detect_subtitle_language(&make("이것은 테스트입니다")), "ko");
+        assert_eq!(detect_subtitle_language(&make("This is the first subtitle")), "en");
+        assert_eq!(detect_subtitle_language(&make("...")), "auto");
+    }
+
+    #[test]
+    fn test_ass_styling_roundtrip() {
+        let content = "[Events]\n\
+            Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n\
+            Dialogue: 0,0:00:01.50,0:00:03.00,Default,,0,0,0,,{\\i1}Hello{\\i0}, {\\b1}world{\\b0}\\Nline two\n";
+        let entries = parse_ass(content).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].start_time.to_string(), "00:00:01,500");
+        assert_eq!(entries[0].text, "<i>Hello</i>, <b>world</b>\nline two");
+        assert_eq!(srt_text_to_ass(&entries[0].text), "{\\i1}Hello{\\i0}, {\\b1}world{\\b0}\\Nline two");
+    }
+
+    #[test]
+    fn test_validate_subtitles() {
+        let entries = vec![cue(1, 0, 2000, ""), cue(2, 1500, 3000, ""), cue(3, 3000, 3000, ""), cue(4, 1000, 500, "")];
+        let kinds: Vec<(usize, String)> = validate_subtitles(&entries).into_iter().map(|i| (i.index, i.kind)).collect();
+        assert_eq!(kinds, vec![
+            (1, "overlap".to_string()),
+            (2, "zero_duration".to_string()),
+            (3, "out_of_order".to_string()),
+            (3, "reversed_timing".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_close_gaps() {
+        let entries = vec![cue(1, 0, 1000, ""), cue(2, 1200, 2000, ""), cue(3, 3000, 4000, ""), cue(4, 3900, 5000, "")];
+        let result = close_gaps(&entries, 300);
+        assert_eq!(result[0].end_time.to_millis(), 1200);
+        assert_eq!(result[1].end_time.to_millis(), 2000); // gap of 1000ms kept
+        assert_eq!(result[2].end_time.to_millis(), 4000); // overlap untouched
+    }
+
+    #[test]
+    fn test_enforce_min_duration() {
+        let entries = vec![cue(1, 0, 300, ""), cue(2, 800, 900, ""), cue(3, 3000, 5000, "")];
+        let result = enforce_min_duration(&entries, 1000).unwrap();
+        assert_eq!(result[0].end_time.to_millis(), 800); // clamped at the next cue
+        assert_eq!(result[1].end_time.to_millis(), 1800);
+        assert_eq!(result[2].end_time.to_millis(), 5000);
+        assert!(enforce_min_duration(&[cue(1, 500, 600, ""), cue(2, 0, 100, "")], 1000).is_err());
+    }
+
+    #[test]
+    fn test_map_text_to_timing() {
+        let existing = vec![cue(1, 0, 2000, "old one"), cue(2, 2500, 4000, "old two"), cue(3, 6000, 7000, "old three")];
+        let transcribed = vec![
+            cue(1, 100, 1200, "new"),
+            cue(2, 1100, 2600, "one"),
+            cue(3, 2700, 4100, "new two"),
+            cue(4, 4300, 4500, "tail"),
+        ];
+        let result = map_text_to_timing(&existing, &transcribed);
+        assert_eq!(result[0].text, "new one");
+        assert_eq!(result[1].text, "new two tail"); // tail overlaps nothing, nearest cue wins
+        assert_eq!(result[2].text, "old three");
+        assert_eq!(result[1].start_time.to_millis(), 2500);
+        assert_eq!(result[1].end_time.to_millis(), 4000);
+    }
+
+    #[test]
+    fn test_merge_subtitles() {
+        let entries = vec![
+            cue(1, 0, 1000, "Hello"),
+            cue(2, 1100, 2000, "world"),
+            cue(3, 5000, 6000, "你好"),
+            cue(4, 6000, 7000, "世界"),
+        ];
+        let merged = merge_subtitles(&entries, 200, 20).unwrap();
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].text, "Hello world");
+        assert_eq!(merged[0].end_time.to_millis(), 2000);
+        assert_eq!(merged[1].id, 2);
+        assert_eq!(merged[1].text, "你好世界");
+        assert_eq!(merge_subtitles(&entries, 200, 8).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_split_subtitles() {
+        let entries = vec![
+            cue(7, 1000, 5000, "The quick brown fox jumps over the lazy dog"),
+            cue(7, 5000, 7000, "今天天气很好我们去公园散步吧"),
+        ];
+        let split = split_subtitles(&entries, 16, 1500).unwrap();
+        for (i, entry) in split.iter().enumerate() {
+            assert_eq!(entry.id, i as u32 + 1);
+            assert!(entry.text.chars().count() <= 16, "{}", entry.text);
+            assert!(entry.end_time.to_millis() - entry.start_time.to_millis() <= 1500);
+        }
+        assert!(split.windows(2).all(|w| w[0].end_time.to_millis() <= w[1].start_time.to_millis()));
+        assert_eq!(split[0].text, "The quick");
+        let cjk: String = split.iter().filter(|e| !e.text.is_ascii()).map(|e| e.text.as_str()).collect();
+        assert_eq!(cjk, "今天天气很好我们去公园散步吧");
+    }
+
+    #[test]
+    fn test_compute_reading_speed() {
+        let entries = vec![cue(1, 0, 2000, "<i>你好 world</i>")];
+        let info = &compute_reading_speed(&entries, Some(3.0))[0];
+        assert_eq!(info.chars, 7);
+        assert_eq!(info.cps, 3.5);
+        assert!(info.too_fast);
+    }
+
+    #[test]
+    fn test_apply_dictionary_whole_word() {
+        let dictionary = vec![
+            DictionaryEntry { wrong: "open ai".to_string(), right: "OpenAI".to_string() },
+            DictionaryEntry { wrong: "ai".to_string(), right: "AI".to_string() },
+            DictionaryEntry { wrong: "语音识别".to_string(), right: "ASR".to_string() },
+        ];
+        assert_eq!(apply_dictionary_to_text("用open ai做语音识别", &dictionary), "用OpenAI做ASR");
+        assert_eq!(apply_dictionary_to_text("ai said: fair, ai", &dictionary), "AI said: fair, AI");
+        assert_eq!(replace_whole_word("我的ai助手", "ai", "AI"), "我的AI助手");
+    }
+
+    #[test]
+    fn test_replace_in_subtitles_literal() {
+        let entries = vec![cue(1, 0, 1000, "color and color ($1)")];
+        let result = replace_in_subtitles(&entries, "color", "colour", false, true).unwrap();
+        assert_eq!(result.count, 2);
+        assert_eq!(result.entries[0].text, "colour and colour ($1)");
+        assert!(replace_in_subtitles(&entries, "", "x", false, true).is_err());
+    }
+
+    #[test]
+    fn test_fcpxml_colour() {
+        assert_eq!(fcpxml_colour("#FF0000").unwrap(), "1 0 0 1");
+        assert_eq!(fcpxml_colour("1 1 1 0.5").unwrap(), "1 1 1 0.5");
+        assert!(fcpxml_colour("2 0 0 1").is_err());
+    }
+
+    #[test]
+    fn test_ass_colour() {
+        assert_eq!(ass_colour("#FF8800").unwrap(), "&H000088FF");
+        assert_eq!(ass_colour("&HFFFFFF").unwrap(), "&H00FFFFFF");
+        assert!(ass_colour("red").is_err());
+    }
+
+    #[test]
+    fn test_subtitles_from_chapters() {
+        let text = "00:00 Intro\n1:30 - Setup\nnot a marker\n1:02:05 Outro";
+        let entries = subtitles_from_chapters(text, 3_800_000);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].text, "Intro");
+        assert_eq!(entries[0].end_time.to_string(), "00:01:30,000");
+        assert_eq!(entries[1].text, "Setup");
+        assert_eq!(entries[2].start_time.to_string(), "01:02:05,000");
+        assert_eq!(entries[2].end_time.to_string(), "01:03:20,000");
+    }
+
+    #[test]
+    fn test_normalize_punctuation_text() {
+        let unicode = PunctuationStyleOptions { ellipsis: "unicode".to_string(), dash: "em".to_string() };
+        let ascii = PunctuationStyleOptions { ellipsis: "ascii".to_string(), dash: "ascii".to_string() };
+
+        assert_eq!(normalize_punctuation_text("Wait... what…? x--y", &unicode), "Wait… what…? x—y");
+        assert_eq!(normalize_punctuation_text("Mixed ...… and --—", &unicode), "Mixed … and —");
+        assert_eq!(normalize_punctuation_text("然后……好吧——", &unicode), "然后……好吧——");
+        assert_eq!(normalize_punctuation_text("Wait… e.g. x—y well-known", &ascii), "Wait... e.g. x--y well-known");
+    }
+
+    #[test]
+    fn test_add_cjk_spaces_to_text() {
+        assert_eq!(add_cjk_spaces_to_text("这是AI技术2024年"), "这是 AI 技术 2024 年");
+        assert_eq!(add_cjk_spaces_to_text("用 iPhone 15拍的"), "用 iPhone 15 拍的");
+        assert_eq!(add_cjk_spaces_to_text("第1行\nLine2行"), "第 1 行\nLine2 行");
+        assert_eq!(add_cjk_spaces_to_text("你好，world！"), "你好，world！");
+        assert_eq!(add_cjk_spaces_to_text("Hello world"), "Hello world");
+        // Korean and Japanese kana are not spaced
+        assert_eq!(add_cjk_spaces_to_text("사과 3개, 2024년"), "사과 3개, 2024년");
+        assert_eq!(add_cjk_spaces_to_text("iPhoneを3つ"), "iPhoneを3つ");
+    }
+
+    #[test]
+    fn test_is_empty_content() {
+        assert!(is_empty_content("。"));
+        assert!(is_empty_content("..."));
+        assert!(is_empty_content("　\n "));
+        assert!(is_empty_content("<i>……！</i>"));
+        assert!(!is_empty_content("好。"));
+        assert!(!is_empty_content("OK"));
+    }
+
+    #[test]
+    fn test_split_into_reels() {
+        let entries = vec![cue(1, 1_000, 2_000, "line 1"), cue(2, 9_000, 11_000, "line 2"), cue(3, 12_000, 13_000, "line 3")];
+
+        let reels = split_into_reels(&entries, &[10_000]);
+        assert_eq!(reels.len(), 2);
+        assert_eq!(reels[0].len(), 2);
+        // Cue crossing the boundary is cut at the end of its reel
+        assert_eq!(reels[0][1].end_time.to_millis(), 10_000);
+        // Second reel is rebased to zero and renumbered
+        assert_eq!(reels[1][0].id, 1);
+        assert_eq!(reels[1][0].start_time.to_millis(), 2_000);
+        assert_eq!(reels[1][0].end_time.to_millis(), 3_000);
+    }
+
+    #[test]
+    fn test_ensemble_merge() {
+        let results = vec![
+            vec![cue(1, 0, 1_000, "Hello world"), cue(1, 2_000, 3_000, "foo")],
+            vec![cue(1, 100, 1_200, "hello, world!"), cue(1, 2_100, 2_900, "bar")],
+            vec![cue(1, 50, 900, "Yellow world")],
+        ];
+
+        let merged = ensemble_merge(&results, &EnsembleStrategy { mode: "majority".to_string(), preferred: 1 });
+        assert_eq!(merged.len(), 2);
+        // Two of three engines agree; the preferred engine's wording is kept
+        assert_eq!(merged[0].text, "hello, world!");
+        assert_eq!(merged[0].start_time.to_millis(), 0);
+        assert_eq!(merged[0].end_time.to_millis(), 1_200);
+        // 1:1 tie goes to the preferred engine
+        assert_eq!(merged[1].text, "bar");
+
+        let merged = ensemble_merge(&results, &EnsembleStrategy { mode: "preferred".to_string(), preferred: 2 });
+        assert_eq!(merged[0].text, "Yellow world");
+        // Preferred engine has no cue here: fall back to majority
+        assert_eq!(merged[1].text, "foo");
+
+        // A chain of cues each overlapping the next must not collapse into one group
+        let chain = vec![
+            vec![cue(1, 0, 1_000, "a"), cue(1, 1_000, 2_000, "b"), cue(1, 2_000, 3_000, "c")],
+            vec![cue(1, 900, 1_900, "a"), cue(1, 1_800, 2_800, "b")],
+        ];
+        let merged = ensemble_merge(&chain, &EnsembleStrategy { mode: "majority".to_string(), preferred: 0 });
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0].end_time.to_millis(), 1_900);
+        assert_eq!(merged[1].start_time.to_millis(), 1_000);
+        assert_eq!(merged[2].text, "c");
+    }
+
     #[test]
     fn test_parse_srt() {
         let content = r#"1
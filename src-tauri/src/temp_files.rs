@@ -0,0 +1,20 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static NEXT_TEMP_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 生成本次调用独占的临时路径（进程 id + 递增序号），并发任务之间不会冲突
+/// suffix 含扩展名，如 ".json"；目录传空字符串
+pub fn unique_temp_path(prefix: &str, suffix: &str) -> PathBuf {
+    let id = NEXT_TEMP_ID.fetch_add(1, Ordering::Relaxed);
+    std::env::temp_dir().join(format!("{}_{}_{}{}", prefix, std::process::id(), id, suffix))
+}
+
+/// 临时文件凭证，drop 时删除文件（包括出错提前返回的情况）
+pub struct TempFileGuard(pub PathBuf);
+
+impl Drop for TempFileGuard {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
@@ -1,12 +1,18 @@
+use crate::srt_parser::SubtitleEntry;
+use serde::{Deserialize, Serialize};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
 use std::fs::File;
-use std::path::Path;
-use std::time::Instant;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
 use symphonia::core::audio::{AudioBufferRef, Signal};
 use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
-use symphonia::core::formats::FormatOptions;
+use symphonia::core::formats::{FormatOptions, SeekMode, SeekTo};
 use symphonia::core::io::MediaSourceStream;
 use symphonia::core::meta::MetadataOptions;
 use symphonia::core::probe::Hint;
+use symphonia::core::units::Time;
 
 /// Callback type for progress updates (progress: 0.0 to 1.0)
 pub type ProgressCallback = Box<dyn Fn(f32) + Send>;
@@ -35,14 +41,157 @@ pub fn generate_waveform(file_path: &str, target_samples: usize) -> Result<Vec<f
 }
 
 /// Generate professional min/max waveform data with progress callback
+/// Returns (interleaved min/max peaks, audio duration in milliseconds)
+/// Results are cached on disk; a cache hit reports 100% progress once and returns immediately
 pub fn generate_waveform_with_progress(
     file_path: &str,
     target_samples: usize,
     progress_callback: Option<ProgressCallback>,
-) -> Result<Vec<f32>, String> {
+) -> Result<(Vec<f32>, u64), String> {
+    let cache_path = waveform_cache_path(file_path, target_samples);
+
+    if let Some((peaks, duration_ms, file_peak)) = cache_path.as_deref().and_then(read_waveform_cache) {
+        remember_file_peak(file_path, file_peak);
+        if let Some(callback) = progress_callback.as_ref() {
+            callback(1.0);
+        }
+        return Ok((peaks, duration_ms));
+    }
+
     let data = generate_waveform_minmax_with_progress(file_path, target_samples, progress_callback)?;
+    let duration_ms = frames_to_ms((data.duration * data.sample_rate as f64).round() as u64, data.sample_rate);
+
+    if let Some(cache_path) = cache_path {
+        let file_peak = known_file_peak(file_path).unwrap_or(1.0);
+        if let Err(e) = write_waveform_cache(&cache_path, &data.peaks, duration_ms, file_peak) {
+            eprintln!("Failed to write waveform cache: {}", e);
+        }
+    }
+
     // Return interleaved min/max data
-    Ok(data.peaks)
+    Ok((data.peaks, duration_ms))
+}
+
+/// Cache file format version, stored as the first byte; bump it whenever the peak
+/// computation or the file layout changes so stale files are ignored
+const WAVEFORM_CACHE_VERSION: u8 = 2;
+
+/// Total size the waveform cache directory may grow to before the oldest files are evicted
+const WAVEFORM_CACHE_MAX_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Cache files not used for this long are deleted
+const WAVEFORM_CACHE_MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// 64-bit FNV-1a; unlike DefaultHasher its output is stable across Rust releases,
+/// so cache file names survive app upgrades
+fn fnv1a_64(parts: &[&[u8]]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for part in parts {
+        for &byte in *part {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+fn waveform_cache_dir() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join(crate::app_config::APP_NAME).join("waveforms"))
+}
+
+/// Waveform cache file for a given audio file and resolution
+/// The key covers the absolute path, modification time and target_samples, so editing
+/// the audio file invalidates its cached waveform
+fn waveform_cache_path(file_path: &str, target_samples: usize) -> Option<PathBuf> {
+    let absolute = std::fs::canonicalize(file_path).ok()?;
+    let mtime = std::fs::metadata(&absolute).and_then(|m| m.modified()).ok()?;
+    let mtime_ns = mtime.duration_since(std::time::UNIX_EPOCH).ok()?.as_nanos();
+
+    let hash = fnv1a_64(&[
+        absolute.to_string_lossy().as_bytes(),
+        &mtime_ns.to_le_bytes(),
+        &(target_samples as u64).to_le_bytes(),
+    ]);
+
+    Some(waveform_cache_dir()?.join(format!("{:016x}.wf", hash)))
+}
+
+/// Size of the cache header: version byte, u64 duration in ms, f32 whole-file peak
+const WAVEFORM_CACHE_HEADER: usize = 13;
+
+/// Read a cache file: the header (all little-endian) followed by raw little-endian f32 peaks
+/// Returns (peaks, duration_ms, whole-file peak)
+/// A hit refreshes the file's modification time so eviction drops the least recently used files
+fn read_waveform_cache(path: &Path) -> Option<(Vec<f32>, u64, f32)> {
+    let bytes = std::fs::read(path).ok()?;
+    if bytes.len() <= WAVEFORM_CACHE_HEADER
+        || (bytes.len() - WAVEFORM_CACHE_HEADER) % 4 != 0
+        || bytes[0] != WAVEFORM_CACHE_VERSION
+    {
+        return None;
+    }
+    let duration_ms = u64::from_le_bytes(bytes[1..9].try_into().ok()?);
+    let file_peak = f32::from_le_bytes(bytes[9..WAVEFORM_CACHE_HEADER].try_into().ok()?);
+    let peaks = bytes[WAVEFORM_CACHE_HEADER..]
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+
+    if let Ok(file) = File::options().append(true).open(path) {
+        let _ = file.set_modified(SystemTime::now());
+    }
+    Some((peaks, duration_ms, file_peak))
+}
+
+fn write_waveform_cache(path: &Path, peaks: &[f32], duration_ms: u64, file_peak: f32) -> Result<(), String> {
+    let dir = path.parent().ok_or("Invalid waveform cache path")?;
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let mut bytes = vec![WAVEFORM_CACHE_VERSION];
+    bytes.extend_from_slice(&duration_ms.to_le_bytes());
+    bytes.extend_from_slice(&file_peak.to_le_bytes());
+    bytes.extend(peaks.iter().flat_map(|v| v.to_le_bytes()));
+    std::fs::write(path, bytes).map_err(|e| e.to_string())?;
+
+    prune_waveform_cache(dir, path);
+    Ok(())
+}
+
+/// Delete cache files older than WAVEFORM_CACHE_MAX_AGE, then the least recently used ones
+/// until the directory fits in WAVEFORM_CACHE_MAX_BYTES; `keep` (the file just written) is never removed
+fn prune_waveform_cache(dir: &Path, keep: &Path) {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let now = SystemTime::now();
+
+    let mut files: Vec<(PathBuf, SystemTime, u64)> = Vec::new();
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("wf") {
+            continue;
+        }
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        let modified = meta.modified().unwrap_or(now);
+        let expired = now.duration_since(modified).map(|age| age > WAVEFORM_CACHE_MAX_AGE).unwrap_or(false);
+        if expired && path != keep {
+            let _ = std::fs::remove_file(&path);
+            continue;
+        }
+        files.push((path, modified, meta.len()));
+    }
+
+    let mut total: u64 = files.iter().map(|(_, _, len)| len).sum();
+    files.sort_by_key(|(_, modified, _)| *modified);
+    for (path, _, len) in files {
+        if total <= WAVEFORM_CACHE_MAX_BYTES {
+            break;
+        }
+        if path != keep && std::fs::remove_file(&path).is_ok() {
+            total -= len;
+        }
+    }
 }
 
 /// Generate min/max waveform data - the core implementation
@@ -51,6 +200,114 @@ pub fn generate_waveform_minmax_with_progress(
     target_samples: usize,
     progress_callback: Option<ProgressCallback>,
 ) -> Result<WaveformData, String> {
+    let (all_samples, sample_rate) = decode_samples_with_progress(file_path, progress_callback.as_ref())?;
+
+    // Report progress: 90% - starting downsample
+    if let Some(callback) = progress_callback.as_ref() {
+        callback(0.9);
+    }
+
+    // Calculate duration
+    let duration = all_samples.len() as f64 / sample_rate as f64;
+    remember_file_peak(file_path, sample_peak(&all_samples));
+
+    // Generate min/max peaks
+    let peaks = generate_minmax_peaks(&all_samples, target_samples);
+
+    // Report progress: 100% - complete
+    if let Some(callback) = progress_callback.as_ref() {
+        callback(1.0);
+    }
+
+    Ok(WaveformData {
+        peaks,
+        length: target_samples,
+        sample_rate,
+        duration,
+    })
+}
+
+/// Decode an audio file into mono f32 samples
+/// Returns (samples, sample_rate)
+pub fn decode_audio_mono(file_path: &str) -> Result<(Vec<f32>, u32), String> {
+    decode_samples_with_progress(file_path, None)
+}
+
+/// Longest decode kept in memory (64M samples, ~256 MB); longer files are decoded on every call
+const MAX_CACHED_SAMPLES: usize = 64 * 1024 * 1024;
+
+/// A cached decode that hasn't been used for this long is released
+const DECODED_AUDIO_IDLE: Duration = Duration::from_secs(300);
+
+/// Decoded audio kept for repeated chunk extraction
+struct DecodedAudio {
+    path: String,
+    mtime: Option<SystemTime>,
+    samples: Arc<Vec<f32>>,
+    sample_rate: u32,
+    last_used: Instant,
+}
+
+// Last decoded file, so single-cue corrections don't re-decode the whole file every time
+static DECODED_AUDIO_CACHE: Lazy<Mutex<Option<DecodedAudio>>> = Lazy::new(|| {
+    // Background reaper: drop the decode once it has been idle for DECODED_AUDIO_IDLE
+    std::thread::spawn(|| loop {
+        std::thread::sleep(Duration::from_secs(60));
+        if let Ok(mut cache) = DECODED_AUDIO_CACHE.lock() {
+            if cache.as_ref().is_some_and(|c| c.last_used.elapsed() >= DECODED_AUDIO_IDLE) {
+                *cache = None;
+            }
+        }
+    });
+    Mutex::new(None)
+});
+
+/// Decode an audio file into mono samples, reusing the cached result if the file hasn't changed
+pub fn decode_audio_mono_cached(file_path: &str) -> Result<(Arc<Vec<f32>>, u32), String> {
+    let mtime = std::fs::metadata(file_path).and_then(|m| m.modified()).ok();
+
+    if let Ok(mut cache) = DECODED_AUDIO_CACHE.lock() {
+        if let Some(cached) = cache.as_mut() {
+            if cached.path == file_path && cached.mtime == mtime {
+                cached.last_used = Instant::now();
+                return Ok((cached.samples.clone(), cached.sample_rate));
+            }
+        }
+    }
+
+    let (samples, sample_rate) = decode_audio_mono(file_path)?;
+    let samples = Arc::new(samples);
+
+    if let Ok(mut cache) = DECODED_AUDIO_CACHE.lock() {
+        // Replacing the entry also releases the previous file's samples
+        *cache = (samples.len() <= MAX_CACHED_SAMPLES).then(|| DecodedAudio {
+            path: file_path.to_string(),
+            mtime,
+            samples: samples.clone(),
+            sample_rate,
+            last_used: Instant::now(),
+        });
+    }
+
+    Ok((samples, sample_rate))
+}
+
+/// Decode all packets of the first audio track into mono samples, reporting progress in the 0.0 - 0.9 range
+fn decode_samples_with_progress(
+    file_path: &str,
+    progress_callback: Option<&ProgressCallback>,
+) -> Result<(Vec<f32>, u32), String> {
+    decode_with_extractor(file_path, progress_callback, extract_samples, 1)
+}
+
+/// Decode all packets of the first audio track using the given sample extractor
+/// values_per_frame: number of values the extractor produces per audio frame (1 = mono, 2 = interleaved L/R)
+fn decode_with_extractor(
+    file_path: &str,
+    progress_callback: Option<&ProgressCallback>,
+    extractor: fn(&AudioBufferRef) -> Vec<f32>,
+    values_per_frame: usize,
+) -> Result<(Vec<f32>, u32), String> {
     let path = Path::new(file_path);
 
     // Open the media source
@@ -120,7 +377,7 @@ pub fn generate_waveform_minmax_with_progress(
     };
 
     // Pre-allocate with estimated capacity to reduce reallocations
-    let mut all_samples: Vec<f32> = Vec::with_capacity(estimated_total_samples);
+    let mut all_samples: Vec<f32> = Vec::with_capacity(estimated_total_samples * values_per_frame);
     let mut decoded_frames: u64 = 0;
     let mut last_progress_time = Instant::now();
     let mut last_reported_progress = 0.0f32;
@@ -153,14 +410,14 @@ pub fn generate_waveform_minmax_with_progress(
         // Decode the packet
         match decoder.decode(&packet) {
             Ok(decoded) => {
-                let samples = extract_samples(&decoded);
+                let samples = extractor(&decoded);
                 let num_samples = samples.len();
                 all_samples.extend(samples);
-                decoded_frames += num_samples as u64;
+                decoded_frames += (num_samples / values_per_frame) as u64;
 
                 // Update progress based on packet count interval AND time interval
                 // This ensures UI has time to render between updates
-                if let Some(ref callback) = progress_callback {
+                if let Some(callback) = progress_callback {
                     let now = Instant::now();
                     let time_since_last = now.duration_since(last_progress_time).as_millis();
                     
@@ -191,28 +448,7 @@ pub fn generate_waveform_minmax_with_progress(
         return Err("No audio samples extracted".to_string());
     }
 
-    // Report progress: 90% - starting downsample
-    if let Some(ref callback) = progress_callback {
-        callback(0.9);
-    }
-
-    // Calculate duration
-    let duration = all_samples.len() as f64 / sample_rate as f64;
-
-    // Generate min/max peaks
-    let peaks = generate_minmax_peaks(&all_samples, target_samples);
-
-    // Report progress: 100% - complete
-    if let Some(ref callback) = progress_callback {
-        callback(1.0);
-    }
-
-    Ok(WaveformData {
-        peaks,
-        length: target_samples,
-        sample_rate,
-        duration,
-    })
+    Ok((all_samples, sample_rate))
 }
 
 /// Extract samples from an audio buffer and convert to mono f32
@@ -282,10 +518,328 @@ fn extract_samples(decoded: &AudioBufferRef) -> Vec<f32> {
     }
 }
 
+/// Convert one channel of an audio buffer to f32 samples in [-1, 1]
+fn channel_to_f32(decoded: &AudioBufferRef, channel: usize) -> Vec<f32> {
+    match decoded {
+        AudioBufferRef::F32(buf) => buf.chan(channel).to_vec(),
+        AudioBufferRef::S32(buf) => {
+            let scale = 1.0 / i32::MAX as f32;
+            buf.chan(channel).iter().map(|&s| s as f32 * scale).collect()
+        }
+        AudioBufferRef::S16(buf) => {
+            let scale = 1.0 / i16::MAX as f32;
+            buf.chan(channel).iter().map(|&s| s as f32 * scale).collect()
+        }
+        AudioBufferRef::U8(buf) => buf.chan(channel).iter().map(|&s| (s as f32 - 128.0) / 128.0).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Extract samples as interleaved [l0, r0, l1, r1, ...]; mono sources are duplicated to both sides
+fn extract_stereo_samples(decoded: &AudioBufferRef) -> Vec<f32> {
+    let channels = decoded.spec().channels.count();
+    let left = channel_to_f32(decoded, 0);
+    let right = if channels >= 2 { channel_to_f32(decoded, 1) } else { left.clone() };
+    left.iter().zip(right.iter()).flat_map(|(&l, &r)| [l, r]).collect()
+}
+
+/// Per-channel min/max waveform data
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StereoWaveform {
+    /// Interleaved min/max pairs for the left channel
+    pub left: Vec<f32>,
+    /// Interleaved min/max pairs for the right channel
+    pub right: Vec<f32>,
+}
+
+/// Generate separate left/right min/max waveforms (mono sources produce identical lanes)
+/// Both lanes share one normalization scale so their relative levels are preserved
+pub fn generate_stereo_waveform_with_progress(
+    file_path: &str,
+    target_samples: usize,
+    progress_callback: Option<ProgressCallback>,
+) -> Result<StereoWaveform, String> {
+    let (interleaved, _) = decode_with_extractor(file_path, progress_callback.as_ref(), extract_stereo_samples, 2)?;
+
+    if let Some(callback) = progress_callback.as_ref() {
+        callback(0.9);
+    }
+
+    let left: Vec<f32> = interleaved.iter().step_by(2).copied().collect();
+    let right: Vec<f32> = interleaved.iter().skip(1).step_by(2).copied().collect();
+
+    let mut peaks = minmax_peaks_raw(&left, target_samples);
+    let left_len = peaks.len();
+    peaks.extend(minmax_peaks_raw(&right, target_samples));
+    let mut peaks = normalize_peaks(peaks);
+    let right_peaks = peaks.split_off(left_len);
+
+    if let Some(callback) = progress_callback.as_ref() {
+        callback(1.0);
+    }
+
+    Ok(StereoWaveform { left: peaks, right: right_peaks })
+}
+
+/// Milliseconds covered by `frames` audio frames, rounded to the nearest millisecond
+/// Every duration reported to the frontend goes through this, so a region ending at the
+/// reported duration is always accepted
+fn frames_to_ms(frames: u64, sample_rate: u32) -> u64 {
+    (frames * 1000 + sample_rate as u64 / 2) / sample_rate as u64
+}
+
+/// Largest absolute sample value, clamped to 1.0 like the min/max peaks
+fn sample_peak(samples: &[f32]) -> f32 {
+    samples.iter().fold(0.0f32, |m, s| m.max(s.abs())).min(1.0)
+}
+
+/// Most files whose whole-file peak is remembered; the table is cleared when it fills up
+const MAX_FILE_PEAKS: usize = 32;
+
+// Whole-file peak of files whose overview waveform was generated, keyed by path and mtime,
+// so zoomed regions can be scaled like the overview without decoding the whole file
+static FILE_PEAKS: Lazy<Mutex<HashMap<String, (Option<SystemTime>, f32)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn remember_file_peak(file_path: &str, peak: f32) {
+    let mtime = std::fs::metadata(file_path).and_then(|m| m.modified()).ok();
+    if let Ok(mut peaks) = FILE_PEAKS.lock() {
+        if peaks.len() >= MAX_FILE_PEAKS && !peaks.contains_key(file_path) {
+            peaks.clear();
+        }
+        peaks.insert(file_path.to_string(), (mtime, peak));
+    }
+}
+
+fn known_file_peak(file_path: &str) -> Option<f32> {
+    let mtime = std::fs::metadata(file_path).and_then(|m| m.modified()).ok();
+    let peaks = FILE_PEAKS.lock().ok()?;
+    peaks.get(file_path).filter(|(m, _)| *m == mtime).map(|(_, peak)| *peak)
+}
+
+/// Largest number of samples reserved up front for a region decode (~10 minutes at 48 kHz)
+const MAX_REGION_PREALLOC: usize = 48_000 * 600;
+
+/// Decode the mono samples of [start_ms, end_ms) by seeking to start_ms rather than decoding the whole file
+/// Formats that can't seek are decoded from the beginning, skipping everything before the region.
+/// Returns (samples, sample_rate, stream_end_ms), where stream_end_ms is set when the audio ended before end_ms
+fn decode_region_mono(file_path: &str, start_ms: u64, end_ms: u64) -> Result<(Vec<f32>, u32, Option<u64>), String> {
+    let path = Path::new(file_path);
+    let file = File::open(path).map_err(|e| format!("Failed to open audio file: {}", e))?;
+    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(ext_str) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext_str);
+    }
+
+    let probed = symphonia::default::get_probe()
+        .format(&hint, mss, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(|e| format!("Failed to probe audio file: {}", e))?;
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| "No audio track found".to_string())?;
+    let track_id = track.id;
+    let sample_rate = track.codec_params.sample_rate.unwrap_or(44100);
+    let time_base = track.codec_params.time_base;
+    let total_frames = track.codec_params.n_frames;
+
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| format!("Failed to create decoder: {}", e))?;
+
+    // Region bounds come from the frontend unchecked; reject values that overflow as frame positions
+    let ms_to_frame = |ms: u64| {
+        ms.checked_mul(sample_rate as u64)
+            .map(|v| v / 1000)
+            .ok_or_else(|| format!("Region bound {}ms is out of range", ms))
+    };
+    let start_frame = ms_to_frame(start_ms)?;
+    let end_frame = ms_to_frame(end_ms)?;
+
+    let seek_to = SeekTo::Time {
+        time: Time::new(start_ms / 1000, (start_ms % 1000) as f64 / 1000.0),
+        track_id: Some(track_id),
+    };
+    if format.seek(SeekMode::Accurate, seek_to).is_ok() {
+        decoder.reset();
+    }
+
+    // Packet timestamps are in the track's time base; convert them to frame positions
+    let ts_to_frame = |ts: u64| match time_base {
+        Some(tb) => {
+            let time = tb.calc_time(ts);
+            ((time.seconds as f64 + time.frac) * sample_rate as f64).round() as u64
+        }
+        None => ts,
+    };
+
+    // Reserve no more than the track holds (when known) and at most MAX_REGION_PREALLOC up front,
+    // so a bogus end_ms can't trigger a huge allocation
+    let expected_frames = total_frames
+        .map_or(end_frame, |total| end_frame.min(total))
+        .saturating_sub(start_frame);
+    let mut samples: Vec<f32> = Vec::with_capacity((expected_frames as usize).min(MAX_REGION_PREALLOC));
+    let mut position: u64 = 0;
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(_) => return Ok((samples, sample_rate, Some(frames_to_ms(position, sample_rate)))),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let packet_start = ts_to_frame(packet.ts());
+        if packet_start >= end_frame {
+            break;
+        }
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => extract_samples(&decoded),
+            Err(e) => {
+                eprintln!("Decode error: {}", e);
+                continue;
+            }
+        };
+        let packet_end = packet_start + decoded.len() as u64;
+        position = packet_end;
+        if packet_end <= start_frame {
+            continue;
+        }
+
+        let from = start_frame.saturating_sub(packet_start) as usize;
+        let to = (end_frame.min(packet_end) - packet_start) as usize;
+        samples.extend_from_slice(&decoded[from..to]);
+        if packet_end >= end_frame {
+            break;
+        }
+    }
+
+    Ok((samples, sample_rate, None))
+}
+
+/// Generate min/max waveform data for a time region at the requested resolution
+/// Only the region is decoded (or sliced from the cached decode when the file is already in memory).
+/// Values are scaled by the whole file's peak once the overview has been generated, so the zoomed
+/// view matches it; before that the region is scaled by its own peak.
+pub fn generate_waveform_region(
+    file_path: &str,
+    start_ms: u64,
+    end_ms: u64,
+    target_samples: usize,
+) -> Result<Vec<f32>, String> {
+    if start_ms >= end_ms {
+        return Err(format!("Invalid region: start {}ms must be before end {}ms", start_ms, end_ms));
+    }
+
+    let mtime = std::fs::metadata(file_path).and_then(|m| m.modified()).ok();
+    let cached = DECODED_AUDIO_CACHE.lock().ok().and_then(|cache| {
+        cache
+            .as_ref()
+            .filter(|c| c.path == file_path && c.mtime == mtime)
+            .map(|c| (c.samples.clone(), c.sample_rate))
+    });
+
+    let (region, sample_rate, stream_end_ms) = match cached {
+        Some((samples, sample_rate)) => {
+            let start = ms_to_sample_index(start_ms, sample_rate, samples.len());
+            let end = ms_to_sample_index(end_ms, sample_rate, samples.len());
+            let duration_ms = frames_to_ms(samples.len() as u64, sample_rate);
+            let stream_end_ms = (end_ms > duration_ms).then_some(duration_ms);
+            (samples[start..end].to_vec(), sample_rate, stream_end_ms)
+        }
+        None => decode_region_mono(file_path, start_ms, end_ms)?,
+    };
+
+    if let Some(duration_ms) = stream_end_ms {
+        if end_ms > duration_ms {
+            return Err(format!("Region end {}ms exceeds audio duration {}ms", end_ms, duration_ms));
+        }
+    }
+    if region.is_empty() {
+        return Err(format!("No audio samples in region {}ms-{}ms (sample rate {})", start_ms, end_ms, sample_rate));
+    }
+
+    let mut peaks = minmax_peaks_raw(&region, target_samples);
+    let max_abs = known_file_peak(file_path).unwrap_or_else(|| sample_peak(&region));
+    if max_abs >= 0.001 {
+        let scale = 1.0 / max_abs;
+        peaks.iter_mut().for_each(|v| *v *= scale);
+    }
+
+    Ok(peaks)
+}
+
+/// Peak and RMS envelopes, one value per bucket
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DetailedWaveform {
+    /// Max absolute sample per bucket
+    pub peak: Vec<f32>,
+    /// Root-mean-square per bucket
+    pub rms: Vec<f32>,
+}
+
+/// Compute peak and RMS for each bucket, both scaled by the loudest peak so they overlay correctly
+fn peak_rms_buckets(samples: &[f32], target_samples: usize) -> DetailedWaveform {
+    let buckets = target_samples.min(samples.len());
+    let mut peak = Vec::with_capacity(buckets);
+    let mut rms = Vec::with_capacity(buckets);
+
+    if buckets > 0 {
+        let chunk_size = samples.len() as f64 / buckets as f64;
+        for i in 0..buckets {
+            let start = (i as f64 * chunk_size) as usize;
+            let end = (((i + 1) as f64 * chunk_size) as usize).min(samples.len());
+            let chunk = &samples[start..end.max(start)];
+            peak.push(chunk.iter().fold(0.0f32, |m, s| m.max(s.abs())).min(1.0));
+            rms.push(compute_rms(chunk).min(1.0));
+        }
+    }
+
+    let max_peak = peak.iter().copied().fold(0.0f32, f32::max);
+    if max_peak >= 0.001 {
+        let scale = 1.0 / max_peak;
+        peak.iter_mut().chain(rms.iter_mut()).for_each(|v| *v *= scale);
+    }
+
+    DetailedWaveform { peak, rms }
+}
+
+/// Generate peak and RMS envelopes from an audio file, reusing the mono decode path
+pub fn generate_detailed_waveform_with_progress(
+    file_path: &str,
+    target_samples: usize,
+    progress_callback: Option<ProgressCallback>,
+) -> Result<DetailedWaveform, String> {
+    let (samples, _) = decode_samples_with_progress(file_path, progress_callback.as_ref())?;
+
+    if let Some(callback) = progress_callback.as_ref() {
+        callback(0.9);
+    }
+
+    let data = peak_rms_buckets(&samples, target_samples);
+
+    if let Some(callback) = progress_callback.as_ref() {
+        callback(1.0);
+    }
+
+    Ok(data)
+}
+
 /// Generate min/max peaks for professional waveform display
 /// Returns interleaved [min0, max0, min1, max1, ...] array
 /// The output is normalized to use the full [-1, 1] range for better visualization
 fn generate_minmax_peaks(samples: &[f32], target_samples: usize) -> Vec<f32> {
+    // Normalize to use full range for better visualization
+    normalize_peaks(minmax_peaks_raw(samples, target_samples))
+}
+
+/// Min/max pairs per bucket, clamped to [-1, 1] but not normalized
+fn minmax_peaks_raw(samples: &[f32], target_samples: usize) -> Vec<f32> {
     if samples.is_empty() {
         return Vec::new();
     }
@@ -294,8 +848,7 @@ fn generate_minmax_peaks(samples: &[f32], target_samples: usize) -> Vec<f32> {
 
     // If we have fewer samples than target, each sample becomes its own min/max
     if total_samples <= target_samples {
-        let peaks: Vec<f32> = samples.iter().flat_map(|&s| [s, s]).collect();
-        return normalize_peaks(peaks);
+        return samples.iter().flat_map(|&s| [s, s]).collect();
     }
 
     let chunk_size = total_samples as f64 / target_samples as f64;
@@ -335,8 +888,7 @@ fn generate_minmax_peaks(samples: &[f32], target_samples: usize) -> Vec<f32> {
         peaks.push(max_val);
     }
 
-    // Normalize to use full range for better visualization
-    normalize_peaks(peaks)
+    peaks
 }
 
 /// Normalize peaks to use the full [-1, 1] range
@@ -368,6 +920,513 @@ fn normalize_peaks(mut peaks: Vec<f32>) -> Vec<f32> {
     peaks
 }
 
+/// Floor used for silent spans so the dBFS value stays finite (and JSON-serializable)
+const SILENCE_FLOOR_DB: f32 = -100.0;
+
+/// Convert a linear RMS value to dBFS, clamped to SILENCE_FLOOR_DB
+fn rms_to_db(rms: f32) -> f32 {
+    if rms <= 0.0 {
+        return SILENCE_FLOOR_DB;
+    }
+    (20.0 * rms.log10()).max(SILENCE_FLOOR_DB)
+}
+
+/// Root-mean-square of a sample slice
+fn compute_rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum_sq: f64 = samples.iter().map(|&s| (s as f64) * (s as f64)).sum();
+    (sum_sq / samples.len() as f64).sqrt() as f32
+}
+
+/// Convert a millisecond position to a sample index, clamped to the buffer length
+fn ms_to_sample_index(ms: u64, sample_rate: u32, total_samples: usize) -> usize {
+    ((ms.saturating_mul(sample_rate as u64) / 1000) as usize).min(total_samples)
+}
+
+/// Compute per-cue loudness (RMS in dBFS) over each subtitle's time span
+/// Returns (subtitle id, rms_db) pairs in the same order as the entries
+pub fn compute_cue_loudness(audio_path: &str, entries: &[SubtitleEntry]) -> Result<Vec<(u32, f32)>, String> {
+    let (samples, sample_rate) = decode_audio_mono(audio_path)?;
+
+    let result = entries
+        .iter()
+        .map(|entry| {
+            let start = ms_to_sample_index(entry.start_time.to_millis(), sample_rate, samples.len());
+            let end = ms_to_sample_index(entry.end_time.to_millis(), sample_rate, samples.len());
+            let rms = if start < end { compute_rms(&samples[start..end]) } else { 0.0 };
+            (entry.id, rms_to_db(rms))
+        })
+        .collect();
+
+    Ok(result)
+}
+
+/// Oversampling factor for inter-sample peak estimation
+const TRUE_PEAK_OVERSAMPLE: usize = 4;
+
+/// Peak magnitude including inter-sample peaks, estimated by Catmull-Rom
+/// interpolation at TRUE_PEAK_OVERSAMPLE points between each pair of samples
+fn true_peak_linear(samples: &[f32]) -> f32 {
+    let mut peak = samples.iter().fold(0.0f32, |m, &s| m.max(s.abs()));
+
+    for w in samples.windows(4) {
+        let (p0, p1, p2, p3) = (w[0], w[1], w[2], w[3]);
+        for k in 1..TRUE_PEAK_OVERSAMPLE {
+            let t = k as f32 / TRUE_PEAK_OVERSAMPLE as f32;
+            let v = 0.5
+                * (2.0 * p1
+                    + (p2 - p0) * t
+                    + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t * t
+                    + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t * t * t);
+            peak = peak.max(v.abs());
+        }
+    }
+
+    peak
+}
+
+/// Measure the true peak of an audio file in dBFS (0.0 = full scale)
+/// Measured per channel (left/right) and the louder one is reported: a mono mixdown would
+/// under-read hard-panned or out-of-phase material. The headroom for normalization is -result
+pub fn measure_true_peak(audio_path: &str) -> Result<f32, String> {
+    let (interleaved, _) = decode_with_extractor(audio_path, None, extract_stereo_samples, 2)?;
+    let left: Vec<f32> = interleaved.iter().step_by(2).copied().collect();
+    let right: Vec<f32> = interleaved.iter().skip(1).step_by(2).copied().collect();
+    Ok(rms_to_db(true_peak_linear(&left).max(true_peak_linear(&right))))
+}
+
+/// Analysis frame length for envelope-based speech detection
+const ENVELOPE_FRAME_MS: u64 = 20;
+
+/// Gaps in detected speech shorter than this are bridged (pauses between words)
+const SPEECH_MERGE_GAP_MS: u64 = 300;
+
+/// Uncovered speech spans shorter than this are not reported
+const MIN_UNCOVERED_SPAN_MS: u64 = 300;
+
+/// RMS level (dBFS) of consecutive fixed-length frames
+fn frame_levels_db(samples: &[f32], sample_rate: u32, frame_ms: u64) -> Vec<f32> {
+    let frame_len = ((sample_rate as u64 * frame_ms / 1000) as usize).max(1);
+    samples
+        .chunks(frame_len)
+        .map(|frame| rms_to_db(compute_rms(frame)))
+        .collect()
+}
+
+/// Mark frames above the threshold as speech, bridging short pauses between words
+fn detect_speech_frames(levels_db: &[f32], threshold_db: f32, frame_ms: u64) -> Vec<bool> {
+    let mut speech: Vec<bool> = levels_db.iter().map(|&db| db > threshold_db).collect();
+    let max_gap = (SPEECH_MERGE_GAP_MS / frame_ms) as usize;
+
+    let speech_indices: Vec<usize> = (0..speech.len()).filter(|&i| speech[i]).collect();
+    for pair in speech_indices.windows(2) {
+        let (prev, next) = (pair[0], pair[1]);
+        if next - prev > 1 && next - prev - 1 <= max_gap {
+            speech[prev + 1..next].iter_mut().for_each(|s| *s = true);
+        }
+    }
+
+    speech
+}
+
+/// Collect runs of consecutive `true` flags as (start, end) index ranges (end exclusive)
+fn collect_runs(flags: &[bool]) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    let mut run_start: Option<usize> = None;
+
+    for (i, &flag) in flags.iter().enumerate() {
+        match (flag, run_start) {
+            (true, None) => run_start = Some(i),
+            (false, Some(start)) => {
+                runs.push((start, i));
+                run_start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = run_start {
+        runs.push((start, flags.len()));
+    }
+
+    runs
+}
+
+/// Subtitle coverage of detected speech
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageReport {
+    /// Total detected speech time
+    pub speech_ms: u64,
+    /// Detected speech time that falls inside a subtitle cue
+    pub covered_ms: u64,
+    /// covered_ms / speech_ms as a percentage (100 when no speech is detected)
+    pub coverage_percent: f32,
+    /// Speech spans (start_ms, end_ms) without any subtitle
+    pub uncovered_spans: Vec<(u64, u64)>,
+}
+
+/// Measure how much of the detected speech is covered by subtitle cues
+/// threshold_db: frames louder than this level (dBFS, e.g. -40.0) count as speech
+pub fn compute_coverage(audio_path: &str, entries: &[SubtitleEntry], threshold_db: f32) -> Result<CoverageReport, String> {
+    let (samples, sample_rate) = decode_audio_mono(audio_path)?;
+    let levels = frame_levels_db(&samples, sample_rate, ENVELOPE_FRAME_MS);
+    let speech = detect_speech_frames(&levels, threshold_db, ENVELOPE_FRAME_MS);
+
+    // Mark frames covered by any cue
+    let mut covered = vec![false; speech.len()];
+    for entry in entries {
+        let start = (entry.start_time.to_millis() / ENVELOPE_FRAME_MS) as usize;
+        let end = (entry.end_time.to_millis().div_ceil(ENVELOPE_FRAME_MS) as usize).min(covered.len());
+        if start < end {
+            covered[start..end].iter_mut().for_each(|c| *c = true);
+        }
+    }
+
+    let speech_frames = speech.iter().filter(|&&s| s).count() as u64;
+    let covered_frames = speech.iter().zip(&covered).filter(|(&s, &c)| s && c).count() as u64;
+
+    // Collect runs of speech that no cue covers
+    let uncovered: Vec<bool> = speech.iter().zip(&covered).map(|(&s, &c)| s && !c).collect();
+    let uncovered_spans: Vec<(u64, u64)> = collect_runs(&uncovered)
+        .into_iter()
+        .map(|(start, end)| (start as u64 * ENVELOPE_FRAME_MS, end as u64 * ENVELOPE_FRAME_MS))
+        .filter(|(start_ms, end_ms)| end_ms - start_ms >= MIN_UNCOVERED_SPAN_MS)
+        .collect();
+
+    let speech_ms = speech_frames * ENVELOPE_FRAME_MS;
+    let covered_ms = covered_frames * ENVELOPE_FRAME_MS;
+    let coverage_percent = if speech_ms > 0 {
+        covered_ms as f32 / speech_ms as f32 * 100.0
+    } else {
+        100.0
+    };
+
+    Ok(CoverageReport {
+        speech_ms,
+        covered_ms,
+        coverage_percent,
+        uncovered_spans,
+    })
+}
+
+/// Runs of frames at or below threshold_db lasting at least min_silence_ms, as (start_ms, end_ms)
+fn silence_spans(levels_db: &[f32], threshold_db: f32, frame_ms: u64, min_silence_ms: u32, total_ms: u64) -> Vec<(u64, u64)> {
+    let silent: Vec<bool> = levels_db.iter().map(|&db| db <= threshold_db).collect();
+    collect_runs(&silent)
+        .into_iter()
+        .map(|(start, end)| (start as u64 * frame_ms, (end as u64 * frame_ms).min(total_ms)))
+        .filter(|(start_ms, end_ms)| end_ms - start_ms >= min_silence_ms as u64)
+        .collect()
+}
+
+/// Detect silent regions to propose cue boundaries
+/// threshold_db: frames at or below this level (dBFS, e.g. -40.0) are silent
+/// Returns (start_ms, end_ms) of every silence lasting at least min_silence_ms
+pub fn detect_silence(file_path: &str, threshold_db: f32, min_silence_ms: u32) -> Result<Vec<(u64, u64)>, String> {
+    let (samples, sample_rate) = decode_audio_mono(file_path)?;
+    if sample_rate == 0 {
+        return Err("Invalid sample rate".to_string());
+    }
+    let total_ms = samples.len() as u64 * 1000 / sample_rate as u64;
+    let levels = frame_levels_db(&samples, sample_rate, ENVELOPE_FRAME_MS);
+
+    Ok(silence_spans(&levels, threshold_db, ENVELOPE_FRAME_MS, min_silence_ms, total_ms))
+}
+
+/// Sample rate expected by the transcription engines
+pub const TRANSCRIBE_SAMPLE_RATE: u32 = 16000;
+
+/// Blackman-windowed sinc low-pass kernel with unit DC gain
+/// cutoff is a fraction of the sample rate (0.0 - 0.5)
+fn lowpass_kernel(cutoff: f64, taps: usize) -> Vec<f32> {
+    let center = (taps - 1) as f64 / 2.0;
+    let kernel: Vec<f64> = (0..taps)
+        .map(|i| {
+            let x = i as f64 - center;
+            let sinc = if x == 0.0 {
+                2.0 * cutoff
+            } else {
+                (2.0 * std::f64::consts::PI * cutoff * x).sin() / (std::f64::consts::PI * x)
+            };
+            let phase = 2.0 * std::f64::consts::PI * i as f64 / (taps - 1) as f64;
+            sinc * (0.42 - 0.5 * phase.cos() + 0.08 * (2.0 * phase).cos())
+        })
+        .collect();
+    let sum: f64 = kernel.iter().sum();
+    kernel.iter().map(|k| (k / sum) as f32).collect()
+}
+
+/// Resample mono audio with linear interpolation
+/// When downsampling, the input is low-pass filtered below the new Nyquist frequency first
+/// (evaluated only at the positions interpolation reads) so high frequencies don't alias
+fn resample_linear(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if from_rate == to_rate || samples.is_empty() {
+        return samples.to_vec();
+    }
+
+    let ratio = from_rate as f64 / to_rate as f64;
+    let out_len = (samples.len() as f64 / ratio).floor() as usize;
+
+    let kernel = (ratio > 1.0).then(|| {
+        // Cutoff at 90% of the target Nyquist leaves room for the transition band
+        let taps = ((16.0 * ratio).ceil() as usize) | 1;
+        lowpass_kernel(0.45 / ratio, taps)
+    });
+    let sample_at = |idx: usize| -> f32 {
+        let Some(kernel) = &kernel else {
+            return samples[idx];
+        };
+        let half = kernel.len() / 2;
+        kernel
+            .iter()
+            .enumerate()
+            .filter_map(|(k, weight)| {
+                (idx + k).checked_sub(half).and_then(|i| samples.get(i)).map(|s| s * weight)
+            })
+            .sum()
+    };
+
+    (0..out_len)
+        .map(|i| {
+            let pos = i as f64 * ratio;
+            let idx = pos as usize;
+            let frac = (pos - idx as f64) as f32;
+            let a = sample_at(idx);
+            let b = if idx + 1 < samples.len() { sample_at(idx + 1) } else { a };
+            a + (b - a) * frac
+        })
+        .collect()
+}
+
+/// Write mono samples as a 16 kHz 16-bit PCM WAV (the format the ASR engines expect)
+pub fn write_wav_16k_mono(output_path: &str, samples: &[f32], sample_rate: u32) -> Result<(), String> {
+    let resampled = resample_linear(samples, sample_rate, TRANSCRIBE_SAMPLE_RATE);
+
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: TRANSCRIBE_SAMPLE_RATE,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let mut writer = hound::WavWriter::create(output_path, spec)
+        .map_err(|e| format!("Failed to create WAV file: {}", e))?;
+
+    for sample in resampled {
+        let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        writer.write_sample(value)
+            .map_err(|e| format!("Failed to write WAV sample: {}", e))?;
+    }
+
+    writer.finalize()
+        .map_err(|e| format!("Failed to finalize WAV file: {}", e))?;
+
+    Ok(())
+}
+
+/// Decode the audio once and write each (start_ms, end_ms) range as a 16 kHz mono WAV
+/// Files are named by position ("00000.wav", "00001.wav", ...) inside output_dir, so ranges with
+/// identical timing still get separate files; the returned paths are in the same order as the ranges
+pub fn extract_chunks(audio_path: &str, ranges: &[(u64, u64)], output_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let (samples, sample_rate) = decode_audio_mono_cached(audio_path)?;
+
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| format!("Failed to create chunk directory: {}", e))?;
+
+    ranges
+        .iter()
+        .enumerate()
+        .map(|(index, &(start_ms, end_ms))| {
+            let start = ms_to_sample_index(start_ms, sample_rate, samples.len());
+            let end = ms_to_sample_index(end_ms, sample_rate, samples.len()).max(start);
+            let chunk_path = output_dir.join(format!("{:05}.wav", index));
+
+            write_wav_16k_mono(
+                chunk_path.to_str().ok_or("Invalid chunk path")?,
+                &samples[start..end],
+                sample_rate,
+            )?;
+
+            Ok(chunk_path)
+        })
+        .collect()
+}
+
+/// Escape a flashcard field: newlines become <br> (Anki fields are HTML);
+/// CSV fields are quoted when needed, TSV fields have tabs replaced
+fn flashcard_field(text: &str, csv: bool) -> String {
+    let text = text.trim().replace("\r\n", "\n").replace('\n', "<br>");
+    if csv {
+        if text.contains(',') || text.contains('"') {
+            format!("\"{}\"", text.replace('"', "\"\""))
+        } else {
+            text
+        }
+    } else {
+        text.replace('\t', " ")
+    }
+}
+
+/// Export one flashcard per cue for Anki: a WAV clip per cue plus a deck file
+/// with `text<TAB>[sound:clip.wav]` lines (format "tsv") or comma-separated ("csv").
+/// Clips are named "<audio stem>_<nnnn>.wav" so they stay unique in Anki's media folder;
+/// copy them into collection.media before importing the deck. Returns the deck file path.
+pub fn export_flashcards(
+    audio_path: &str,
+    entries: &[SubtitleEntry],
+    output_dir: &Path,
+    format: &str,
+) -> Result<String, String> {
+    let csv = match format {
+        "tsv" => false,
+        "csv" => true,
+        _ => return Err(format!("Unsupported flashcard format: {}", format)),
+    };
+    let stem = Path::new(audio_path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "clip".to_string());
+
+    let ranges: Vec<(u64, u64)> = entries
+        .iter()
+        .map(|e| (e.start_time.to_millis(), e.end_time.to_millis()))
+        .collect();
+    let chunks = extract_chunks(audio_path, &ranges, output_dir)?;
+
+    let separator = if csv { ',' } else { '\t' };
+    let mut lines = Vec::new();
+    for (index, (entry, chunk)) in entries.iter().zip(chunks).enumerate() {
+        let clip_name = format!("{}_{:04}.wav", stem, index + 1);
+        std::fs::rename(&chunk, output_dir.join(&clip_name))
+            .map_err(|e| format!("Failed to rename clip: {}", e))?;
+
+        lines.push(format!("{}{}[sound:{}]", flashcard_field(&entry.text, csv), separator, clip_name));
+    }
+
+    let deck_path = output_dir.join(format!("{}.{}", stem, format));
+    std::fs::write(&deck_path, lines.join("\n"))
+        .map_err(|e| format!("Failed to write deck file: {}", e))?;
+
+    println!("Exported {} flashcards to {}", lines.len(), deck_path.display());
+    Ok(deck_path.to_string_lossy().to_string())
+}
+
+/// Convert a whole audio file to a 16 kHz mono WAV
+pub fn convert_to_16k_wav(audio_path: &str, output_path: &str) -> Result<(), String> {
+    let (samples, sample_rate) = decode_audio_mono_cached(audio_path)?;
+    write_wav_16k_mono(output_path, &samples, sample_rate)
+}
+
+/// Container extensions treated as video (audio is extracted with ffmpeg first)
+const VIDEO_EXTENSIONS: &[&str] = &["mp4", "mkv", "mov", "avi", "webm", "flv", "wmv", "m4v", "ts"];
+
+/// Whether a path looks like a video file, judged by extension
+pub fn is_video_file(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| VIDEO_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+        .unwrap_or(false)
+}
+
+/// Locate ffmpeg: next to the app executable first (bundled), then on PATH
+fn find_ffmpeg() -> Option<PathBuf> {
+    let name = if cfg!(target_os = "windows") { "ffmpeg.exe" } else { "ffmpeg" };
+
+    let bundled = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(name)));
+    if let Some(path) = bundled.filter(|p| p.is_file()) {
+        return Some(path);
+    }
+
+    std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths)
+            .map(|dir| dir.join(name))
+            .find(|p| p.is_file())
+    })
+}
+
+/// Extract the audio track of a video into a 16 kHz mono WAV in the temp directory
+/// Every call gets its own file, so videos sharing a file name don't overwrite each other.
+/// Returns the path of the extracted file; the caller is responsible for removing it
+pub fn extract_audio_to_wav(video_path: &str) -> Result<String, String> {
+    if !Path::new(video_path).is_file() {
+        return Err(format!("File not found: {}", video_path));
+    }
+    let ffmpeg = find_ffmpeg()
+        .ok_or_else(|| "ffmpeg not found. Install ffmpeg and make sure it is on PATH to open video files".to_string())?;
+
+    let stem = Path::new(video_path)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "video".to_string());
+    let output_path = crate::temp_files::unique_temp_path(&format!("{}_audio", stem), ".wav");
+
+    let mut command = std::process::Command::new(&ffmpeg);
+    command.args(["-y", "-i", video_path, "-vn", "-ac", "1", "-ar", "16000", "-acodec", "pcm_s16le"]);
+    command.arg(&output_path);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = command
+        .output()
+        .map_err(|e| format!("Failed to run ffmpeg: {}", e))?;
+    if !output.status.success() {
+        let _ = std::fs::remove_file(&output_path);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let last_line = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("");
+        return Err(format!("ffmpeg failed to extract audio: {}", last_line));
+    }
+
+    println!("Extracted audio from {} -> {}", video_path, output_path.display());
+    Ok(output_path.to_string_lossy().to_string())
+}
+
+/// Trim leading/trailing silence and write the result as a 16 kHz mono WAV
+/// threshold_db: samples quieter than this level (dBFS, e.g. -45.0) count as silence
+/// padding_ms: silence kept on each side of the detected audio
+/// Returns the number of milliseconds removed from the front, so subtitle timings can be offset
+pub fn trim_audio_silence(
+    input_path: &str,
+    output_path: &str,
+    threshold_db: f32,
+    padding_ms: u64,
+) -> Result<u64, String> {
+    let (samples, sample_rate) = decode_audio_mono(input_path)?;
+    let threshold = 10f32.powf(threshold_db / 20.0);
+
+    let first = samples.iter().position(|s| s.abs() > threshold);
+    let last = samples.iter().rposition(|s| s.abs() > threshold);
+
+    let (first, last) = match (first, last) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Err(format!("No audio above {:.1} dBFS found", threshold_db)),
+    };
+
+    let padding = ms_to_sample_index(padding_ms, sample_rate, samples.len());
+    let start = first.saturating_sub(padding);
+    let end = (last + 1 + padding).min(samples.len());
+
+    write_wav_16k_mono(output_path, &samples[start..end], sample_rate)?;
+
+    let leading_trimmed_ms = start as u64 * 1000 / sample_rate as u64;
+    println!(
+        "Trimmed silence: {}ms from front, {}ms from end -> {}",
+        leading_trimmed_ms,
+        (samples.len() - end) as u64 * 1000 / sample_rate as u64,
+        output_path
+    );
+
+    Ok(leading_trimmed_ms)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -391,4 +1450,128 @@ mod tests {
         assert_eq!(result[0], -0.5); // min
         assert_eq!(result[1], 0.8); // max
     }
+
+    #[test]
+    fn test_resample_linear_filters_aliasing() {
+        let tone = |freq: f64| -> Vec<f32> {
+            (0..48_000)
+                .map(|i| (2.0 * std::f64::consts::PI * freq * i as f64 / 48_000.0).sin() as f32)
+                .collect()
+        };
+        let rms = |s: &[f32]| (s.iter().map(|x| x * x).sum::<f32>() / s.len() as f32).sqrt();
+
+        // 1 kHz passes through; 12 kHz is above the 8 kHz Nyquist of 16 kHz and must not fold back
+        let pass = resample_linear(&tone(1_000.0), 48_000, 16_000);
+        let stop = resample_linear(&tone(12_000.0), 48_000, 16_000);
+        assert_eq!(pass.len(), 16_000);
+        assert!((rms(&pass) - 0.707).abs() < 0.05);
+        assert!(rms(&stop) < 0.05);
+    }
+
+    #[test]
+    fn test_waveform_cache_roundtrip_and_version() {
+        let dir = std::env::temp_dir().join(format!("vosub_wf_cache_{}", std::process::id()));
+        let path = dir.join("a.wf");
+        write_waveform_cache(&path, &[-0.5, 0.5, -0.25, 0.75], 1234, 0.8).unwrap();
+        assert_eq!(read_waveform_cache(&path), Some((vec![-0.5, 0.5, -0.25, 0.75], 1234, 0.8)));
+
+        // Files from another format version are ignored
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[0] = WAVEFORM_CACHE_VERSION + 1;
+        std::fs::write(&path, bytes).unwrap();
+        assert_eq!(read_waveform_cache(&path), None);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_prune_waveform_cache_drops_expired_files() {
+        let dir = std::env::temp_dir().join(format!("vosub_wf_prune_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let old = dir.join("old.wf");
+        let fresh = dir.join("fresh.wf");
+        std::fs::write(&old, [0u8; 16]).unwrap();
+        std::fs::write(&fresh, [0u8; 16]).unwrap();
+        let stale = SystemTime::now() - WAVEFORM_CACHE_MAX_AGE - Duration::from_secs(60);
+        File::options().append(true).open(&old).unwrap().set_modified(stale).unwrap();
+
+        prune_waveform_cache(&dir, &fresh);
+        assert!(!old.exists());
+        assert!(fresh.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_frames_to_ms_rounds_to_nearest() {
+        assert_eq!(frames_to_ms(44_100, 44_100), 1000);
+        // 999.6ms rounds up, 999.4ms rounds down
+        assert_eq!(frames_to_ms(15_994, 16_000), 1000);
+        assert_eq!(frames_to_ms(15_990, 16_000), 999);
+    }
+
+    #[test]
+    fn test_fnv1a_64_is_stable() {
+        assert_eq!(fnv1a_64(&[]), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a_64(&[b"a"]), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a_64(&[b"fo", b"obar"]), fnv1a_64(&[b"foobar"]));
+    }
+
+    #[test]
+    fn test_silence_spans() {
+        // 20ms frames: 100ms of silence, 60ms of speech, a 40ms dip, then trailing silence
+        let mut levels = vec![-80.0; 5];
+        levels.extend(vec![-20.0; 3]);
+        levels.extend(vec![-80.0; 2]);
+        levels.extend(vec![-20.0; 2]);
+        levels.extend(vec![-80.0; 6]);
+
+        let spans = silence_spans(&levels, -40.0, 20, 100, 350);
+        assert_eq!(spans, vec![(0, 100), (240, 350)]);
+    }
+
+    #[test]
+    fn test_detect_speech_frames_bridges_short_gaps() {
+        // 20ms frames: a 40ms dip is bridged, a 400ms gap is not
+        let mut levels = vec![-20.0; 5];
+        levels.extend(vec![-80.0; 2]);
+        levels.extend(vec![-20.0; 5]);
+        levels.extend(vec![-80.0; 20]);
+        levels.push(-20.0);
+
+        let speech = detect_speech_frames(&levels, -40.0, 20);
+        assert!(speech[5] && speech[6]);
+        assert!(!speech[15]);
+        assert!(speech[32]);
+    }
+
+    #[test]
+    fn test_true_peak_finds_inter_sample_peak() {
+        // Sine at fs/4 sampled 45 degrees off its crest: samples peak at 0.707, the waveform at 1.0
+        let samples: Vec<f32> = (0..64)
+            .map(|i| (std::f32::consts::FRAC_PI_2 * i as f32 + std::f32::consts::FRAC_PI_4).sin())
+            .collect();
+        let sample_peak = samples.iter().fold(0.0f32, |m, &s| m.max(s.abs()));
+        assert!(sample_peak < 0.71);
+        assert!(true_peak_linear(&samples) > 0.85);
+    }
+
+    #[test]
+    fn test_peak_rms_buckets() {
+        let samples = vec![0.5, -0.5, 0.5, -0.5, 0.25, -0.25, 0.25, -0.25];
+        let data = peak_rms_buckets(&samples, 2);
+        assert_eq!(data.peak, vec![1.0, 0.5]);
+        assert!((data.rms[0] - 1.0).abs() < 1e-6);
+        assert!((data.rms[1] - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_rms_to_db() {
+        // Full-scale square wave has RMS 1.0 => 0 dBFS
+        let rms = compute_rms(&[1.0, -1.0, 1.0, -1.0]);
+        assert!((rms_to_db(rms) - 0.0).abs() < 1e-4);
+        // Half amplitude => about -6 dBFS
+        let rms = compute_rms(&[0.5, -0.5]);
+        assert!((rms_to_db(rms) + 6.0206).abs() < 1e-3);
+        // Silence is clamped to the floor
+        assert_eq!(rms_to_db(compute_rms(&[0.0, 0.0])), SILENCE_FLOOR_DB);
+    }
 }
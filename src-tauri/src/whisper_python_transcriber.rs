@@ -1,3 +1,5 @@
+use crate::model_registry::{forget_model, format_model_size, get_model_label, get_registered_models, register_model, RegisteredModel};
+use crate::active_operation::{begin_operation, OperationKind};
 use crate::srt_parser::{SubtitleEntry, TimeStamp};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -56,6 +58,7 @@ pub struct WhisperProgress {
 pub struct WhisperEnvInfo {
     pub installed: bool,
     pub ready: bool,
+    pub broken: bool,  // 依赖已安装但 Python 解释器无法启动（需要修复）
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -76,14 +79,13 @@ pub struct WhisperModelInfo {
     pub size: String,
     pub downloaded: bool,
     pub partial_size: Option<u64>,
+    /// 用户自定义的显示名称
+    pub label: Option<String>,
 }
 
 /// 获取 Whisper 环境基础目录
 fn get_whisper_base_dir() -> Result<PathBuf, String> {
-    let home_dir = dirs::home_dir()
-        .ok_or_else(|| "Failed to get home directory".to_string())?;
-    
-    Ok(home_dir.join(".config").join("vosub"))
+    crate::app_config::app_config_dir()
 }
 
 /// 获取 Whisper CPU 环境目录
@@ -160,13 +162,7 @@ pub fn get_whisper_model_dir() -> Result<PathBuf, String> {
 
 /// 获取 Python 脚本目录
 pub fn get_scripts_dir() -> Result<PathBuf, String> {
-    let home_dir = dirs::home_dir()
-        .ok_or_else(|| "Failed to get home directory".to_string())?;
-    
-    let scripts_dir = home_dir
-        .join(".config")
-        .join("vosub")
-        .join("scripts");
+    let scripts_dir = crate::app_config::app_config_dir()?.join("scripts");
     
     if !scripts_dir.exists() {
         std::fs::create_dir_all(&scripts_dir)
@@ -300,8 +296,24 @@ fn get_uv_path() -> Option<PathBuf> {
     }
 }
 
-/// 检查指定环境是否就绪
+/// 获取指定环境的 Python 版本，解释器不存在或无法启动时返回 None
+fn get_python_version_for_env(env_dir: &PathBuf) -> Option<String> {
+    crate::python_env::python_version(&get_python_path_for_env(env_dir))
+}
+
+/// 获取 Whisper 指定环境（CPU/GPU）的 Python 版本
+pub fn get_whisper_python_version(use_gpu: bool) -> Option<String> {
+    let env_dir = if use_gpu { get_whisper_gpu_env_dir() } else { get_whisper_cpu_env_dir() }.ok()?;
+    get_python_version_for_env(&env_dir)
+}
+
+/// 检查指定环境是否就绪（依赖已安装且 Python 解释器可以启动）
 fn check_whisper_env_ready(env_dir: &PathBuf) -> bool {
+    check_whisper_env_files_ready(env_dir) && get_python_version_for_env(env_dir).is_some()
+}
+
+/// 检查指定环境的依赖文件是否存在（不启动 Python）
+fn check_whisper_env_files_ready(env_dir: &PathBuf) -> bool {
     let python_path = get_python_path_for_env(env_dir);
     if !env_dir.exists() || !python_path.exists() {
         return false;
@@ -344,12 +356,16 @@ pub fn check_whisper_env() -> WhisperEnvStatus {
     // 检查 CPU 环境
     let cpu_dir = get_whisper_cpu_env_dir().unwrap_or_default();
     let cpu_installed = cpu_dir.exists();
+    let cpu_files_ready = check_whisper_env_files_ready(&cpu_dir);
     let cpu_ready = check_whisper_env_ready(&cpu_dir);
+    let cpu_broken = cpu_files_ready && !cpu_ready;
     
     // 检查 GPU 环境
     let gpu_dir = get_whisper_gpu_env_dir().unwrap_or_default();
     let gpu_installed = gpu_dir.exists();
+    let gpu_files_ready = check_whisper_env_files_ready(&gpu_dir);
     let gpu_ready = check_whisper_env_ready(&gpu_dir);
+    let gpu_broken = gpu_files_ready && !gpu_ready;
     
     // 获取当前激活的环境
     let mut active_env = get_whisper_active_env_type();
@@ -392,10 +408,12 @@ pub fn check_whisper_env() -> WhisperEnvStatus {
         cpu_env: WhisperEnvInfo {
             installed: cpu_installed,
             ready: cpu_ready,
+            broken: cpu_broken,
         },
         gpu_env: WhisperEnvInfo {
             installed: gpu_installed,
             ready: gpu_ready,
+            broken: gpu_broken,
         },
         active_env,
         env_exists,
@@ -408,6 +426,8 @@ pub fn check_whisper_env() -> WhisperEnvStatus {
 /// 安装 Whisper 环境
 /// use_gpu: 是否安装 GPU 版本（需要 NVIDIA 显卡和 CUDA）
 pub async fn install_whisper_env(window: Window, use_gpu: bool) -> Result<String, String> {
+    let _operation = begin_operation("whisper", OperationKind::Installing);
+    
     reset_cancellation();
     
     // 获取 uv 路径
@@ -421,6 +441,12 @@ pub async fn install_whisper_env(window: Window, use_gpu: bool) -> Result<String
         get_whisper_cpu_env_dir()?
     };
     
+    // 预检磁盘空间，避免安装到一半磁盘写满
+    crate::disk_space::ensure_disk_space(
+        &env_dir,
+        if use_gpu { crate::disk_space::GPU_ENV_REQUIRED_BYTES } else { crate::disk_space::CPU_ENV_REQUIRED_BYTES },
+    )?;
+    
     let version_type = if use_gpu { "GPU" } else { "CPU" };
     
     // 发送进度
@@ -679,6 +705,7 @@ Whisper 转录脚本 - 使用 faster-whisper（带实时进度）
 import sys
 import os
 import json
+import math
 import argparse
 
 # 强制禁用输出缓冲
@@ -700,7 +727,9 @@ def get_audio_duration(audio_path: str) -> float:
     except:
         return 0.0
 
-def transcribe(audio_path: str, model_size: str, language: str, device: str = "auto", output_path: str = None):
+def transcribe(audio_path: str, model_size: str, language: str, device: str = "auto", output_path: str = None,
+               beam_size: int = 5, temperature: float = 0.0, word_timestamps: bool = False,
+               initial_prompt: str = None, task: str = "transcribe"):
     """转录音频文件，实时输出进度"""
     
     import torch
@@ -730,6 +759,7 @@ def transcribe(audio_path: str, model_size: str, language: str, device: str = "a
     log(f"DURATION:{audio_duration:.1f}")
     
     # 加载模型
+    # model_size 可以是内置大小、HuggingFace 仓库 ID 或本地模型目录，原样传给 faster-whisper
     model = WhisperModel(model_size, device=device, compute_type=compute_type)
     
     # 输出转录状态，同时传递估算信息
@@ -739,7 +769,11 @@ def transcribe(audio_path: str, model_size: str, language: str, device: str = "a
     segments, info = model.transcribe(
         audio_path,
         language=language if language != "auto" else None,
-        beam_size=5,
+        task=task,
+        beam_size=beam_size,
+        temperature=temperature,
+        word_timestamps=word_timestamps,
+        initial_prompt=initial_prompt or None,
         vad_filter=True,
         vad_parameters=dict(min_silence_duration_ms=500),
     )
@@ -749,11 +783,20 @@ def transcribe(audio_path: str, model_size: str, language: str, device: str = "a
     # 收集结果，输出每个 segment 的进度
     results = []
     for segment in segments:
-        results.append({
+        item = {
             "start": segment.start,
             "end": segment.end,
-            "text": segment.text.strip()
-        })
+            "text": segment.text.strip(),
+            # 平均对数概率换算为 0-1 的置信度
+            "confidence": math.exp(segment.avg_logprob)
+        }
+        if word_timestamps and segment.words:
+            # 保留词前空格，拼接时还原英文词间距
+            item["words"] = [
+                {"start": w.start, "end": w.end, "word": w.word, "probability": w.probability}
+                for w in segment.words
+            ]
+        results.append(item)
         
         # 基于实际 segment 更新进度
         progress = min((segment.end / total_duration) * 100, 95.0)
@@ -781,11 +824,23 @@ def main():
     parser.add_argument("--language", default="auto", help="语言代码")
     parser.add_argument("--device", default="auto", help="设备: auto, cpu, cuda")
     parser.add_argument("--output", required=True, help="输出 JSON 文件路径")
+    parser.add_argument("--beam-size", type=int, default=5, help="束搜索宽度 (1-10)")
+    parser.add_argument("--temperature", type=float, default=0.0, help="采样温度")
+    parser.add_argument("--word-timestamps", action="store_true", help="输出词级时间戳")
+    parser.add_argument("--initial-prompt-file", default=None, help="初始提示词文件（UTF-8 文本）")
+    parser.add_argument("--task", default="transcribe", choices=["transcribe", "translate"], help="任务: transcribe 转录, translate 翻译为英文")
     
     args = parser.parse_args()
     
     try:
-        result = transcribe(args.audio, args.model, args.language, args.device, args.output)
+        # 提示词通过文件传入，避免引号、换行和编码在命令行中出错
+        initial_prompt = None
+        if args.initial_prompt_file:
+            with open(args.initial_prompt_file, "r", encoding="utf-8") as f:
+                initial_prompt = f.read().strip()
+        
+        result = transcribe(args.audio, args.model, args.language, args.device, args.output,
+                            args.beam_size, args.temperature, args.word_timestamps, initial_prompt, args.task)
         log(json.dumps({"status": "success", "segments": len(result["segments"])}))
     except Exception as e:
         print(f"ERROR:{str(e)}", file=sys.stderr, flush=True)
@@ -806,7 +861,6 @@ if __name__ == "__main__":
 #[derive(Debug, Deserialize)]
 struct TranscriptionResult {
     segments: Vec<TranscriptionSegment>,
-    #[allow(dead_code)]
     language: Option<String>,
     #[allow(dead_code)]
     duration: Option<f64>,
@@ -817,6 +871,123 @@ struct TranscriptionSegment {
     start: f64,
     end: f64,
     text: String,
+    #[serde(default)]
+    confidence: Option<f32>,
+    /// 词级时间戳（仅在启用 word_level 时输出）
+    #[serde(default)]
+    words: Vec<TranscriptionWord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptionWord {
+    start: f64,
+    end: f64,
+    word: String,
+    #[serde(default)]
+    probability: Option<f32>,
+}
+
+/// 将词级时间戳拼成字幕条目：每条最多 max_chars 个字符（为 None 时每个词一条）
+/// 单个词超过上限时独立成条，不拆分
+fn group_words_into_entries(segments: &[TranscriptionSegment], max_chars: Option<usize>) -> Vec<SubtitleEntry> {
+    let mut entries: Vec<SubtitleEntry> = Vec::new();
+    let mut group: Vec<&TranscriptionWord> = Vec::new();
+
+    let flush = |group: &mut Vec<&TranscriptionWord>, entries: &mut Vec<SubtitleEntry>| {
+        if group.is_empty() {
+            return;
+        }
+        let text: String = group.iter().map(|w| w.word.as_str()).collect::<String>().trim().to_string();
+        let probabilities: Vec<f32> = group.iter().filter_map(|w| w.probability).collect();
+        let confidence = if probabilities.is_empty() {
+            None
+        } else {
+            Some(probabilities.iter().sum::<f32>() / probabilities.len() as f32)
+        };
+        entries.push(SubtitleEntry {
+            id: (entries.len() + 1) as u32,
+            start_time: TimeStamp::from_millis((group[0].start * 1000.0) as u64),
+            end_time: TimeStamp::from_millis((group[group.len() - 1].end * 1000.0) as u64),
+            text,
+            confidence,
+            events: None,
+        });
+        group.clear();
+    };
+
+    for word in segments.iter().flat_map(|seg| seg.words.iter()) {
+        if word.word.trim().is_empty() {
+            continue;
+        }
+        let fits = match max_chars {
+            Some(limit) => {
+                let current: usize = group.iter().map(|w| w.word.chars().count()).sum();
+                group.is_empty() || (current + word.word.chars().count()) <= limit
+            }
+            None => group.is_empty(),
+        };
+        if !fits {
+            flush(&mut group, &mut entries);
+        }
+        group.push(word);
+    }
+    flush(&mut group, &mut entries);
+
+    entries
+}
+
+/// 转录结果及运行信息（引擎、模型、实际设备、耗时），用于基准测试和导出时记录来源
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptionReport {
+    pub entries: Vec<SubtitleEntry>,
+    /// "whisper" | "sensevoice"
+    pub engine: String,
+    pub model: String,
+    /// 实际使用的设备，如 "CPU" 或 "CUDA (RTX 4090, 24.0GB)"
+    pub device: String,
+    pub elapsed_secs: f64,
+    /// 识别出的语言代码（如 "zh"、"en"），引擎未报告时为 None
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+/// Whisper 解码参数，未设置的项使用默认值（beam 5，温度 0.0）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WhisperOptions {
+    pub beam_size: Option<usize>,
+    pub temperature: Option<f32>,
+    /// 按词输出字幕（卡拉 OK 字幕等场景）
+    pub word_level: Option<bool>,
+    /// 按词输出时每条字幕的最大字符数，None 表示每个词一条
+    pub word_group_chars: Option<usize>,
+    /// 初始提示词，用于引导识别专有名词（如 "Kubernetes, Grafana, Prometheus"）
+    pub initial_prompt: Option<String>,
+    /// "transcribe"（默认）| "translate"（翻译为英文）
+    pub task: Option<String>,
+    /// 仅本次转录使用的环境（"cpu" | "gpu"），不改变已激活的环境；None 表示使用激活的环境
+    #[serde(default)]
+    pub env_type: Option<String>,
+}
+
+impl WhisperOptions {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(beam_size) = self.beam_size {
+            if !(1..=10).contains(&beam_size) {
+                return Err(format!("beam_size 必须在 1 到 10 之间，当前为 {}", beam_size));
+            }
+        }
+        if let Some(temperature) = self.temperature {
+            if !temperature.is_finite() || temperature < 0.0 {
+                return Err(format!("temperature 不能为负数，当前为 {}", temperature));
+            }
+        }
+        if let Some(task) = &self.task {
+            if task != "transcribe" && task != "translate" {
+                return Err(format!("不支持的任务类型: {}（可选 transcribe 或 translate）", task));
+            }
+        }
+        Ok(())
+    }
 }
 
 /// 使用 Whisper 转录音频
@@ -824,32 +995,77 @@ pub async fn transcribe_with_whisper(
     audio_path: String,
     model_size: String,
     language: String,
+    options: WhisperOptions,
     window: Window,
 ) -> Result<Vec<SubtitleEntry>, String> {
+    transcribe_with_whisper_report(audio_path, model_size, language, options, window)
+        .await
+        .map(|report| report.entries)
+}
+
+/// 使用 Whisper 转录音频，同时返回运行信息
+pub async fn transcribe_with_whisper_report(
+    audio_path: String,
+    model_size: String,
+    language: String,
+    options: WhisperOptions,
+    window: Window,
+) -> Result<TranscriptionReport, String> {
     use std::io::{BufRead, BufReader};
     use std::process::Stdio;
     
+    let _operation = begin_operation("whisper", OperationKind::Transcribing);
+    
+    options.validate()?;
+    
     reset_cancellation();
     
     // 记录开始时间
     let start_time = std::time::Instant::now();
     
     // 检查环境
-    let env_status = check_whisper_env();
-    if !env_status.ready {
-        return Err("Whisper 环境未安装，请先安装环境".to_string());
-    }
+    let (env_type, python_path, is_gpu) = match options.env_type.as_deref() {
+        Some(env_type) => {
+            let env_dir = match env_type {
+                "gpu" => get_whisper_gpu_env_dir()?,
+                "cpu" => get_whisper_cpu_env_dir()?,
+                _ => return Err(format!("未知的环境类型: {}", env_type)),
+            };
+            let python_path = get_python_path_for_env(&env_dir);
+            if !python_path.exists() {
+                return Err(format!("Whisper {} 环境未安装，请先安装环境", env_type.to_uppercase()));
+            }
+            (env_type.to_string(), python_path, env_type == "gpu")
+        }
+        None => {
+            let env_status = check_whisper_env();
+            if !env_status.ready {
+                return Err("Whisper 环境未安装，请先安装环境".to_string());
+            }
+            (get_whisper_active_env_type(), get_python_path()?, env_status.is_gpu)
+        }
+    };
+    
+    // GPU 环境下排队等待显卡空闲，避免与其他任务同时占用显存
+    let _gpu_permit = crate::gpu_guard::acquire_gpu_permit(&env_type).await?;
     
-    let python_path = get_python_path()?;
     let scripts_dir = get_scripts_dir()?;
     let script_path = scripts_dir.join("whisper_transcribe.py");
     
     // 总是更新脚本以确保使用最新版本
     write_transcription_script()?;
     
-    // 创建临时输出文件
-    let temp_dir = std::env::temp_dir();
-    let output_path = temp_dir.join(format!("whisper_result_{}.json", std::process::id()));
+    // 自定义模型：本地目录必须存在；仓库 ID 由 faster-whisper 自动下载
+    let model_size = expand_model_path(&model_size);
+    if is_local_model_path(&model_size) && !std::path::Path::new(&model_size).is_dir() {
+        return Err(format!("本地模型目录不存在: {}", model_size));
+    }
+    if is_custom_model(&model_size) {
+        log::info!("[Whisper] 使用自定义模型: {}", model_size);
+    }
+    
+    // 创建临时输出文件（每次调用独占，并发转录互不覆盖）
+    let output_path = crate::temp_files::unique_temp_path("whisper_result", ".json");
     
     // 发送初始进度
     let _ = window.emit("transcription-progress", WhisperProgress {
@@ -863,7 +1079,42 @@ pub async fn transcribe_with_whisper(
     }
     
     // 确定设备
-    let device = if env_status.is_gpu { "cuda" } else { "cpu" };
+    let device = if is_gpu { "cuda" } else { "cpu" };
+    
+    let beam_size = options.beam_size.unwrap_or(5).to_string();
+    let temperature = options.temperature.unwrap_or(0.0).to_string();
+    let mut args = vec![
+        "-u",  // unbuffered output
+        script_path.to_str().unwrap(),
+        "--audio", &audio_path,
+        "--model", &model_size,
+        "--language", &language,
+        "--device", device,
+        "--output", output_path.to_str().unwrap(),
+        "--beam-size", &beam_size,
+        "--temperature", &temperature,
+        "--task", options.task.as_deref().unwrap_or("transcribe"),
+    ];
+    let word_level = options.word_level.unwrap_or(false);
+    if word_level {
+        args.push("--word-timestamps");
+    }
+    
+    // 初始提示词写入临时文件传递，函数返回时（包括出错）自动删除
+    let prompt_file = crate::temp_files::TempFileGuard(crate::temp_files::unique_temp_path("whisper_prompt", ".txt"));
+    let prompt_path_str = prompt_file.0.to_string_lossy().to_string();
+    let has_prompt = match options.initial_prompt.as_deref().map(str::trim) {
+        Some(prompt) if !prompt.is_empty() => {
+            std::fs::write(&prompt_file.0, prompt)
+                .map_err(|e| format!("写入提示词文件失败: {}", e))?;
+            true
+        }
+        _ => false,
+    };
+    if has_prompt {
+        args.push("--initial-prompt-file");
+        args.push(&prompt_path_str);
+    }
     
     // 运行 Python 脚本，使用 Stdio::piped() 实时读取输出
     #[cfg(target_os = "windows")]
@@ -871,15 +1122,7 @@ pub async fn transcribe_with_whisper(
         use std::os::windows::process::CommandExt;
         const CREATE_NO_WINDOW: u32 = 0x08000000;
         Command::new(&python_path)
-            .args([
-                "-u",  // unbuffered output
-                script_path.to_str().unwrap(),
-                "--audio", &audio_path,
-                "--model", &model_size,
-                "--language", &language,
-                "--device", device,
-                "--output", output_path.to_str().unwrap(),
-            ])
+            .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .creation_flags(CREATE_NO_WINDOW)
@@ -889,15 +1132,7 @@ pub async fn transcribe_with_whisper(
     
     #[cfg(not(target_os = "windows"))]
     let mut child = Command::new(&python_path)
-        .args([
-            "-u",  // unbuffered output
-            script_path.to_str().unwrap(),
-            "--audio", &audio_path,
-            "--model", &model_size,
-            "--language", &language,
-            "--device", device,
-            "--output", output_path.to_str().unwrap(),
-        ])
+        .args(&args)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
@@ -907,17 +1142,11 @@ pub async fn transcribe_with_whisper(
     let stdout = child.stdout.take();
     let stderr = child.stderr.take();
     
-    // 用于进度模拟的共享状态
-    let audio_duration = Arc::new(std::sync::atomic::AtomicU64::new(0));
-    let is_transcribing = Arc::new(AtomicBool::new(false));
-    let transcribe_done = Arc::new(AtomicBool::new(false));
-    let current_progress = Arc::new(std::sync::atomic::AtomicU64::new(0));
-    
     let window_clone = window.clone();
-    let audio_duration_clone = audio_duration.clone();
-    let is_transcribing_clone = is_transcribing.clone();
-    let transcribe_done_clone = transcribe_done.clone();
-    let current_progress_clone = current_progress.clone();
+    
+    // 实际使用的设备（由脚本输出的 DEVICE_INFO 解析）
+    let device_used = Arc::new(std::sync::Mutex::new(String::new()));
+    let device_used_clone = device_used.clone();
     
     // 用于日志的参数
     let audio_path_for_log = audio_path.clone();
@@ -925,7 +1154,9 @@ pub async fn transcribe_with_whisper(
     let language_for_log = language.clone();
     
     // 在后台线程读取 stdout，解析进度
+    // 进度完全来自脚本输出的实际解码位置，只增不减，避免进度条倒退
     let stdout_handle = std::thread::spawn(move || {
+        let mut current_progress: f32 = 0.0;
         if let Some(stdout) = stdout {
             let reader = BufReader::new(stdout);
             for line in reader.lines().flatten() {
@@ -942,6 +1173,9 @@ pub async fn transcribe_with_whisper(
                     } else {
                         "CPU".to_string()
                     };
+                    if let Ok(mut device) = device_used_clone.lock() {
+                        *device = device_str.clone();
+                    }
                     log::info!(
                         "开始语音转录: 音频文件={}, 模型=faster-whisper-{}, 语言={}, 设备={}",
                         audio_path_for_log, model_size_for_log, language_for_log, device_str
@@ -951,9 +1185,7 @@ pub async fn transcribe_with_whisper(
                 
                 // 解析 DURATION:xxx 格式
                 if line.starts_with("DURATION:") {
-                    if let Ok(duration) = line.trim_start_matches("DURATION:").parse::<f64>() {
-                        audio_duration_clone.store((duration * 1000.0) as u64, Ordering::SeqCst);
-                    }
+                    log::debug!("Whisper 音频时长: {} 秒", line.trim_start_matches("DURATION:"));
                     continue;
                 }
                 
@@ -962,17 +1194,11 @@ pub async fn transcribe_with_whisper(
                     let status = line.trim_start_matches("STATUS:");
                     let (progress, text, status_str) = match status {
                         "loading" => (5.0, "正在加载语音模型...".to_string(), "loading"),
-                        "transcribing" => {
-                            is_transcribing_clone.store(true, Ordering::SeqCst);
-                            (10.0, "正在识别语音内容...".to_string(), "transcribing")
-                        },
-                        "completed" => {
-                            transcribe_done_clone.store(true, Ordering::SeqCst);
-                            (99.0, "正在处理结果...".to_string(), "processing")
-                        },
+                        "transcribing" => (10.0, "正在识别语音内容...".to_string(), "transcribing"),
+                        "completed" => (99.0, "正在处理结果...".to_string(), "processing"),
                         _ => continue,
                     };
-                    current_progress_clone.store((progress * 100.0) as u64, Ordering::SeqCst);
+                    current_progress = current_progress.max(progress);
                     let _ = window_clone.emit("transcription-progress", WhisperProgress {
                         progress,
                         current_text: text,
@@ -987,9 +1213,8 @@ pub async fn transcribe_with_whisper(
                             // 将进度映射到 10-95 范围
                             let mapped_progress = 10.0 + (pct * 0.85);
                             // 只有当新进度大于当前进度时才更新，避免进度倒退
-                            let current = current_progress_clone.load(Ordering::SeqCst) as f32 / 100.0;
-                            if mapped_progress > current {
-                                current_progress_clone.store((mapped_progress * 100.0) as u64, Ordering::SeqCst);
+                            if mapped_progress > current_progress {
+                                current_progress = mapped_progress;
                                 let _ = window_clone.emit("transcription-progress", WhisperProgress {
                                     progress: mapped_progress,
                                     current_text: "正在识别语音内容...".to_string(),
@@ -1021,70 +1246,13 @@ pub async fn transcribe_with_whisper(
         stderr_output
     });
     
-    // 进度模拟线程 - 在 Rust 端模拟进度
-    let window_for_progress = window.clone();
-    let model_size_clone = model_size.clone();
-    let is_gpu = env_status.is_gpu;
-    let progress_handle = std::thread::spawn(move || {
-        // 等待转录开始
-        while !is_transcribing.load(Ordering::SeqCst) && !transcribe_done.load(Ordering::SeqCst) {
-            std::thread::sleep(std::time::Duration::from_millis(100));
-        }
-        
-        if transcribe_done.load(Ordering::SeqCst) {
-            return;
-        }
-        
-        let duration_ms = audio_duration.load(Ordering::SeqCst);
-        if duration_ms == 0 {
-            return;
-        }
-        
-        let duration_secs = duration_ms as f64 / 1000.0;
-        
-        // 根据模型大小和设备估算处理速度（相对于实时播放速度的倍数）
-        // GPU 通常比实时快很多，根据你的实际测试调整
-        let speed_factor = if is_gpu { 12.0 } else { 2.0 };
-        let model_factor = match model_size_clone.as_str() {
-            "tiny" => 3.0,
-            "base" => 2.0,
-            "small" => 1.2,
-            "medium" => 0.8,
-            "large-v2" | "large-v3" => 0.55,
-            _ => 1.0,
-        };
-        let estimated_time = duration_secs / (speed_factor * model_factor);
-        
-        let start_time = std::time::Instant::now();
-        
-        while !transcribe_done.load(Ordering::SeqCst) {
-            let elapsed = start_time.elapsed().as_secs_f64();
-            // 使用非线性进度曲线
-            let simulated_progress = ((elapsed / estimated_time).powf(0.7) * 85.0).min(85.0);
-            let mapped_progress = 10.0 + simulated_progress;
-            
-            // 只有当模拟进度大于当前进度时才更新
-            let current = current_progress.load(Ordering::SeqCst) as f64 / 100.0;
-            if mapped_progress > current {
-                current_progress.store((mapped_progress * 100.0) as u64, Ordering::SeqCst);
-                let _ = window_for_progress.emit("transcription-progress", WhisperProgress {
-                    progress: mapped_progress as f32,
-                    current_text: "正在识别语音内容...".to_string(),
-                    status: "transcribing".to_string(),
-                });
-            }
-            
-            std::thread::sleep(std::time::Duration::from_millis(500));
-        }
-    });
-    
     // 等待所有线程完成
     let _ = stdout_handle.join();
     let stderr_output = stderr_handle.join().unwrap_or_default();
-    let _ = progress_handle.join();
     
     // 等待进程结束
     let status = child.wait().map_err(|e| format!("等待转录完成失败: {}", e))?;
+    drop(prompt_file);
     
     if is_cancelled() {
         let _ = std::fs::remove_file(&output_path);
@@ -1106,32 +1274,31 @@ pub async fn transcribe_with_whisper(
     let result: TranscriptionResult = serde_json::from_str(&result_json)
         .map_err(|e| format!("解析转录结果失败: {}", e))?;
     
+    // 识别出的语言，由交互式转录命令在选择"自动"时通知前端
+    let detected_language = result.language.clone().filter(|l| !l.is_empty());
+    
     // 转换为字幕条目
-    let entries: Vec<SubtitleEntry> = result.segments
-        .iter()
-        .enumerate()
-        .map(|(i, seg)| {
-            let start_ms = (seg.start * 1000.0) as u32;
-            let end_ms = (seg.end * 1000.0) as u32;
-            
-            SubtitleEntry {
+    let entries: Vec<SubtitleEntry> = if word_level {
+        group_words_into_entries(&result.segments, options.word_group_chars)
+    } else {
+        result.segments
+            .iter()
+            .enumerate()
+            .map(|(i, seg)| SubtitleEntry {
                 id: (i + 1) as u32,
-                start_time: TimeStamp {
-                    hours: start_ms / 3600000,
-                    minutes: (start_ms % 3600000) / 60000,
-                    seconds: (start_ms % 60000) / 1000,
-                    milliseconds: start_ms % 1000,
-                },
-                end_time: TimeStamp {
-                    hours: end_ms / 3600000,
-                    minutes: (end_ms % 3600000) / 60000,
-                    seconds: (end_ms % 60000) / 1000,
-                    milliseconds: end_ms % 1000,
-                },
+                start_time: TimeStamp::from_millis((seg.start * 1000.0) as u64),
+                end_time: TimeStamp::from_millis((seg.end * 1000.0) as u64),
                 text: seg.text.clone(),
-            }
-        })
-        .collect();
+                confidence: seg.confidence,
+                events: None,
+            })
+            .collect()
+    };
+    
+    // 自定义模型首次使用时由 faster-whisper 自动下载，转录成功后登记
+    if is_custom_model(&model_size) {
+        register_whisper_model(&model_size);
+    }
     
     // 计算耗时
     let elapsed = start_time.elapsed();
@@ -1152,9 +1319,435 @@ pub async fn transcribe_with_whisper(
     // 短暂延迟让前端有时间显示 100% 进度
     std::thread::sleep(std::time::Duration::from_millis(500));
     
+    let device = device_used.lock().map(|d| d.clone()).unwrap_or_default();
+    let device = if device.is_empty() {
+        if is_gpu { "CUDA".to_string() } else { "CPU".to_string() }
+    } else {
+        device
+    };
+    
+    Ok(TranscriptionReport {
+        entries,
+        engine: "whisper".to_string(),
+        model: model_size,
+        device,
+        elapsed_secs,
+        language: detected_language,
+    })
+}
+
+/// 判断转录失败是否值得换用更小的模型重试（显存不足、模型加载失败或进程崩溃）
+fn is_retryable_failure(error: &str) -> bool {
+    let lower = error.to_lowercase();
+    // 进程崩溃（如段错误）时 stderr 中没有错误信息
+    if error.trim() == "转录失败:" {
+        return true;
+    }
+    // 只匹配显存/CUDA 与模型文件加载的报错；音频文件不存在等输入错误换模型也无济于事
+    [
+        "out of memory",
+        "cuda error",
+        "cuda failed",
+        "cublas",
+        "cudnn",
+        "memoryerror",
+        "bad_alloc",
+        "unable to open file 'model.bin'",
+    ]
+    .iter()
+    .any(|pattern| lower.contains(pattern))
+}
+
+/// 使用 Whisper 转录，失败时按 fallback_models 顺序降级重试
+/// 仅在显存不足、模型加载失败等情况下降级；取消或环境问题直接返回错误
+pub async fn transcribe_with_fallback(
+    audio_path: String,
+    model_size: String,
+    language: String,
+    fallback_models: Vec<String>,
+    options: WhisperOptions,
+    window: Window,
+) -> Result<TranscriptionReport, String> {
+    let mut models = vec![model_size];
+    models.extend(fallback_models);
+    
+    let mut last_error = String::new();
+    for (index, model) in models.iter().enumerate() {
+        if index > 0 {
+            log::warn!("[Whisper] 模型 {} 转录失败，降级为 {} 重试: {}", models[index - 1], model, last_error);
+            let _ = window.emit("transcription-progress", WhisperProgress {
+                progress: 0.0,
+                current_text: format!("{} 模型运行失败，正在改用 {} 模型重试...", models[index - 1], model),
+                status: "starting".to_string(),
+            });
+        }
+        
+        match transcribe_with_whisper_report(audio_path.clone(), model.clone(), language.clone(), options.clone(), window.clone()).await {
+            Ok(report) => return Ok(report),
+            Err(e) if is_retryable_failure(&e) && !is_cancelled() => last_error = e,
+            Err(e) => return Err(e),
+        }
+    }
+    
+    Err(last_error)
+}
+
+/// 写入强制对齐脚本
+fn write_alignment_script() -> Result<(), String> {
+    let scripts_dir = get_scripts_dir()?;
+    let script_path = scripts_dir.join("whisper_align.py");
+    
+    let script_content = r#"#!/usr/bin/env python3
+# -*- coding: utf-8 -*-
+"""
+Whisper 强制对齐脚本 - 将已知文稿逐行对齐到音频
+先用 faster-whisper 获取词级时间戳（以文稿作为提示词），
+再按字符将文稿与识别结果做序列匹配，为每一行取得起止时间
+"""
+
+import sys
+import os
+import json
+import argparse
+import difflib
+
+os.environ["PYTHONUNBUFFERED"] = "1"
+
+from faster_whisper import WhisperModel
+
+def log(msg):
+    print(msg, flush=True)
+
+def char_timeline(words):
+    """将词级时间戳展开为字符级：[(字符, 开始, 结束)]"""
+    chars = []
+    for w in words:
+        letters = [c.lower() for c in w.word if c.isalnum()]
+        if not letters:
+            continue
+        step = (w.end - w.start) / len(letters)
+        for k, c in enumerate(letters):
+            chars.append((c, w.start + step * k, w.start + step * (k + 1)))
+    return chars
+
+def align_lines(lines, chars, duration):
+    """按字符匹配为每一行计算时间；未匹配的行按字数在相邻行之间插值"""
+    target = []
+    owner = []
+    for i, line in enumerate(lines):
+        for c in line:
+            if c.isalnum():
+                target.append(c.lower())
+                owner.append(i)
+
+    matcher = difflib.SequenceMatcher(None, target, [c[0] for c in chars], autojunk=False)
+    spans = [None] * len(lines)
+    for a, b, size in matcher.get_matching_blocks():
+        for k in range(size):
+            i = owner[a + k]
+            _, start, end = chars[b + k]
+            if spans[i] is None:
+                spans[i] = [start, end]
+            else:
+                spans[i][0] = min(spans[i][0], start)
+                spans[i][1] = max(spans[i][1], end)
+
+    # 保证时间单调：与前一行重叠时截断
+    prev_end = 0.0
+    for i, span in enumerate(spans):
+        if span is None:
+            continue
+        if span[0] < prev_end:
+            span[0] = prev_end
+        if span[1] < span[0]:
+            span[1] = span[0]
+        prev_end = span[1]
+
+    # 未匹配的连续行：在前后已对齐行之间按文本长度分配时间
+    i = 0
+    while i < len(lines):
+        if spans[i] is not None:
+            i += 1
+            continue
+        j = i
+        while j < len(lines) and spans[j] is None:
+            j += 1
+        gap_start = spans[i - 1][1] if i > 0 else 0.0
+        gap_end = spans[j][0] if j < len(lines) else max(duration, gap_start)
+        weights = [max(len(lines[k].strip()), 1) for k in range(i, j)]
+        total = float(sum(weights))
+        cursor = gap_start
+        for k, weight in zip(range(i, j), weights):
+            length = (gap_end - gap_start) * weight / total
+            spans[k] = [cursor, cursor + length]
+            cursor += length
+        i = j
+
+    return [{"start": s[0], "end": s[1], "text": lines[k].strip()} for k, s in enumerate(spans)]
+
+def align(audio_path, transcript_path, model_size, language, device, output_path):
+    import torch
+
+    if device == "auto":
+        device = "cuda" if torch.cuda.is_available() else "cpu"
+    compute_type = "float16" if device == "cuda" else "int8"
+
+    with open(transcript_path, "r", encoding="utf-8") as f:
+        lines = json.load(f)
+
+    log("STATUS:loading")
+    model = WhisperModel(model_size, device=device, compute_type=compute_type)
+
+    log("STATUS:aligning")
+    # 文稿开头作为提示词，引导识别结果贴近已知文本
+    prompt = " ".join(line.strip() for line in lines)[:400]
+    segments, info = model.transcribe(
+        audio_path,
+        language=language if language != "auto" else None,
+        beam_size=5,
+        word_timestamps=True,
+        initial_prompt=prompt or None,
+        condition_on_previous_text=False,
+    )
+
+    words = []
+    for segment in segments:
+        words.extend(segment.words or [])
+        if info.duration:
+            log(f"PROGRESS:{min(segment.end / info.duration * 100, 95.0):.1f}")
+
+    result = {
+        "segments": align_lines(lines, char_timeline(words), info.duration or 0.0),
+        "language": info.language,
+        "duration": info.duration,
+    }
+
+    with open(output_path, "w", encoding="utf-8") as f:
+        json.dump(result, f, ensure_ascii=False, indent=2)
+
+    log("STATUS:completed")
+
+def main():
+    parser = argparse.ArgumentParser(description="Whisper 强制对齐")
+    parser.add_argument("--audio", required=True, help="音频文件路径")
+    parser.add_argument("--transcript", required=True, help="文稿 JSON 文件路径（字符串数组，每项一行）")
+    parser.add_argument("--model", default="base", help="模型大小")
+    parser.add_argument("--language", default="auto", help="语言代码")
+    parser.add_argument("--device", default="auto", help="设备: auto, cpu, cuda")
+    parser.add_argument("--output", required=True, help="输出 JSON 文件路径")
+
+    args = parser.parse_args()
+
+    try:
+        align(args.audio, args.transcript, args.model, args.language, args.device, args.output)
+    except Exception as e:
+        print(f"ERROR:{str(e)}", file=sys.stderr, flush=True)
+        sys.exit(1)
+
+if __name__ == "__main__":
+    main()
+"#;
+    
+    std::fs::write(&script_path, script_content)
+        .map_err(|e| format!("写入对齐脚本失败: {}", e))?;
+    
+    Ok(())
+}
+
+/// 强制对齐：为已知文稿的每一行生成时间轴（而非重新识别文本）
+/// 返回的字幕与 transcript_lines 一一对应，空行会被忽略
+pub async fn force_align(
+    audio_path: String,
+    transcript_lines: Vec<String>,
+    language: String,
+    model_size: String,
+) -> Result<Vec<SubtitleEntry>, String> {
+    let _operation = begin_operation("whisper", OperationKind::Transcribing);
+    reset_cancellation();
+    let model_size = expand_model_path(&model_size);
+    
+    let lines: Vec<String> = transcript_lines
+        .into_iter()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    if lines.is_empty() {
+        return Err("文稿为空".to_string());
+    }
+    
+    let env_status = check_whisper_env();
+    if !env_status.ready {
+        return Err("Whisper 环境未安装，请先安装环境".to_string());
+    }
+    
+    let _gpu_permit = crate::gpu_guard::acquire_gpu_permit(&get_whisper_active_env_type()).await?;
+    
+    let python_path = get_python_path()?;
+    let script_path = get_scripts_dir()?.join("whisper_align.py");
+    write_alignment_script()?;
+    
+    // 临时文件每次调用独占，函数返回时（包括出错）自动删除
+    let transcript_file = crate::temp_files::TempFileGuard(crate::temp_files::unique_temp_path("whisper_align_input", ".json"));
+    let output_file = crate::temp_files::TempFileGuard(crate::temp_files::unique_temp_path("whisper_align_result", ".json"));
+    let transcript_path = transcript_file.0.clone();
+    let output_path = output_file.0.clone();
+    
+    let transcript_json = serde_json::to_string(&lines)
+        .map_err(|e| format!("序列化文稿失败: {}", e))?;
+    std::fs::write(&transcript_path, transcript_json)
+        .map_err(|e| format!("写入文稿失败: {}", e))?;
+    
+    let device = if env_status.is_gpu { "cuda" } else { "cpu" };
+    log::info!(
+        "开始强制对齐: 音频文件={}, 模型=faster-whisper-{}, 语言={}, 行数={}",
+        audio_path, model_size, language, lines.len()
+    );
+    
+    let start_time = std::time::Instant::now();
+    let args = vec![
+        "-u".to_string(),
+        script_path.to_string_lossy().to_string(),
+        "--audio".to_string(), audio_path.clone(),
+        "--transcript".to_string(), transcript_path.to_string_lossy().to_string(),
+        "--model".to_string(), model_size.clone(),
+        "--language".to_string(), language,
+        "--device".to_string(), device.to_string(),
+        "--output".to_string(), output_path.to_string_lossy().to_string(),
+    ];
+    
+    // 启动进程后轮询取消标志，用户取消时结束对齐进程
+    let (status, stderr_output) = tauri::async_runtime::spawn_blocking(move || {
+        use std::io::Read;
+        use std::process::Stdio;
+        
+        let mut cmd = Command::new(&python_path);
+        cmd.args(&args).stdout(Stdio::null()).stderr(Stdio::piped());
+        
+        #[cfg(target_os = "windows")]
+        {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            cmd.creation_flags(CREATE_NO_WINDOW);
+        }
+        
+        let mut child = cmd.spawn().map_err(|e| format!("运行对齐脚本失败: {}", e))?;
+        let stderr = child.stderr.take();
+        let stderr_handle = std::thread::spawn(move || {
+            let mut output = String::new();
+            if let Some(mut stderr) = stderr {
+                let _ = stderr.read_to_string(&mut output);
+            }
+            output
+        });
+        
+        loop {
+            if is_cancelled() {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err("对齐已取消".to_string());
+            }
+            match child.try_wait() {
+                Ok(Some(status)) => {
+                    let stderr_output = stderr_handle.join().unwrap_or_default();
+                    return Ok((status, stderr_output));
+                }
+                Ok(None) => std::thread::sleep(std::time::Duration::from_millis(100)),
+                Err(e) => return Err(format!("等待对齐完成失败: {}", e)),
+            }
+        }
+    })
+    .await
+    .map_err(|e| format!("Task error: {:?}", e))??;
+    
+    drop(transcript_file);
+    
+    if !status.success() {
+        return Err(format!("对齐失败: {}", stderr_output));
+    }
+    
+    let result_json = std::fs::read_to_string(&output_path)
+        .map_err(|e| format!("读取对齐结果失败: {}", e))?;
+    drop(output_file);
+    
+    let result: TranscriptionResult = serde_json::from_str(&result_json)
+        .map_err(|e| format!("解析对齐结果失败: {}", e))?;
+    
+    let entries: Vec<SubtitleEntry> = result.segments
+        .iter()
+        .enumerate()
+        .map(|(i, seg)| SubtitleEntry {
+            id: (i + 1) as u32,
+            start_time: TimeStamp::from_millis((seg.start * 1000.0) as u64),
+            end_time: TimeStamp::from_millis((seg.end * 1000.0) as u64),
+            text: seg.text.clone(),
+            confidence: None,
+            events: None,
+        })
+        .collect();
+    
+    log::info!(
+        "强制对齐完成: 音频文件={}, 耗时={:.2}秒, 生成{}条字幕",
+        audio_path, start_time.elapsed().as_secs_f64(), entries.len()
+    );
+    
     Ok(entries)
 }
 
+/// 判断是否为自定义模型（HuggingFace 仓库 ID 如 "org/model"，或本地模型目录路径）
+fn is_custom_model(model: &str) -> bool {
+    model.contains('/') || model.contains('\\') || std::path::Path::new(model).is_absolute()
+}
+
+/// 判断是否为本地模型目录路径
+fn is_local_model_path(model: &str) -> bool {
+    let path = std::path::Path::new(model);
+    path.is_absolute() || model.starts_with("./") || model.starts_with("~/")
+}
+
+/// 将 ~/ 开头的本地模型路径展开为用户主目录下的绝对路径，其余模型名原样返回
+/// faster-whisper 不会展开 ~，必须在传给 Python 之前处理
+fn expand_model_path(model: &str) -> String {
+    match (model.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest).to_string_lossy().to_string(),
+        _ => model.to_string(),
+    }
+}
+
+/// 获取模型在 HuggingFace 缓存中的目录名
+/// 内置模型: models--Systran--faster-whisper-{name}，自定义仓库: models--{org}--{name}
+pub fn get_model_cache_dir_name(model_name: &str) -> String {
+    if is_custom_model(model_name) {
+        format!("models--{}", model_name.replace('/', "--"))
+    } else {
+        format!("models--Systran--faster-whisper-{}", model_name)
+    }
+}
+
+/// 内置模型的大致体积（字节），用于下载前的磁盘空间预检
+fn estimated_model_bytes(model_name: &str) -> Option<u64> {
+    const MB: u64 = 1024 * 1024;
+    match model_name {
+        "tiny" => Some(75 * MB),
+        "base" => Some(145 * MB),
+        "small" => Some(488 * MB),
+        "medium" => Some(1536 * MB),
+        "large-v2" | "large-v3" => Some(3174 * MB),
+        _ => None,
+    }
+}
+
+/// 下载前预检模型缓存目录所在磁盘的剩余空间
+/// 已下载的模型或大小未知的自定义仓库按 0 字节计算
+pub fn check_whisper_model_disk_space(model_name: &str) -> Result<crate::disk_space::DiskSpaceInfo, String> {
+    let required = if check_model_downloaded(model_name) {
+        0
+    } else {
+        estimated_model_bytes(model_name)
+            .map(|size| size.saturating_sub(get_whisper_partial_size(model_name)))
+            .unwrap_or(0)
+    };
+    crate::disk_space::check_disk_space(&get_whisper_model_dir()?, required)
+}
+
 /// 获取已下载的部分大小（用于断点续传显示）
 pub fn get_whisper_partial_size(model_name: &str) -> u64 {
     let home_dir = match dirs::home_dir() {
@@ -1163,7 +1756,7 @@ pub fn get_whisper_partial_size(model_name: &str) -> u64 {
     };
     
     let hub_dir = home_dir.join(".cache").join("huggingface").join("hub");
-    let model_dir_name = format!("models--Systran--faster-whisper-{}", model_name);
+    let model_dir_name = get_model_cache_dir_name(model_name);
     let model_path = hub_dir.join(&model_dir_name);
     
     if !model_path.exists() {
@@ -1222,7 +1815,7 @@ pub fn get_whisper_models() -> Vec<WhisperModelInfo> {
         ("large-v3", "~3.1 GB"),
     ];
     
-    models.iter().map(|(name, size)| {
+    let mut result: Vec<WhisperModelInfo> = models.iter().map(|(name, size)| {
         // 检查模型是否已下载（faster-whisper 会自动下载到 huggingface 缓存）
         let downloaded = check_model_downloaded(name);
         
@@ -1239,12 +1832,64 @@ pub fn get_whisper_models() -> Vec<WhisperModelInfo> {
             size: size.to_string(),
             downloaded,
             partial_size,
+            label: get_model_label("whisper", name),
+        }
+    }).collect();
+    
+    // 追加登记表中的自定义模型（HuggingFace 仓库或本地目录）
+    for model in get_registered_models("whisper") {
+        if result.iter().any(|m| m.name == model.name) {
+            continue;
         }
-    }).collect()
+        result.push(WhisperModelInfo {
+            downloaded: check_model_downloaded(&model.name),
+            size: if model.size > 0 { format_model_size(model.size) } else { "未知".to_string() },
+            partial_size: None,
+            label: model.label,
+            name: model.name,
+        });
+    }
+    
+    result
+}
+
+/// 将模型登记到模型登记表，使其出现在模型列表中
+fn register_whisper_model(model_name: &str) {
+    let (repo, path) = if is_local_model_path(model_name) {
+        (None, PathBuf::from(expand_model_path(model_name)))
+    } else {
+        let repo = if is_custom_model(model_name) {
+            model_name.to_string()
+        } else {
+            format!("Systran/faster-whisper-{}", model_name)
+        };
+        let path = get_whisper_model_dir()
+            .map(|dir| dir.join(get_model_cache_dir_name(model_name)))
+            .unwrap_or_default();
+        (Some(repo), path)
+    };
+    
+    let model = RegisteredModel {
+        name: model_name.to_string(),
+        repo,
+        size: get_whisper_partial_size(model_name),
+        path: path.to_string_lossy().to_string(),
+        backend: "whisper".to_string(),
+        label: None,
+    };
+    if let Err(e) = register_model(model) {
+        log::warn!("[Whisper] 登记模型失败: {}", e);
+    }
 }
 
 /// 检查模型是否已下载
 fn check_model_downloaded(model_name: &str) -> bool {
+    // 本地模型目录：直接检查模型文件
+    if is_local_model_path(model_name) {
+        let local_dir = PathBuf::from(expand_model_path(model_name));
+        return local_dir.join("model.bin").exists() && local_dir.join("config.json").exists();
+    }
+    
     let home_dir = match dirs::home_dir() {
         Some(dir) => dir,
         None => return false,
@@ -1254,7 +1899,8 @@ fn check_model_downloaded(model_name: &str) -> bool {
     
     // 检查方式1: HuggingFace Hub 缓存格式
     // 格式: ~/.cache/huggingface/hub/models--Systran--faster-whisper-{model_name}
-    let model_dir_name = format!("models--Systran--faster-whisper-{}", model_name);
+    // 自定义仓库: ~/.cache/huggingface/hub/models--{org}--{name}
+    let model_dir_name = get_model_cache_dir_name(model_name);
     let model_path = hub_dir.join(&model_dir_name);
     
     if model_path.exists() {
@@ -1336,10 +1982,14 @@ pub fn open_whisper_model_dir() -> Result<(), String> {
 
 /// 删除 Whisper 模型
 pub fn delete_whisper_model(model_name: &str) -> Result<String, String> {
+    if is_local_model_path(model_name) {
+        return Err("本地模型目录不由应用管理，请手动删除".to_string());
+    }
+    
     let home_dir = dirs::home_dir()
         .ok_or_else(|| "无法获取用户目录".to_string())?;
     
-    let model_dir_name = format!("models--Systran--faster-whisper-{}", model_name);
+    let model_dir_name = get_model_cache_dir_name(model_name);
     let model_path = home_dir
         .join(".cache")
         .join("huggingface")
@@ -1353,15 +2003,27 @@ pub fn delete_whisper_model(model_name: &str) -> Result<String, String> {
     std::fs::remove_dir_all(&model_path)
         .map_err(|e| format!("删除模型失败: {}", e))?;
     
+    // 自定义模型删除后不再出现在列表中
+    if is_custom_model(model_name) {
+        let _ = forget_model("whisper", model_name);
+    }
+    
     Ok(format!("模型 {} 已删除", model_name))
 }
 
 
 /// 下载 Whisper 模型（使用 Python 脚本预下载）
+/// model_name: 内置模型大小（tiny/base/...）或任意 HuggingFace 仓库 ID（如 "org/faster-whisper-custom"）
 pub async fn download_whisper_model(model_name: &str, window: Window) -> Result<String, String> {
     use std::io::{BufRead, BufReader};
     use std::process::Stdio;
     
+    let _operation = begin_operation("whisper", OperationKind::Downloading);
+    
+    if is_local_model_path(model_name) {
+        return Err("本地模型目录无需下载".to_string());
+    }
+    
     // 检查环境是否就绪
     let env_status = check_whisper_env();
     if !env_status.ready {
@@ -1375,6 +2037,14 @@ pub async fn download_whisper_model(model_name: &str, window: Window) -> Result<
     // 确保下载脚本存在
     write_download_script()?;
     
+    // 预检磁盘空间（自定义仓库大小未知，不做检查）
+    if let Some(size) = estimated_model_bytes(model_name) {
+        crate::disk_space::ensure_disk_space(
+            &get_whisper_model_dir()?,
+            size.saturating_sub(get_whisper_partial_size(model_name)),
+        )?;
+    }
+    
     // 发送初始进度
     let _ = window.emit("whisper-model-progress", WhisperProgress {
         progress: 0.0,
@@ -1382,6 +2052,8 @@ pub async fn download_whisper_model(model_name: &str, window: Window) -> Result<
         status: "downloading".to_string(),
     });
     
+    let download_env = crate::download_config::download_env();
+    
     // 运行 Python 脚本下载模型，实时读取输出
     #[cfg(target_os = "windows")]
     let mut child = {
@@ -1393,6 +2065,7 @@ pub async fn download_whisper_model(model_name: &str, window: Window) -> Result<
                 download_script_path.to_str().unwrap(),
                 "--model", model_name,
             ])
+            .envs(download_env)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .creation_flags(CREATE_NO_WINDOW)
@@ -1407,6 +2080,7 @@ pub async fn download_whisper_model(model_name: &str, window: Window) -> Result<
             download_script_path.to_str().unwrap(),
             "--model", model_name,
         ])
+        .envs(download_env)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
@@ -1478,6 +2152,8 @@ pub async fn download_whisper_model(model_name: &str, window: Window) -> Result<
         status: "completed".to_string(),
     });
     
+    register_whisper_model(model_name);
+    
     Ok(format!("模型 {} 下载成功", model_name))
 }
 
@@ -1512,11 +2188,13 @@ MODEL_REPOS = {
 }
 
 def download_model(model_size: str):
-    """下载指定大小的模型"""
-    if model_size not in MODEL_REPOS:
-        raise ValueError(f"未知的模型大小: {model_size}，可选: {list(MODEL_REPOS.keys())}")
-    
-    repo_id = MODEL_REPOS[model_size]
+    """下载指定大小的模型，或任意 HuggingFace 仓库（org/name 格式）"""
+    if model_size in MODEL_REPOS:
+        repo_id = MODEL_REPOS[model_size]
+    elif "/" in model_size:
+        repo_id = model_size
+    else:
+        raise ValueError(f"未知的模型大小: {model_size}，可选: {list(MODEL_REPOS.keys())} 或 HuggingFace 仓库 ID")
     
     print(f"正在下载 {model_size} 模型 ({repo_id})...", flush=True)
     print("PROGRESS:0", flush=True)
@@ -1548,7 +2226,7 @@ def download_model(model_size: str):
 
 def main():
     parser = argparse.ArgumentParser(description="下载 Whisper 模型")
-    parser.add_argument("--model", required=True, help="模型大小: tiny, base, small, medium, large-v2, large-v3")
+    parser.add_argument("--model", required=True, help="模型大小: tiny, base, small, medium, large-v2, large-v3，或 HuggingFace 仓库 ID")
     
     args = parser.parse_args()
     
@@ -1568,3 +2246,80 @@ if __name__ == "__main__":
     
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(start: f64, end: f64, text: &str, probability: Option<f32>) -> TranscriptionWord {
+        TranscriptionWord { start, end, word: text.to_string(), probability }
+    }
+
+    fn segment(words: Vec<TranscriptionWord>) -> TranscriptionSegment {
+        TranscriptionSegment { start: 0.0, end: 0.0, text: String::new(), confidence: None, words }
+    }
+
+    fn sample_segments() -> Vec<TranscriptionSegment> {
+        vec![
+            segment(vec![
+                word(0.0, 0.5, " Hello", Some(0.9)),
+                word(0.5, 1.0, " world", Some(0.7)),
+                word(1.0, 1.1, " ", None),
+                word(1.2, 1.6, " again", None),
+            ]),
+            segment(vec![word(2.0, 3.0, " supercalifragilistic", Some(0.5))]),
+        ]
+    }
+
+    #[test]
+    fn test_group_words_one_entry_per_word() {
+        let entries = group_words_into_entries(&sample_segments(), None);
+        let texts: Vec<&str> = entries.iter().map(|e| e.text.as_str()).collect();
+        // Whitespace-only words are dropped
+        assert_eq!(texts, vec!["Hello", "world", "again", "supercalifragilistic"]);
+        assert_eq!(entries.iter().map(|e| e.id).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(entries[1].start_time.to_millis(), 500);
+        assert_eq!(entries[1].end_time.to_millis(), 1000);
+    }
+
+    #[test]
+    fn test_group_words_by_max_chars() {
+        let entries = group_words_into_entries(&sample_segments(), Some(12));
+        assert_eq!(entries.len(), 3);
+
+        assert_eq!(entries[0].text, "Hello world");
+        assert_eq!(entries[0].start_time.to_millis(), 0);
+        assert_eq!(entries[0].end_time.to_millis(), 1000);
+        assert!((entries[0].confidence.unwrap() - 0.8).abs() < 1e-6);
+
+        // No probabilities in the group: no confidence
+        assert_eq!(entries[1].text, "again");
+        assert_eq!(entries[1].confidence, None);
+
+        // A word longer than the limit gets an entry of its own instead of being split
+        assert_eq!(entries[2].text, "supercalifragilistic");
+        assert_eq!(entries[2].start_time.to_millis(), 2000);
+        assert_eq!(entries[2].end_time.to_millis(), 3000);
+    }
+
+    #[test]
+    fn test_is_retryable_failure() {
+        assert!(is_retryable_failure("转录失败: RuntimeError: CUDA failed with error out of memory"));
+        assert!(is_retryable_failure("转录失败: RuntimeError: Unable to open file 'model.bin' in model '/models/large-v3'"));
+        assert!(is_retryable_failure("转录失败: Could not load library libcudnn_ops_infer.so.8"));
+        assert!(is_retryable_failure("转录失败:"));
+        // A missing or unreadable input file is not fixed by a smaller model
+        assert!(!is_retryable_failure("转录失败: av.error.FileNotFoundError: [Errno 2] No such file or directory: 'a.mp3'"));
+        assert!(!is_retryable_failure("转录失败: RuntimeError: unable to open input 'a.mp3'"));
+        assert!(!is_retryable_failure("转录失败: Failed to load audio: a.mp3"));
+    }
+
+    #[test]
+    fn test_expand_model_path() {
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(expand_model_path("~/models/whisper"), home.join("models/whisper").to_string_lossy());
+        assert_eq!(expand_model_path("/opt/models/whisper"), "/opt/models/whisper");
+        assert_eq!(expand_model_path("large-v3"), "large-v3");
+        assert_eq!(expand_model_path("org/model~/x"), "org/model~/x");
+    }
+}
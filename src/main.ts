@@ -337,6 +337,10 @@ const globalExportSubtitles = async (format: string) => {
       srt: { ext: 'srt', name: 'SRT 字幕文件' },
       markdown: { ext: 'md', name: 'Markdown 文件' },
       fcpxml: { ext: 'fcpxml', name: 'Final Cut Pro XML' },
+      audacity: { ext: 'txt', name: 'Audacity 标签文件' },
+      lrc: { ext: 'lrc', name: 'LRC 歌词文件' },
+      csv: { ext: 'csv', name: 'CSV 表格文件' },
+      ttml: { ext: 'ttml', name: 'TTML 字幕文件' },
     }
     
     const config = formatConfig[format]
@@ -475,6 +479,14 @@ const globalExportSubtitles = async (format: string) => {
       await invoke('write_srt', { filePath, entries })
     } else if (format === 'markdown') {
       await invoke('export_markdown', { filePath, entries })
+    } else if (format === 'audacity') {
+      await invoke('export_audacity_labels', { filePath, entries })
+    } else if (format === 'lrc') {
+      await invoke('export_lrc', { filePath, entries })
+    } else if (format === 'csv') {
+      await invoke('export_csv', { filePath, entries })
+    } else if (format === 'ttml') {
+      await invoke('export_ttml', { filePath, entries })
     }
     
     ElMessage.success(`已导出为 ${config.ext.toUpperCase()} 格式`)
@@ -506,6 +518,10 @@ const globalShowExportDialog = async () => {
       { value: 'srt', label: 'SRT', desc: 'SRT 字幕' },
       { value: 'markdown', label: 'Markdown', desc: '带时间戳的文档' },
       { value: 'fcpxml', label: 'FCPXML', desc: 'Final Cut Pro' },
+      { value: 'audacity', label: 'Audacity', desc: 'Audacity 标签轨' },
+      { value: 'lrc', label: 'LRC', desc: '歌词同步' },
+      { value: 'csv', label: 'CSV', desc: '表格审校' },
+      { value: 'ttml', label: 'TTML', desc: '广播电视 (DFXP)' },
     ]
     
     const defaultFormat = configStore.defaultExportFormat
@@ -529,6 +529,17 @@ export const useAudioStore = defineStore('audio', () => {
       waveformProgress.value = 0
     }
 
+    // 后端解码得到的精确时长（毫秒），Howler 尚未加载完成时用它填充
+    const durationUnlisten = await listen<number>('waveform-duration', (event) => {
+      const seconds = event.payload / 1000
+      if (seconds > 0 && playerState.value.duration === 0) {
+        playerState.value.duration = seconds
+        if (tabManager.activeTab) {
+          tabManager.activeTab.audio.duration = seconds
+        }
+      }
+    })
+
     try {
       const targetSamples = 20000
 
@@ -555,6 +566,7 @@ export const useAudioStore = defineStore('audio', () => {
         generateTime: `${waveformDuration}ms`,
       })
     } finally {
+      durationUnlisten()
       if (progressSimulationTimer) {
         clearInterval(progressSimulationTimer)
         progressSimulationTimer = null
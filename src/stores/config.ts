@@ -1,5 +1,6 @@
 import { defineStore } from 'pinia'
 import { ref, computed } from 'vue'
+import { invoke } from '@tauri-apps/api/core'
 import type { EditorConfig, KeyBinding } from '@/types/subtitle'
 import logger from '@/utils/logger'
 
@@ -269,6 +270,22 @@ export const useConfigStore = defineStore('config', () => {
         logger.error('最近文件列表加载失败', { error: String(error) })
       }
     }
+    loadRecentFilesFromBackend()
+  }
+
+  // 从后端读取持久化的最近文件列表（已不存在的文件会被后端移除）
+  // 升级后首次启动时后端列表为空，用 localStorage 中已有的列表初始化，避免被清空
+  const loadRecentFilesFromBackend = async () => {
+    try {
+      let files = await invoke<RecentFile[]>('get_recent_files')
+      if (files.length === 0 && recentFiles.value.length > 0) {
+        files = await invoke<RecentFile[]>('seed_recent_files', { files: recentFiles.value })
+      }
+      recentFiles.value = files
+      localStorage.setItem('vosub-recent-files', JSON.stringify(recentFiles.value))
+    } catch (error) {
+      logger.error('读取后端最近文件列表失败', { error: String(error) })
+    }
   }
 
   // 添加最近文件
@@ -292,18 +309,27 @@ export const useConfigStore = defineStore('config', () => {
     
     // 保存到本地存储
     localStorage.setItem('vosub-recent-files', JSON.stringify(recentFiles.value))
+    invoke('add_recent_file', { path: filePath }).catch((error) => {
+      logger.error('保存最近文件失败', { error: String(error) })
+    })
   }
 
   // 清空最近文件
   const clearRecentFiles = () => {
     recentFiles.value = []
     localStorage.removeItem('vosub-recent-files')
+    invoke('clear_recent_files').catch((error) => {
+      logger.error('清空最近文件失败', { error: String(error) })
+    })
   }
 
   // 删除单个最近文件
   const removeRecentFile = (filePath: string) => {
     recentFiles.value = recentFiles.value.filter(f => f.path !== filePath)
     localStorage.setItem('vosub-recent-files', JSON.stringify(recentFiles.value))
+    invoke('remove_recent_file', { path: filePath }).catch((error) => {
+      logger.error('移除最近文件失败', { error: String(error) })
+    })
   }
 
   // 初始化时加载配置
@@ -10,6 +10,7 @@ import type { TimeStamp } from './subtitle'
 export interface FireRedEnvInfo {
   installed: boolean
   ready: boolean
+  broken: boolean // 依赖已安装但 Python 解释器无法启动
 }
 
 /**
@@ -45,6 +46,8 @@ export interface CorrectionEntry {
   original: string
   corrected: string
   has_diff: boolean
+  /** 识别置信度（0-1），识别失败时为空 */
+  confidence?: number | null
 }
 
 /**
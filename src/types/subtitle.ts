@@ -20,6 +20,8 @@ export interface SubtitleEntry {
   startTime: TimeStamp // 开始时间
   endTime: TimeStamp // 结束时间
   text: string // 字幕文本
+  confidence?: number // 识别置信度（0-1，由转录引擎提供）
+  events?: string[] // 情绪/音频事件标签（如 laughter、happy，由 SenseVoice 提供）
   isEditing?: boolean // 是否正在编辑
   hasConflict?: boolean // 是否存在时间冲突
   trackNumber?: number // 轨道号 (0=默认轨道, 1=冲突轨道)
@@ -47,6 +49,7 @@ export interface SRTFile {
   path: string // 文件路径
   entries: SubtitleEntry[] // 字幕条目列表
   encoding?: string // 文件编码
+  language?: string // 自动检测的语言代码（zh/en/ja/ko/yue）
 }
 
 /**